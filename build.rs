@@ -284,6 +284,12 @@ mod build_tesseract {
             },
         );
 
+        build_shim(
+            &tesseract_install_dir.join("include"),
+            &tesseract_dir.join("src"),
+            &leptonica_include_dir,
+        );
+
         // --- Start of new logic for bundling Tesseract artifacts with Tauri ---
         // Change tesseract_bundle_root to point to Kompres-rs/src-tauri/external/tesseract-bundle
         let tesseract_bundle_root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
@@ -480,6 +486,57 @@ mod build_tesseract {
         download_tessdata(&project_dir);
     }
 
+    /// Compiles the C++ sources in `shim/`, which expose parts of the Tesseract C++ API that
+    /// the C API lacks.
+    fn build_shim(
+        tesseract_include_dir: &Path,
+        tesseract_source_dir: &Path,
+        leptonica_include_dir: &Path,
+    ) {
+        let shim_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("shim");
+        println!("cargo:rerun-if-changed={}", shim_dir.display());
+
+        let sources: Vec<PathBuf> = glob::glob(&shim_dir.join("*.cpp").to_string_lossy())
+            .expect("Failed to read shim directory")
+            .filter_map(|entry| entry.ok())
+            .collect();
+
+        let mut build = cc::Build::new();
+        build
+            .cpp(true)
+            .files(&sources)
+            .include(tesseract_include_dir)
+            .include(leptonica_include_dir)
+            // Must match the definitions Tesseract was built with, which change the layout
+            // of some of its internal classes.
+            .define("GRAPHICS_DISABLED", None)
+            // The C++ standard library is linked by set_os_specific_link_flags.
+            .cpp_link_stdlib(None);
+        // Internal headers, such as the page results edited by the mutable iterator shim,
+        // are only found in the source tree.
+        for entry in fs::read_dir(tesseract_source_dir).expect("Failed to read Tesseract sources") {
+            let path = entry.expect("Failed to read Tesseract sources").path();
+            if path.is_dir() {
+                build.include(path);
+            }
+        }
+        if cfg!(target_os = "windows") {
+            build.flag("/EHsc").flag("/std:c++17");
+        } else {
+            build.flag("-std=c++17");
+            // Match the standard library Tesseract was built against.
+            if cfg!(target_os = "macos")
+                || cfg!(target_env = "musl")
+                || env::var("CC")
+                    .map(|cc| cc.contains("clang"))
+                    .unwrap_or(false)
+            {
+                build.flag("-stdlib=libc++");
+            }
+        }
+        build.compile("tesseract_rs_shim");
+    }
+
     fn get_os_specific_config() -> (String, Vec<(String, String)>) {
         let mut cmake_cxx_flags = String::new();
         let mut additional_defines = Vec::new();
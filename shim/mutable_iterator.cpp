@@ -0,0 +1,126 @@
+// Word edits that the Tesseract C API does not expose.
+//
+// The handle is the `TessMutableIterator` returned by `TessBaseAPIGetMutableIterator`, which
+// is a `tesseract::MutableIterator` on the C++ side. Edits change the page results in place,
+// so the text, hOCR, PDF and other outputs generated from them afterwards include the edits.
+// The page result classes are internal to Tesseract and are read from its source tree.
+
+#include "mutableiterator.h"
+#include "pageres.h"
+
+#include <vector>
+
+namespace {
+
+using tesseract::BLOB_CHOICE;
+using tesseract::BoxWord;
+using tesseract::MutableIterator;
+using tesseract::PAGE_RES_IT;
+using tesseract::TBOX;
+using tesseract::UNICHAR_ID;
+using tesseract::UNICHARSET;
+using tesseract::WERD_RES;
+
+// Splits a word box into `count` boxes of equal width, one per character.
+BoxWord *split_box(const TBOX &box, unsigned count) {
+  auto *boxes = new BoxWord;
+  const int width = box.width();
+  for (unsigned i = 0; i < count; ++i) {
+    const int left = box.left() + width * static_cast<int>(i) / static_cast<int>(count);
+    const int right = box.left() + width * static_cast<int>(i + 1) / static_cast<int>(count);
+    boxes->InsertBox(i, TBOX(left, box.bottom(), right, box.top()));
+  }
+  return boxes;
+}
+
+// Moves the iterator back onto `word` so that it reloads the symbols it caches for it.
+void reload_word(MutableIterator *handle, const WERD_RES *word) {
+  handle->RestartRow();
+  while (handle->PageResIt()->word() != word &&
+         handle->tesseract::PageIterator::Next(tesseract::RIL_WORD)) {
+  }
+}
+
+}  // namespace
+
+extern "C" {
+
+// Replaces the text of the current word, keeping its confidence. The symbol boxes are kept
+// when the number of characters is unchanged and split evenly from the word box otherwise.
+// Returns 0 if the iterator is not on a recognized word or the text cannot be encoded with
+// the character set the word was recognized with.
+int TessRsMutableIteratorReplaceWord(MutableIterator *handle, const char *text) {
+  if (handle == nullptr || text == nullptr) {
+    return 0;
+  }
+  WERD_RES *word = handle->PageResIt()->word();
+  if (word == nullptr || word->best_choice == nullptr || word->uch_set == nullptr) {
+    return 0;
+  }
+  const UNICHARSET &unicharset = *word->uch_set;
+  std::vector<UNICHAR_ID> encoding;
+  if (!unicharset.encode_string(text, true, &encoding, nullptr, nullptr) || encoding.empty()) {
+    return 0;
+  }
+
+  const auto count = static_cast<unsigned>(encoding.size());
+  if (word->box_word == nullptr || word->box_word->length() != count) {
+    const TBOX box = word->box_word != nullptr ? word->box_word->bounding_box()
+                                               : word->word->bounding_box();
+    delete word->box_word;
+    word->box_word = split_box(box, count);
+  }
+
+  const float certainty = word->best_choice->certainty();
+  std::vector<BLOB_CHOICE *> choices;
+  choices.reserve(count);
+  for (UNICHAR_ID id : encoding) {
+    choices.push_back(new BLOB_CHOICE(id, 0.0f, certainty, unicharset.get_script(id), 0.0f,
+                                      0.0f, 0.0f, tesseract::BCC_FAKE));
+  }
+  // Rebuilds the ratings, best choice, reject map and segmentation from one choice per box.
+  word->FakeClassifyWord(count, choices.data());
+  // The LSTM candidates no longer describe the word.
+  word->timesteps.clear();
+  word->segmented_timesteps.clear();
+  word->CTC_symbol_choices.clear();
+
+  reload_word(handle, word);
+  return 1;
+}
+
+// Removes the word the iterator is on from the page results and moves the iterator to the
+// word that followed it in reading order. Returns 0 if the iterator is not on a word;
+// otherwise sets `has_next` to 0 when the removed word was the last one on the page.
+int TessRsMutableIteratorDeleteWord(MutableIterator *handle, int *has_next) {
+  if (handle == nullptr || has_next == nullptr) {
+    return 0;
+  }
+  const WERD_RES *word = handle->PageResIt()->word();
+  if (word == nullptr) {
+    return 0;
+  }
+  *has_next = handle->Next(tesseract::RIL_WORD) ? 1 : 0;
+  const WERD_RES *next = *has_next ? handle->PageResIt()->word() : nullptr;
+
+  PAGE_RES_IT page_it(handle->PageResIt()->page_res);
+  for (page_it.restart_page(); page_it.word() != nullptr; page_it.forward()) {
+    if (page_it.word() == word) {
+      page_it.DeleteCurrentWord();
+      break;
+    }
+  }
+
+  // The iterator's position may refer to the removed word, so find the next word again.
+  handle->Begin();
+  if (*has_next) {
+    while (handle->PageResIt()->word() != next && handle->Next(tesseract::RIL_WORD)) {
+    }
+  } else {
+    while (handle->Next(tesseract::RIL_WORD)) {
+    }
+  }
+  return 1;
+}
+
+}  // extern "C"
@@ -0,0 +1,62 @@
+// Result iterator accessors that the Tesseract C API does not expose.
+//
+// The handle is the `TessResultIterator` returned by `TessBaseAPIGetIterator`, which is a
+// `tesseract::ResultIterator` on the C++ side.
+
+#include <tesseract/resultiterator.h>
+
+#include <cstddef>
+
+namespace {
+
+using Timesteps = std::vector<std::vector<std::vector<std::pair<const char *, float>>>>;
+
+const Timesteps *raw_timesteps(const tesseract::ResultIterator *handle) {
+  return handle == nullptr ? nullptr : handle->GetRawLSTMTimesteps();
+}
+
+}  // namespace
+
+extern "C" {
+
+// Returns the number of symbols of the current word with LSTM timesteps, or -1 if the
+// iterator is not on a word.
+int TessRsResultIteratorLSTMSymbolCount(const tesseract::ResultIterator *handle) {
+  const Timesteps *symbols = raw_timesteps(handle);
+  return symbols == nullptr ? -1 : static_cast<int>(symbols->size());
+}
+
+// Returns the number of timesteps of a symbol of the current word.
+int TessRsResultIteratorLSTMTimestepCount(const tesseract::ResultIterator *handle, int symbol) {
+  const Timesteps *symbols = raw_timesteps(handle);
+  if (symbols == nullptr || symbol < 0 || static_cast<size_t>(symbol) >= symbols->size()) {
+    return 0;
+  }
+  return static_cast<int>((*symbols)[symbol].size());
+}
+
+// Returns the number of candidates of a timestep of a symbol of the current word.
+int TessRsResultIteratorLSTMChoiceCount(const tesseract::ResultIterator *handle, int symbol,
+                                        int timestep) {
+  if (timestep < 0 || timestep >= TessRsResultIteratorLSTMTimestepCount(handle, symbol)) {
+    return 0;
+  }
+  return static_cast<int>((*raw_timesteps(handle))[symbol][timestep].size());
+}
+
+// Returns the text of a candidate, owned by the engine, and stores its probability, or
+// returns null if the indices are out of range.
+const char *TessRsResultIteratorLSTMChoice(const tesseract::ResultIterator *handle, int symbol,
+                                           int timestep, int choice, float *probability) {
+  if (choice < 0 || choice >= TessRsResultIteratorLSTMChoiceCount(handle, symbol, timestep)) {
+    return nullptr;
+  }
+  const std::pair<const char *, float> &candidate =
+      (*raw_timesteps(handle))[symbol][timestep][choice];
+  if (probability != nullptr) {
+    *probability = candidate.second;
+  }
+  return candidate.first;
+}
+
+}  // extern "C"
@@ -0,0 +1,205 @@
+// Output streams for result renderers that write to Rust instead of to a file.
+//
+// The renderers write through non-virtual methods to a `FILE*` they open themselves from the
+// output base. A renderer created with the output base "-" writes to stdout until its stream
+// is replaced with one that passes every write to a callback. Where the C library cannot
+// create such a stream, an anonymous temporary file is used instead and its contents are
+// passed on whenever the output is flushed.
+
+#include <tesseract/renderer.h>
+
+#include <cstddef>
+#include <cstdio>
+
+#if defined(__APPLE__) || defined(__ANDROID__) || defined(__FreeBSD__) || defined(__NetBSD__) || \
+    defined(__OpenBSD__) || defined(__DragonFly__)
+#define TESS_RS_FUNOPEN 1
+#elif defined(__linux__) || defined(__GLIBC__)
+#define TESS_RS_FOPENCOOKIE 1
+#else
+#include <map>
+#include <mutex>
+#include <vector>
+#endif
+
+namespace {
+
+using tesseract::TessResultRenderer;
+
+// Writes `size` bytes to the Rust writer behind `context`, returning 0 on failure.
+using WriteFn = int (*)(void *context, const char *data, size_t size);
+
+struct Sink {
+  WriteFn write;
+  void *context;
+};
+
+// The output stream is private to TessResultRenderer. Explicit template instantiations may
+// name private members, which gives access to it without changing the Tesseract headers.
+using FileMember = FILE *TessResultRenderer::*;
+FileMember output_member();
+
+template <FileMember Member>
+struct OutputAccess {
+  friend FileMember output_member() {
+    return Member;
+  }
+};
+template struct OutputAccess<&TessResultRenderer::fout_>;
+
+FILE *&output_of(TessResultRenderer *renderer) {
+  return renderer->*output_member();
+}
+
+#if defined(TESS_RS_FOPENCOOKIE)
+
+ssize_t cookie_write(void *cookie, const char *data, size_t size) {
+  auto *sink = static_cast<Sink *>(cookie);
+  return sink->write(sink->context, data, size) != 0 ? static_cast<ssize_t>(size) : -1;
+}
+
+int cookie_close(void *cookie) {
+  delete static_cast<Sink *>(cookie);
+  return 0;
+}
+
+FILE *open_stream(Sink *sink) {
+  cookie_io_functions_t functions = {nullptr, cookie_write, nullptr, cookie_close};
+  FILE *stream = fopencookie(sink, "w", functions);
+  if (stream == nullptr) {
+    delete sink;
+  }
+  return stream;
+}
+
+void flush_stream(FILE *stream) {
+  std::fflush(stream);
+}
+
+void release_stream(FILE *stream) {
+  std::fflush(stream);
+}
+
+#elif defined(TESS_RS_FUNOPEN)
+
+int funopen_write(void *cookie, const char *data, int size) {
+  auto *sink = static_cast<Sink *>(cookie);
+  return sink->write(sink->context, data, static_cast<size_t>(size)) != 0 ? size : -1;
+}
+
+int funopen_close(void *cookie) {
+  delete static_cast<Sink *>(cookie);
+  return 0;
+}
+
+FILE *open_stream(Sink *sink) {
+  FILE *stream = funopen(sink, nullptr, funopen_write, nullptr, funopen_close);
+  if (stream == nullptr) {
+    delete sink;
+  }
+  return stream;
+}
+
+void flush_stream(FILE *stream) {
+  std::fflush(stream);
+}
+
+void release_stream(FILE *stream) {
+  std::fflush(stream);
+}
+
+#else
+
+// A temporary file and the number of its bytes already passed to the sink.
+struct Spool {
+  Sink sink;
+  long copied;
+};
+
+std::mutex spools_lock;
+std::map<FILE *, Spool> spools;
+
+FILE *open_stream(Sink *sink) {
+  FILE *stream = std::tmpfile();
+  if (stream != nullptr) {
+    std::lock_guard<std::mutex> guard(spools_lock);
+    spools[stream] = Spool{*sink, 0};
+  }
+  delete sink;
+  return stream;
+}
+
+// Passes the bytes written since the last call on to the sink.
+void flush_stream(FILE *stream) {
+  std::fflush(stream);
+  std::lock_guard<std::mutex> guard(spools_lock);
+  auto spool = spools.find(stream);
+  if (spool == spools.end()) {
+    return;
+  }
+  const long end = std::ftell(stream);
+  if (end <= spool->second.copied || std::fseek(stream, spool->second.copied, SEEK_SET) != 0) {
+    return;
+  }
+  std::vector<char> bytes(static_cast<size_t>(end - spool->second.copied));
+  const size_t read = std::fread(bytes.data(), 1, bytes.size(), stream);
+  std::fseek(stream, end, SEEK_SET);
+  if (read > 0 && spool->second.sink.write(spool->second.sink.context, bytes.data(), read) != 0) {
+    spool->second.copied += static_cast<long>(read);
+  }
+}
+
+void release_stream(FILE *stream) {
+  flush_stream(stream);
+  std::lock_guard<std::mutex> guard(spools_lock);
+  spools.erase(stream);
+}
+
+#endif
+
+}  // namespace
+
+extern "C" {
+
+// Replaces the output of a renderer created with the output base "-" with a stream passing
+// everything written to `write`. Returns 0 if the renderer writes to a file or the stream
+// cannot be created.
+int TessRsResultRendererSetOutput(TessResultRenderer *renderer, WriteFn write, void *context) {
+  if (renderer == nullptr || write == nullptr) {
+    return 0;
+  }
+  FILE *&output = output_of(renderer);
+  if (output != stdout) {
+    return 0;
+  }
+  FILE *stream = open_stream(new Sink{write, context});
+  if (stream == nullptr) {
+    return 0;
+  }
+  output = stream;
+  return 1;
+}
+
+// Passes everything written so far by a renderer and those inserted after it to their
+// callbacks.
+void TessRsResultRendererFlushOutput(TessResultRenderer *renderer) {
+  for (; renderer != nullptr; renderer = renderer->next()) {
+    FILE *output = output_of(renderer);
+    if (output != nullptr && output != stdout) {
+      flush_stream(output);
+    }
+  }
+}
+
+// Flushes the output of a renderer and those inserted after it before they are deleted, after
+// which the callbacks are no longer called.
+void TessRsResultRendererReleaseOutput(TessResultRenderer *renderer) {
+  for (; renderer != nullptr; renderer = renderer->next()) {
+    FILE *output = output_of(renderer);
+    if (output != nullptr && output != stdout) {
+      release_stream(output);
+    }
+  }
+}
+
+}  // extern "C"
@@ -0,0 +1,294 @@
+use crate::error::{Result, TesseractError};
+use crate::page::BoundingBox;
+use roxmltree::Node;
+use std::path::Path;
+
+/// The `Layout` of an ALTO document, holding one or more pages.
+///
+/// Reads both the fragment returned by `TesseractAPI::get_alto_text` and complete files
+/// written by the ALTO renderer. Tesseract wraps the text blocks of each layout block in a
+/// `ComposedBlock`; these groups are flattened into the page's list of text blocks.
+///
+/// ```no_run
+/// use tesseract_rs::AltoLayout;
+///
+/// let layout = AltoLayout::read("scan.xml")?;
+/// for string in layout.pages.iter().flat_map(|page| page.strings()) {
+///     println!("{} {:?} {:?}", string.content, string.bbox, string.confidence);
+/// }
+/// # Ok::<(), tesseract_rs::TesseractError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AltoLayout {
+    pub pages: Vec<AltoPage>,
+}
+
+/// A page (`Page`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AltoPage {
+    pub id: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    /// Zero-based image number from the `PHYSICAL_IMG_NR` attribute.
+    pub physical_image_number: Option<u32>,
+    pub text_blocks: Vec<AltoTextBlock>,
+}
+
+/// A block of text (`TextBlock`), one per Tesseract paragraph.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AltoTextBlock {
+    pub id: Option<String>,
+    pub bbox: BoundingBox,
+    pub lines: Vec<AltoTextLine>,
+}
+
+/// A line of text (`TextLine`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AltoTextLine {
+    pub id: Option<String>,
+    pub bbox: BoundingBox,
+    pub strings: Vec<AltoString>,
+}
+
+/// A word (`String`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AltoString {
+    pub id: Option<String>,
+    /// Text of the word from the `CONTENT` attribute.
+    pub content: String,
+    pub bbox: BoundingBox,
+    /// Word confidence (0-1) from the `WC` attribute.
+    pub confidence: Option<f32>,
+}
+
+impl AltoLayout {
+    /// Parses ALTO XML.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A complete ALTO document or one or more `Page` elements.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed layout if successful, otherwise returns a `ParseError`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let trimmed = text.trim_start();
+        let wrapped;
+        // `get_alto_text` returns bare page elements, which need a common root.
+        let source = if trimmed.starts_with("<?xml") || trimmed.starts_with("<alto") {
+            text
+        } else {
+            wrapped = format!("<Layout>{}</Layout>", text);
+            &wrapped
+        };
+        let document =
+            roxmltree::Document::parse(source).map_err(|e| parse_error(e.to_string()))?;
+        let pages = document
+            .descendants()
+            .filter(|node| is_element(node, "Page"))
+            .map(|node| parse_page(&node))
+            .collect::<Result<_>>()?;
+        Ok(AltoLayout { pages })
+    }
+
+    /// Reads an ALTO file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the ALTO file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed layout if successful, otherwise returns an error.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| TesseractError::FileError {
+            path: path.display().to_string(),
+            output: e.to_string(),
+        })?;
+        Self::parse(&text)
+    }
+}
+
+impl AltoPage {
+    /// Iterates over every word on the page in reading order.
+    pub fn strings(&self) -> impl Iterator<Item = &AltoString> {
+        self.text_blocks
+            .iter()
+            .flat_map(|block| &block.lines)
+            .flat_map(|line| &line.strings)
+    }
+}
+
+impl AltoTextLine {
+    /// Gets the text of the line.
+    ///
+    /// # Returns
+    ///
+    /// Returns the words of the line separated by single spaces.
+    pub fn text(&self) -> String {
+        self.strings
+            .iter()
+            .map(|string| string.content.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn parse_error(message: String) -> TesseractError {
+    TesseractError::ParseError {
+        format: "ALTO",
+        message,
+    }
+}
+
+fn is_element(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn id(node: &Node) -> Option<String> {
+    node.attribute("ID").map(str::to_owned)
+}
+
+fn number(node: &Node, name: &str) -> Result<Option<f32>> {
+    node.attribute(name)
+        .map(|value| {
+            value.trim().parse::<f32>().map_err(|_| {
+                parse_error(format!(
+                    "invalid {} '{}' on {}",
+                    name,
+                    value,
+                    node.tag_name().name()
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn bbox(node: &Node) -> Result<BoundingBox> {
+    let value = |name: &str| -> Result<i32> {
+        Ok(number(node, name)?.map_or(0, |value| value.round() as i32))
+    };
+    let (left, top) = (value("HPOS")?, value("VPOS")?);
+    Ok(BoundingBox::new(
+        left,
+        top,
+        left + value("WIDTH")?,
+        top + value("HEIGHT")?,
+    ))
+}
+
+fn parse_page(node: &Node) -> Result<AltoPage> {
+    Ok(AltoPage {
+        id: id(node),
+        width: number(node, "WIDTH")?.map_or(0, |value| value.round() as i32),
+        height: number(node, "HEIGHT")?.map_or(0, |value| value.round() as i32),
+        physical_image_number: number(node, "PHYSICAL_IMG_NR")?.map(|value| value as u32),
+        text_blocks: node
+            .descendants()
+            .filter(|child| is_element(child, "TextBlock"))
+            .map(|block| {
+                Ok(AltoTextBlock {
+                    id: id(&block),
+                    bbox: bbox(&block)?,
+                    lines: block
+                        .children()
+                        .filter(|child| is_element(child, "TextLine"))
+                        .map(|line| parse_line(&line))
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn parse_line(node: &Node) -> Result<AltoTextLine> {
+    Ok(AltoTextLine {
+        id: id(node),
+        bbox: bbox(node)?,
+        strings: node
+            .children()
+            .filter(|child| is_element(child, "String"))
+            .map(|string| {
+                Ok(AltoString {
+                    id: id(&string),
+                    content: string.attribute("CONTENT").unwrap_or("").to_owned(),
+                    bbox: bbox(&string)?,
+                    confidence: number(&string, "WC")?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "\t\t<Page WIDTH=\"400\" HEIGHT=\"200\" PHYSICAL_IMG_NR=\"0\" ID=\"page_0\">\n\
+        \t\t\t<PrintSpace HPOS=\"0\" VPOS=\"0\" WIDTH=\"400\" HEIGHT=\"200\">\n\
+        \t\t\t\t<ComposedBlock ID=\"cblock_0\" HPOS=\"36\" VPOS=\"92\" WIDTH=\"300\" HEIGHT=\"30\">\n\
+        \t\t\t\t\t<TextBlock ID=\"block_0\" HPOS=\"36\" VPOS=\"92\" WIDTH=\"300\" HEIGHT=\"30\">\n\
+        \t\t\t\t\t\t<TextLine ID=\"line_0\" HPOS=\"36\" VPOS=\"92\" WIDTH=\"300\" HEIGHT=\"30\">\n\
+        \t\t\t\t\t\t\t<String ID=\"string_0\" HPOS=\"36\" VPOS=\"92\" WIDTH=\"60\" HEIGHT=\"24\" WC=\"0.96\" CONTENT=\"Hello\"/><SP WIDTH=\"14\" VPOS=\"92\" HPOS=\"96\"/>\n\
+        \t\t\t\t\t\t\t<String ID=\"string_1\" HPOS=\"110\" VPOS=\"92\" WIDTH=\"226\" HEIGHT=\"30\" WC=\"0.91\" CONTENT=\"&quot;world&quot;\"/>\n\
+        \t\t\t\t\t\t</TextLine>\n\
+        \t\t\t\t\t</TextBlock>\n\
+        \t\t\t\t</ComposedBlock>\n\
+        \t\t\t</PrintSpace>\n\
+        \t\t</Page>\n";
+
+    #[test]
+    fn test_parse_get_alto_text_fragment() {
+        let layout = AltoLayout::parse(PAGE).unwrap();
+        assert_eq!(layout.pages.len(), 1);
+        let page = &layout.pages[0];
+        assert_eq!(page.id.as_deref(), Some("page_0"));
+        assert_eq!((page.width, page.height), (400, 200));
+        assert_eq!(page.physical_image_number, Some(0));
+        assert_eq!(page.text_blocks.len(), 1);
+
+        let line = &page.text_blocks[0].lines[0];
+        assert_eq!(line.bbox, BoundingBox::new(36, 92, 336, 122));
+        assert_eq!(line.text(), "Hello \"world\"");
+        assert_eq!(
+            line.strings[0],
+            AltoString {
+                id: Some("string_0".to_owned()),
+                content: "Hello".to_owned(),
+                bbox: BoundingBox::new(36, 92, 96, 116),
+                confidence: Some(0.96),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_renderer_document() {
+        let document = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <alto xmlns=\"http://www.loc.gov/standards/alto/ns-v3#\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\">\n\
+             \t<Description>\n\t\t<MeasurementUnit>pixel</MeasurementUnit>\n\t</Description>\n\
+             \t<Layout>\n{}{}\t</Layout>\n</alto>\n",
+            PAGE,
+            PAGE.replace("page_0", "page_1")
+                .replace("PHYSICAL_IMG_NR=\"0\"", "PHYSICAL_IMG_NR=\"1\"")
+        );
+        let layout = AltoLayout::parse(&document).unwrap();
+        assert_eq!(layout.pages.len(), 2);
+        assert_eq!(layout.pages[1].physical_image_number, Some(1));
+        assert_eq!(layout.pages[1].strings().count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_alto() {
+        assert!(matches!(
+            AltoLayout::parse("<Page WIDTH=\"400\""),
+            Err(TesseractError::ParseError { .. })
+        ));
+        assert!(matches!(
+            AltoLayout::parse("<Page WIDTH=\"wide\"></Page>"),
+            Err(TesseractError::ParseError { .. })
+        ));
+    }
+}
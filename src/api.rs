@@ -1,27 +1,50 @@
-use crate::enums::TessPageSegMode;
-use crate::error::{Result, TesseractError};
-use crate::page_iterator::TessPageIteratorDelete; // Removed TessBaseAPIGetIterator
-use crate::result_iterator::TessResultIteratorDelete;
-use crate::{PageIterator, ResultIterator};
+use crate::builder::TesseractBuilder;
+use crate::enums::{TessLstmChoiceMode, TessOcrEngineMode, TessPageSegMode};
+use crate::error::{to_cstring, Result, TesseractError};
+use crate::page::Page;
+use crate::page_source::DocumentPages;
+use crate::parameters::{
+    parse_print_variables, Parameter, ParameterSnapshot, ParameterType, ParameterValue,
+};
+use crate::pix::{pixClone, Pix};
+use crate::result_iterator::{TessResultIteratorDelete, TessResultIteratorGetPageIterator};
+use crate::results::{Results, ResultsGuard};
+use crate::stderr;
+use crate::traineddata::language_missing_legacy;
+use crate::{
+    Document, MutableIterator, PageIterator, ResultIterator, TessMonitor, TessResultRenderer,
+};
 use std::collections::HashMap;
-use std::ffi::{CStr, CString};
+use std::ffi::CStr;
 use std::os::raw::{c_char, c_double, c_float, c_int, c_void};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
 pub struct TesseractConfiguration {
-    datapath: String,
-    language: String,
+    init: Option<TesseractBuilder>,
     variables: HashMap<String, String>,
 }
 
+impl TesseractConfiguration {
+    /// Checks whether the configuration describes an initialized engine.
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.init.is_some()
+    }
+
+    /// Checks whether two configurations initialize the engine the same way.
+    pub(crate) fn same_init(&self, other: &TesseractConfiguration) -> bool {
+        self.init == other.init
+    }
+}
+
 /// Main interface to the Tesseract OCR engine.
 #[cfg(feature = "build-tesseract")]
 pub struct TesseractAPI {
     /// Handle to the Tesseract engine.
     pub handle: Arc<Mutex<*mut c_void>>,
     config: Arc<Mutex<TesseractConfiguration>>,
+    pub(crate) results: Results,
 }
 
 unsafe impl Send for TesseractAPI {}
@@ -38,10 +61,10 @@ impl TesseractAPI {
         TesseractAPI {
             handle: Arc::new(Mutex::new(unsafe { TessBaseAPICreate() })),
             config: Arc::new(Mutex::new(TesseractConfiguration {
-                datapath: String::new(), // Initially empty, indicates not initialized
-                language: String::new(), // Initially empty
+                init: None, // Not initialized yet
                 variables: HashMap::new(),
             })),
+            results: Results::default(),
         }
     }
 
@@ -57,10 +80,48 @@ impl TesseractAPI {
             .into_owned()
     }
 
+    /// Creates a builder for an initialized engine.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `TesseractBuilder`.
+    pub fn builder() -> TesseractBuilder {
+        TesseractBuilder::new()
+    }
+
+    /// Gets the OCR engine mode that was loaded.
+    ///
+    /// When the engine was initialized with `OEM_DEFAULT`, the mode Tesseract selected from
+    /// the available traineddata components is returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns the engine mode if the engine is initialized, otherwise returns
+    /// `UninitializedError`.
+    pub fn oem(&self) -> Result<TessOcrEngineMode> {
+        // Resolving `OEM_DEFAULT` reads a variable of the engine, which `Init` creates.
+        if !self.configuration()?.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
+        let oem = {
+            let handle = self
+                .handle
+                .lock()
+                .map_err(|_| TesseractError::MutexLockError)?;
+            TessOcrEngineMode::from_int(unsafe { TessBaseAPIOem(*handle) })
+        };
+        if oem == TessOcrEngineMode::OEM_DEFAULT {
+            let resolved = self.get_int_variable("tessedit_ocr_engine_mode")?;
+            return Ok(TessOcrEngineMode::from_int(resolved));
+        }
+        Ok(oem)
+    }
+
     /// Initializes the Tesseract engine with the specified datapath and language.
     ///
-    /// This method is robust against multiple calls and will re-initialize the Tesseract
-    /// instance if the datapath or language changes, ensuring proper resource management.
+    /// This is a shorthand for initializing with a `TesseractBuilder` holding only the
+    /// datapath and language. It is robust against multiple calls and will re-initialize the
+    /// Tesseract instance if the configuration changes, ensuring proper resource management.
     ///
     /// # Arguments
     ///
@@ -71,9 +132,26 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if initialization is successful, otherwise returns an error.
     pub fn init<P: AsRef<Path>>(&self, datapath: P, language: &str) -> Result<()> {
-        let datapath_str = datapath.as_ref().to_str().unwrap().to_owned();
-        let language_str = language.to_owned();
+        self.init_with(
+            &TesseractBuilder::new()
+                .datapath(datapath)
+                .language(language),
+        )
+    }
 
+    /// Initializes the Tesseract engine as described by a builder.
+    ///
+    /// The builder is recorded so that clones and pools repeat the same initialization.
+    /// Variables set earlier with `set_variable` are re-applied after initialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The initialization settings.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if initialization is successful, otherwise returns an error.
+    pub fn init_with(&self, builder: &TesseractBuilder) -> Result<()> {
         let mut config_guard = self
             .config
             .lock()
@@ -81,45 +159,171 @@ impl TesseractAPI {
         let handle_guard = self
             .handle
             .lock()
-            .map_err(|_| TesseractError::MutexLockError)?; // Changed to immutable
+            .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
+
+        // If the engine was initialized differently, end it to release its resources.
+        if config_guard
+            .init
+            .as_ref()
+            .is_some_and(|previous| previous != builder)
+        {
+            unsafe { TessBaseAPIEnd(*handle_guard) };
+        }
+        config_guard.init = None;
 
-        // Check if Tesseract was previously initialized AND if the configuration has changed.
-        let was_initialized = !config_guard.datapath.is_empty();
-        let config_changed = was_initialized
-            && (config_guard.datapath != datapath_str || config_guard.language != language_str);
+        if builder.analyse_only {
+            unsafe { TessBaseAPIInitForAnalysePage(*handle_guard) };
+        } else {
+            let datapath = builder.datapath.as_deref().map(to_cstring).transpose()?;
+            let language = to_cstring(&builder.language_string())?;
+            let configs = builder
+                .configs
+                .iter()
+                .map(|c| to_cstring(c))
+                .collect::<Result<Vec<_>>>()?;
+            let names = builder
+                .init_variables
+                .iter()
+                .map(|(name, _)| to_cstring(name))
+                .collect::<Result<Vec<_>>>()?;
+            let values = builder
+                .init_variables
+                .iter()
+                .map(|(_, value)| to_cstring(value))
+                .collect::<Result<Vec<_>>>()?;
+            let config_ptrs: Vec<_> = configs.iter().map(|c| c.as_ptr()).collect();
+            let name_ptrs: Vec<_> = names.iter().map(|n| n.as_ptr()).collect();
+            let value_ptrs: Vec<_> = values.iter().map(|v| v.as_ptr()).collect();
+
+            let (result, output) = stderr::capture(|| unsafe {
+                match &builder.traineddata {
+                    Some(data) => TessBaseAPIInit5(
+                        *handle_guard,
+                        data.as_ptr(),
+                        data.len() as c_int,
+                        language.as_ptr(),
+                        builder.oem as c_int,
+                        config_ptrs.as_ptr(),
+                        config_ptrs.len() as c_int,
+                        name_ptrs.as_ptr(),
+                        value_ptrs.as_ptr(),
+                        name_ptrs.len(),
+                        builder.set_only_non_debug_params as c_int,
+                    ),
+                    None => TessBaseAPIInit4(
+                        *handle_guard,
+                        datapath.as_ref().map_or(std::ptr::null(), |d| d.as_ptr()),
+                        language.as_ptr(),
+                        builder.oem as c_int,
+                        config_ptrs.as_ptr(),
+                        config_ptrs.len() as c_int,
+                        name_ptrs.as_ptr(),
+                        value_ptrs.as_ptr(),
+                        name_ptrs.len(),
+                        builder.set_only_non_debug_params as c_int,
+                    ),
+                }
+            });
+            if result != 0 {
+                return Err(match language_missing_legacy(builder) {
+                    Some(language) => TesseractError::LegacyEngineUnavailable { language },
+                    None => TesseractError::InitError {
+                        datapath: builder.datapath.clone(),
+                        language: builder.language_string(),
+                        oem: builder.oem,
+                        output,
+                    },
+                });
+            }
+            stderr::replay(&output);
+        }
+        config_guard.init = Some(builder.clone());
 
-        if config_changed {
-            // If configuration changed, end the current instance to release resources.
-            unsafe { TessBaseAPIEnd(*handle_guard) };
+        // Re-apply any stored variables, as TessBaseAPIInit can reset them, then the
+        // builder's own variables.
+        for (name, value) in &builder.variables {
+            config_guard.variables.insert(name.clone(), value.clone());
         }
+        for (name, value) in &config_guard.variables {
+            self.set_variable_internal(name, value, *handle_guard)?;
+        }
+        Ok(())
+    }
 
-        // Update configuration fields for the current instance.
-        config_guard.datapath = datapath_str.clone();
-        config_guard.language = language_str.clone();
+    /// Creates a new instance initialized with the same configuration.
+    ///
+    /// A new `TessBaseAPI` handle is created, initialized with the recorded datapath and
+    /// language, and given the same variables. An uninitialized instance yields another
+    /// uninitialized instance.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance if initialization is successful, otherwise returns an error.
+    pub fn try_clone(&self) -> Result<Self> {
+        let config = self
+            .config
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?
+            .clone();
+        Self::from_configuration(&config)
+    }
 
-        let datapath_c = CString::new(datapath_str).unwrap();
-        let language_c = CString::new(language_str).unwrap();
+    /// Gets a snapshot of the configuration used to initialize this instance.
+    pub(crate) fn configuration(&self) -> Result<TesseractConfiguration> {
+        Ok(self
+            .config
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?
+            .clone())
+    }
 
-        let result =
-            unsafe { TessBaseAPIInit3(*handle_guard, datapath_c.as_ptr(), language_c.as_ptr()) };
+    /// Creates a new instance from a configuration snapshot.
+    pub(crate) fn from_configuration(config: &TesseractConfiguration) -> Result<Self> {
+        let new_api = TesseractAPI::new();
+        new_api
+            .config
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?
+            .variables = config.variables.clone();
+        if let Some(builder) = &config.init {
+            new_api.init_with(builder)?;
+        }
+        Ok(new_api)
+    }
 
-        if result != 0 {
-            // If init fails, clear the config to reflect an uninitialized state.
-            config_guard.datapath.clear();
-            config_guard.language.clear();
-            Err(TesseractError::InitError)
-        } else {
-            // Re-apply any stored variables, as TessBaseAPIInit can reset them.
-            // Clone variables to avoid holding the config_guard lock during iteration,
-            // as set_variable_internal uses the handle_guard which is already held.
-            let variables_to_apply = config_guard.variables.clone();
-
-            // Re-applying variables directly here.
-            for (name, value) in &variables_to_apply {
-                self.set_variable_internal(name, value, *handle_guard)?;
-            }
-            Ok(())
+    /// Resets the variables set since the engine was created from a configuration.
+    ///
+    /// Variables the configuration sets get its values back; the others get their defaults.
+    pub(crate) fn restore_configuration(&self, config: &TesseractConfiguration) -> Result<()> {
+        let mut config_guard = self
+            .config
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        let handle_guard = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        let mut defaults = None;
+        for (name, value) in &config_guard.variables {
+            let restored = match config.variables.get(name) {
+                Some(original) if original == value => continue,
+                Some(original) => original.clone(),
+                None => {
+                    let defaults = match &defaults {
+                        Some(defaults) => defaults,
+                        None => defaults.insert(DefaultEngine::new()?),
+                    };
+                    defaults
+                        .read(&to_cstring(name)?)?
+                        .ok_or_else(|| TesseractError::UnknownParameter { name: name.clone() })?
+                        .to_string()
+                }
+            };
+            self.set_variable_internal(name, &restored, *handle_guard)?;
         }
+        config_guard.variables = config.variables.clone();
+        Ok(())
     }
 
     /// Gets the confidence values for all recognized words.
@@ -132,6 +336,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
 
         let confidences_ptr = unsafe { TessBaseAPIAllWordConfidences(*handle) };
         if confidences_ptr.is_null() {
@@ -157,6 +362,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         Ok(unsafe { TessBaseAPIMeanTextConf(*handle) })
     }
 
@@ -182,23 +388,25 @@ impl TesseractAPI {
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
 
-        // Update internal config first
+        // Apply to the Tesseract engine first so a rejected variable is not replayed on clones
+        self.set_variable_internal(name, value, *handle_guard)?;
         config_guard
             .variables
             .insert(name.to_owned(), value.to_owned());
-
-        // Then apply to the Tesseract engine using the internal helper
-        self.set_variable_internal(name, value, *handle_guard)
+        Ok(())
     }
 
     /// Internal helper to set a Tesseract variable directly on a `c_void` handle.
     /// Assumes the `handle` is already locked and avoids re-acquiring mutexes.
     fn set_variable_internal(&self, name: &str, value: &str, handle: *mut c_void) -> Result<()> {
-        let name_c = CString::new(name).unwrap();
-        let value_c = CString::new(value).unwrap();
+        let name_c = to_cstring(name)?;
+        let value_c = to_cstring(value)?;
         let result = unsafe { TessBaseAPISetVariable(handle, name_c.as_ptr(), value_c.as_ptr()) };
         if result != 1 {
-            Err(TesseractError::SetVariableError)
+            Err(TesseractError::SetVariableError {
+                name: name.to_owned(),
+                value: value.to_owned(),
+            })
         } else {
             Ok(())
         }
@@ -212,19 +420,13 @@ impl TesseractAPI {
     ///
     /// # Returns
     ///
-    /// Returns the value of the variable as a string.
+    /// Returns the value of the variable as a string, or `UnknownParameter` or
+    /// `ParameterTypeMismatch` if there is no string variable with this name.
     pub fn get_string_variable(&self, name: &str) -> Result<String> {
-        let name = CString::new(name).unwrap();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        let value_ptr = unsafe { TessBaseAPIGetStringVariable(*handle, name.as_ptr()) };
-        if value_ptr.is_null() {
-            return Err(TesseractError::GetVariableError);
+        match self.get_parameter(name)? {
+            ParameterValue::String(value) => Ok(value),
+            other => Err(type_mismatch(name, ParameterType::String, &other)),
         }
-        let c_str = unsafe { CStr::from_ptr(value_ptr) };
-        Ok(c_str.to_str()?.to_owned())
     }
 
     /// Gets an integer variable.
@@ -235,14 +437,13 @@ impl TesseractAPI {
     ///
     /// # Returns
     ///
-    /// Returns the value of the variable as an integer.
+    /// Returns the value of the variable as an integer, or `UnknownParameter` or
+    /// `ParameterTypeMismatch` if there is no integer variable with this name.
     pub fn get_int_variable(&self, name: &str) -> Result<i32> {
-        let name = CString::new(name).unwrap();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        Ok(unsafe { TessBaseAPIGetIntVariable(*handle, name.as_ptr()) })
+        match self.get_parameter(name)? {
+            ParameterValue::Int(value) => Ok(value),
+            other => Err(type_mismatch(name, ParameterType::Int, &other)),
+        }
     }
 
     /// Gets a boolean variable.
@@ -253,32 +454,228 @@ impl TesseractAPI {
     ///
     /// # Returns
     ///
-    /// Returns the value of the variable as a boolean.
+    /// Returns the value of the variable as a boolean, or `UnknownParameter` or
+    /// `ParameterTypeMismatch` if there is no boolean variable with this name.
     pub fn get_bool_variable(&self, name: &str) -> Result<bool> {
-        let name = CString::new(name).unwrap();
+        match self.get_parameter(name)? {
+            ParameterValue::Bool(value) => Ok(value),
+            other => Err(type_mismatch(name, ParameterType::Bool, &other)),
+        }
+    }
+
+    /// Gets a double variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value of the variable as a double, or `UnknownParameter` or
+    /// `ParameterTypeMismatch` if there is no double variable with this name.
+    pub fn get_double_variable(&self, name: &str) -> Result<f64> {
+        match self.get_parameter(name)? {
+            ParameterValue::Double(value) => Ok(value),
+            other => Err(type_mismatch(name, ParameterType::Double, &other)),
+        }
+    }
+
+    /// Gets the value of a parameter, whatever its type.
+    ///
+    /// Before initialization, this reads the values `set_variable` applied or the defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// Returns the typed value, or `UnknownParameter` if Tesseract has no such parameter.
+    pub fn get_parameter(&self, name: &str) -> Result<ParameterValue> {
+        let name_c = to_cstring(name)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        Ok(unsafe { TessBaseAPIGetBoolVariable(*handle, name.as_ptr()) } != 0)
+        allocate_parameters(*handle)?;
+        read_parameter(*handle, &name_c)?.ok_or_else(|| TesseractError::UnknownParameter {
+            name: name.to_owned(),
+        })
     }
 
-    /// Gets a double variable.
+    /// Sets a parameter after checking its name and type.
+    ///
+    /// Unlike `set_variable`, which passes the value to Tesseract as a string, the value's type
+    /// must match the parameter's type, so a misspelled name or a value Tesseract would
+    /// silently ignore is reported.
     ///
     /// # Arguments
     ///
-    /// * `name` - Name of the variable.
+    /// * `name` - Name of the parameter.
+    /// * `value` - Value to set.
     ///
     /// # Returns
     ///
-    /// Returns the value of the variable as a double.
-    pub fn get_double_variable(&self, name: &str) -> Result<f64> {
-        let name = CString::new(name).unwrap();
+    /// Returns `Ok(())` if the parameter was set, `UnknownParameter` if Tesseract has no such
+    /// parameter and `ParameterTypeMismatch` if the value has a different type.
+    pub fn set_parameter(&self, name: &str, value: impl Into<ParameterValue>) -> Result<()> {
+        let value = value.into();
+        let current = self.get_parameter(name)?;
+        if current.parameter_type() != value.parameter_type() {
+            return Err(type_mismatch(name, value.parameter_type(), &current));
+        }
+        self.set_variable(name, &value.to_string())
+    }
+
+    /// Lists the parameters known to the engine with their current and default values.
+    ///
+    /// Defaults are read from a new engine. Global parameters such as `debug_file` are shared
+    /// by every engine in the process, so their default is the current global value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parameters sorted by name if the engine is initialized, otherwise returns
+    /// an error.
+    pub fn parameters(&self) -> Result<Vec<Parameter>> {
+        if !self.configuration()?.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        Ok(unsafe { TessBaseAPIGetDoubleVariable(*handle, name.as_ptr()) })
+        let defaults = DefaultEngine::new()?;
+        let printed = print_variables(*handle)?;
+        let mut parameters = parse_print_variables(&printed)
+            .into_iter()
+            .map(|(name, description)| {
+                let name_c = to_cstring(name)?;
+                let unknown = || TesseractError::UnknownParameter {
+                    name: name.to_owned(),
+                };
+                let value = read_parameter(*handle, &name_c)?.ok_or_else(unknown)?;
+                let default = defaults.read(&name_c)?.ok_or_else(unknown)?;
+                Ok(Parameter {
+                    name: name.to_owned(),
+                    value,
+                    default,
+                    description: description.to_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        parameters.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(parameters)
+    }
+
+    /// Exports the current value of every parameter.
+    ///
+    /// Unlike the configuration kept for `Clone`, the snapshot includes parameters loaded from
+    /// config files or set with `set_debug_variable`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the snapshot if the engine is initialized, otherwise returns an error.
+    pub fn export_parameters(&self) -> Result<ParameterSnapshot> {
+        let mut snapshot = ParameterSnapshot::new();
+        for parameter in self.parameters()? {
+            snapshot.set(&parameter.name, parameter.value);
+        }
+        Ok(snapshot)
+    }
+
+    /// Applies every parameter of a snapshot with `set_variable`.
+    ///
+    /// The parameters are recorded like other variables, so they carry over to clones and
+    /// pooled engines. Init-only parameters such as `load_system_dawg` are accepted but have
+    /// no effect; pass them to `TesseractBuilder::init_variable` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The parameters to apply.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if every parameter was set, otherwise the error for the first one
+    /// Tesseract rejected.
+    pub fn import_parameters(&self, snapshot: &ParameterSnapshot) -> Result<()> {
+        for (name, value) in snapshot.iter() {
+            self.set_variable(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Restricts recognition to a set of characters (`tessedit_char_whitelist`).
+    ///
+    /// # Arguments
+    ///
+    /// * `characters` - Characters that may be recognized; empty to allow all.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the whitelist was set, otherwise returns an error.
+    pub fn set_char_whitelist(&self, characters: &str) -> Result<()> {
+        self.set_variable("tessedit_char_whitelist", characters)
+    }
+
+    /// Excludes a set of characters from recognition (`tessedit_char_blacklist`).
+    ///
+    /// # Arguments
+    ///
+    /// * `characters` - Characters that are never recognized.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the blacklist was set, otherwise returns an error.
+    pub fn set_char_blacklist(&self, characters: &str) -> Result<()> {
+        self.set_variable("tessedit_char_blacklist", characters)
+    }
+
+    /// Keeps runs of spaces between words in the text output (`preserve_interword_spaces`).
+    ///
+    /// # Arguments
+    ///
+    /// * `preserve` - Whether spaces between words are preserved.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the setting was applied, otherwise returns an error.
+    pub fn set_preserve_interword_spaces(&self, preserve: bool) -> Result<()> {
+        self.set_variable(
+            "preserve_interword_spaces",
+            &ParameterValue::Bool(preserve).to_string(),
+        )
+    }
+
+    /// Sets which recognition alternatives the LSTM engine keeps (`lstm_choice_mode`).
+    ///
+    /// `LSTM_CHOICE_SYMBOLS` makes `ResultIterator::choices` report alternatives for each
+    /// symbol, `LSTM_CHOICE_TIMESTEPS` makes `ResultIterator::word_timestep_choices` report
+    /// the network's candidates for each timestep, and both modes add alternatives to the
+    /// hOCR output. Takes effect for the next recognition.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The alternatives to keep.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the mode was set, otherwise returns an error.
+    pub fn set_lstm_choice_mode(&self, mode: TessLstmChoiceMode) -> Result<()> {
+        self.set_variable("lstm_choice_mode", &(mode as i32).to_string())
+    }
+
+    /// Sets the resolution used when the image carries none (`user_defined_dpi`).
+    ///
+    /// # Arguments
+    ///
+    /// * `dpi` - Resolution in dots per inch.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the resolution was set, otherwise returns an error.
+    pub fn set_dpi(&self, dpi: u32) -> Result<()> {
+        let dpi = i32::try_from(dpi).map_err(|_| TesseractError::InvalidParameterError)?;
+        self.set_variable("user_defined_dpi", &dpi.to_string())
     }
 
     /// Sets the page segmentation mode.
@@ -323,6 +720,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize();
         let result = unsafe { TessBaseAPIRecognize(*handle, std::ptr::null_mut()) };
         if result != 0 {
             Err(TesseractError::OcrError)
@@ -331,6 +729,87 @@ impl TesseractAPI {
         }
     }
 
+    /// Recognizes the text in the current image while reporting to a monitor.
+    ///
+    /// The monitor's progress callback is invoked after each word, and recognition stops early
+    /// when the monitor is cancelled or its deadline passes.
+    ///
+    /// The engine stays locked while the progress callback runs, so the callback must not call
+    /// methods of this `TesseractAPI`; doing so deadlocks. A panic in the callback cancels the
+    /// recognition and is resumed after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - The monitor receiving progress and controlling cancellation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if recognition is successful, `TesseractError::Cancelled` if the monitor
+    /// was cancelled, otherwise returns an error.
+    pub fn recognize_with_monitor(&self, monitor: &TessMonitor) -> Result<()> {
+        // Reading the input image dereferences the engine, which `Init` creates.
+        if !self.configuration()?.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
+        let image_height = self
+            .get_input_image()
+            .and_then(|pix| pix.height())
+            .unwrap_or(0);
+        let monitor_handle = monitor.prepare(image_height)?;
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize();
+        let result = unsafe { TessBaseAPIRecognize(*handle, monitor_handle) };
+        drop(handle);
+        if let Some(payload) = monitor.take_panic() {
+            std::panic::resume_unwind(payload);
+        }
+        if monitor.is_cancelled() {
+            Err(TesseractError::Cancelled)
+        } else if result != 0 {
+            Err(TesseractError::OcrError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Recognizes the text in the current image and collects the full result hierarchy.
+    ///
+    /// Blocks, paragraphs, lines, words and symbols are gathered in a single pass over the
+    /// result iterator, which is released before returning. The returned `Page` owns all of
+    /// its data and stays valid after the engine is cleared or given a new image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the recognized `Page` if successful, otherwise returns an error.
+    pub fn recognize_page(&self) -> Result<Page> {
+        self.recognize()?;
+        let iterator = self.get_iterator()?;
+        Page::from_result_iterator(&iterator)
+    }
+
+    /// Recognizes every page of a document, one page at a time.
+    ///
+    /// Pages are taken lazily from `pages`, so a multi-page TIFF is never decoded in full.
+    /// Attach a renderer with `DocumentPages::with_renderer` to render the document page by
+    /// page.
+    ///
+    /// # Arguments
+    ///
+    /// * `pages` - The page images, for example a `TiffPages` source.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding a `RecognizedPage` with its index for each page.
+    pub fn recognize_document<I>(&self, pages: I) -> DocumentPages<'_, I::IntoIter>
+    where
+        I: IntoIterator<Item = Result<Pix>>,
+    {
+        DocumentPages::new(self, pages.into_iter())
+    }
+
     /// Gets the HOCR text for the specified page.
     ///
     /// # Arguments
@@ -345,6 +824,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetHOCRText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -369,6 +849,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetAltoText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -393,6 +874,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetTsvText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -413,7 +895,7 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if setting the input name is successful, otherwise returns an error.
     pub fn set_input_name(&self, name: &str) -> Result<()> {
-        let name = CString::new(name).unwrap();
+        let name = to_cstring(name)?;
         let handle = self
             .handle
             .lock()
@@ -473,20 +955,18 @@ impl TesseractAPI {
 
     /// Gets the thresholded image.
     ///
+    /// The engine hands over a new reference, so the returned `Pix` is owned by the caller
+    /// and released when dropped.
+    ///
     /// # Returns
     ///
-    /// Returns a pointer to the thresholded image.
-    pub fn get_thresholded_image(&self) -> Result<*mut c_void> {
+    /// Returns the thresholded image as a `Pix`.
+    pub fn get_thresholded_image(&self) -> Result<Pix> {
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let pix = unsafe { TessBaseAPIGetThresholdedImage(*handle) };
-        if pix.is_null() {
-            Err(TesseractError::NullPointerError)
-        } else {
-            Ok(pix)
-        }
+        unsafe { Pix::from_raw(TessBaseAPIGetThresholdedImage(*handle)) }
     }
 
     /// Gets the box text for the specified page.
@@ -503,6 +983,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetBoxText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -527,6 +1008,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetLSTMBoxText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -551,6 +1033,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetWordStrBoxText(*handle, page) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -571,6 +1054,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let text_ptr = unsafe { TessBaseAPIGetUNLVText(*handle) };
         if text_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -591,6 +1075,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
         let confidences_ptr = unsafe { TessBaseAPIAllWordConfidences(*handle) };
         if confidences_ptr.is_null() {
             return Err(TesseractError::OcrError);
@@ -620,13 +1105,15 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let wordstr = CString::new(wordstr).unwrap();
+        let wordstr = to_cstring(wordstr)?;
         let result = unsafe { TessBaseAPIAdaptToWordStr(*handle, mode, wordstr.as_ptr()) };
         Ok(result != 0)
     }
 
     /// Detects the orientation and script.
     ///
+    /// Detection clears the recognition results, which invalidates existing iterators.
+    ///
     /// # Returns
     ///
     /// Returns a tuple containing the orientation in degrees, the orientation confidence, the script name, and the script confidence.
@@ -635,6 +1122,8 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        // Detection clears the recognition results before it runs.
+        self.results.invalidate();
         let mut orient_deg = 0;
         let mut orient_conf = 0.0;
         let mut script_name_ptr = std::ptr::null_mut();
@@ -685,7 +1174,7 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns a `PageIterator` object.
-    pub fn get_page_iterator(&self) -> Result<PageIterator> {
+    pub fn get_page_iterator(&self) -> Result<PageIterator<'_>> {
         let handle = self
             .handle
             .lock()
@@ -694,24 +1183,39 @@ impl TesseractAPI {
         if iterator.is_null() {
             return Err(TesseractError::NullPointerError);
         }
-        Ok(PageIterator::new(iterator))
+        Ok(PageIterator::with_results(
+            iterator,
+            ResultsGuard::new(self),
+        ))
     }
 
     /// Sets the input image.
     ///
+    /// The engine takes its own reference to the image, so `pix` can be dropped afterwards.
+    ///
     /// # Arguments
     ///
-    /// * `pix` - Pointer to the input image.
+    /// * `pix` - The input image.
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if setting the input image is successful, otherwise returns an error.
-    pub fn set_input_image(&self, pix: *mut c_void) -> Result<()> {
+    /// Returns `Ok(())` if setting the input image is successful, `UninitializedError` if the
+    /// engine is not initialized, otherwise returns an error.
+    pub fn set_input_image(&self, pix: &Pix) -> Result<()> {
+        // The input image is held by the engine, which `Init` creates.
+        if !self.configuration()?.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
+        let pix_handle = pix
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        unsafe { TessBaseAPISetInputImage(*handle, pix) };
+        // The engine destroys the input image it holds, so hand it a reference of its own.
+        unsafe { TessBaseAPISetInputImage(*handle, pixClone(*pix_handle)) };
         Ok(())
     }
 
@@ -719,18 +1223,18 @@ impl TesseractAPI {
     ///
     /// # Returns
     ///
-    /// Returns a pointer to the input image.
-    pub fn get_input_image(&self) -> Result<*mut c_void> {
+    /// Returns a new reference to the input image as a `Pix`, or `UninitializedError` if the
+    /// engine is not initialized.
+    pub fn get_input_image(&self) -> Result<Pix> {
+        // The input image is held by the engine, which `Init` creates.
+        if !self.configuration()?.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let pix = unsafe { TessBaseAPIGetInputImage(*handle) };
-        if pix.is_null() {
-            Err(TesseractError::NullPointerError)
-        } else {
-            Ok(pix)
-        }
+        Pix::from_borrowed(unsafe { TessBaseAPIGetInputImage(*handle) })
     }
 
     /// Sets the output name.
@@ -743,7 +1247,7 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if setting the output name is successful, otherwise returns an error.
     pub fn set_output_name(&self, name: &str) -> Result<()> {
-        let name = CString::new(name).unwrap();
+        let name = to_cstring(name)?;
         let handle = self
             .handle
             .lock()
@@ -762,15 +1266,19 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if setting the debug variable is successful, otherwise returns an error.
     pub fn set_debug_variable(&self, name: &str, value: &str) -> Result<()> {
-        let name = CString::new(name).unwrap();
-        let value = CString::new(value).unwrap();
+        let name_c = to_cstring(name)?;
+        let value_c = to_cstring(value)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe { TessBaseAPISetDebugVariable(*handle, name.as_ptr(), value.as_ptr()) };
+        let result =
+            unsafe { TessBaseAPISetDebugVariable(*handle, name_c.as_ptr(), value_c.as_ptr()) };
         if result != 1 {
-            Err(TesseractError::SetVariableError)
+            Err(TesseractError::SetVariableError {
+                name: name.to_owned(),
+                value: value.to_owned(),
+            })
         } else {
             Ok(())
         }
@@ -786,14 +1294,18 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if printing the variables to the file is successful, otherwise returns an error.
     pub fn print_variables_to_file(&self, filename: &str) -> Result<()> {
-        let filename = CString::new(filename).unwrap();
+        let filename_c = to_cstring(filename)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe { TessBaseAPIPrintVariablesToFile(*handle, filename.as_ptr()) };
-        if result != 0 {
-            Err(TesseractError::IoError)
+        // Returns TRUE once the file was opened and written.
+        let result = unsafe { TessBaseAPIPrintVariablesToFile(*handle, filename_c.as_ptr()) };
+        if result == 0 {
+            Err(TesseractError::FileError {
+                path: filename.to_owned(),
+                output: String::new(),
+            })
         } else {
             Ok(())
         }
@@ -801,17 +1313,24 @@ impl TesseractAPI {
 
     /// Initializes for analysing a page.
     ///
+    /// Does nothing if the engine is already initialized.
+    ///
     /// # Returns
     ///
     /// Returns `Ok(())` if initialization is successful, otherwise returns an error.
+    #[deprecated(note = "use `TesseractBuilder::analyse_only` instead")]
     pub fn init_for_analyse_page(&self) -> Result<()> {
-        let handle = self
-            .handle
+        let initialized = self
+            .config
             .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        unsafe { TessBaseAPIInitForAnalysePage(*handle) };
-        Ok(())
+            .map_err(|_| TesseractError::MutexLockError)?
+            .is_initialized();
+        if initialized {
+            return Ok(());
+        }
+        self.init_with(&TesseractBuilder::new().analyse_only(true))
     }
+
     /// Reads the configuration file.
     ///
     /// # Arguments
@@ -822,12 +1341,21 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if reading the configuration file is successful, otherwise returns an error.
     pub fn read_config_file(&self, filename: &str) -> Result<()> {
-        let filename = CString::new(filename).unwrap();
+        let filename_c = to_cstring(filename)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        unsafe { TessBaseAPIReadConfigFile(*handle, filename.as_ptr()) };
+        // Tesseract only reports an unreadable file on stderr.
+        let ((), output) =
+            stderr::capture(|| unsafe { TessBaseAPIReadConfigFile(*handle, filename_c.as_ptr()) });
+        if output.contains(CANT_OPEN_CONFIG) {
+            return Err(TesseractError::FileError {
+                path: filename.to_owned(),
+                output,
+            });
+        }
+        stderr::replay(&output);
         Ok(())
     }
 
@@ -841,12 +1369,22 @@ impl TesseractAPI {
     ///
     /// Returns `Ok(())` if reading the debug configuration file is successful, otherwise returns an error.
     pub fn read_debug_config_file(&self, filename: &str) -> Result<()> {
-        let filename = CString::new(filename).unwrap();
+        let filename_c = to_cstring(filename)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        unsafe { TessBaseAPIReadDebugConfigFile(*handle, filename.as_ptr()) };
+        // Tesseract only reports an unreadable file on stderr.
+        let ((), output) = stderr::capture(|| unsafe {
+            TessBaseAPIReadDebugConfigFile(*handle, filename_c.as_ptr())
+        });
+        if output.contains(CANT_OPEN_CONFIG) {
+            return Err(TesseractError::FileError {
+                path: filename.to_owned(),
+                output,
+            });
+        }
+        stderr::replay(&output);
         Ok(())
     }
 
@@ -880,23 +1418,86 @@ impl TesseractAPI {
         retry_config: Option<&str>,
         timeout_millisec: i32,
     ) -> Result<String> {
-        let filename = CString::new(filename).unwrap();
-        let retry_config = retry_config.map(|s| CString::new(s).unwrap());
+        self.process_pages_into(filename, retry_config, timeout_millisec, None)
+    }
+
+    /// Processes the pages of a file, rendering every page with `renderer`.
+    ///
+    /// Tesseract begins the renderer's document, adds each recognized page and ends the
+    /// document, so the renderer must not have begun one already. `filename` may be an
+    /// image, a multi-page TIFF or a text file listing one image path per line.
+    ///
+    /// ```no_run
+    /// # fn example(api: &tesseract_rs::TesseractAPI) -> tesseract_rs::Result<()> {
+    /// use tesseract_rs::TessResultRenderer;
+    ///
+    /// let renderer = TessResultRenderer::composite([
+    ///     TessResultRenderer::new_pdf_renderer("scans/out", "tessdata", false)?,
+    ///     TessResultRenderer::new_hocr_renderer("scans/out")?,
+    /// ])?;
+    /// api.process_pages_with("scans/batch.tif", None, 0, &renderer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Name of the file to process.
+    /// * `retry_config` - Retry configuration.
+    /// * `timeout_millisec` - Timeout in milliseconds.
+    /// * `renderer` - The renderer receiving the document.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if every page was processed and rendered, otherwise returns
+    /// `ProcessPagesError`.
+    pub fn process_pages_with(
+        &self,
+        filename: &str,
+        retry_config: Option<&str>,
+        timeout_millisec: i32,
+        renderer: &TessResultRenderer,
+    ) -> Result<()> {
+        self.process_pages_into(filename, retry_config, timeout_millisec, Some(renderer))?;
+        Ok(())
+    }
+
+    fn process_pages_into(
+        &self,
+        filename: &str,
+        retry_config: Option<&str>,
+        timeout_millisec: i32,
+        renderer: Option<&TessResultRenderer>,
+    ) -> Result<String> {
+        let filename_c = to_cstring(filename)?;
+        let retry_config = retry_config.map(to_cstring).transpose()?;
+        // The engine is locked before the renderer, in the same order as `add_image`.
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe {
+        self.results.invalidate();
+        let process = |renderer: *mut c_void| unsafe {
             TessBaseAPIProcessPages(
                 *handle,
-                filename.as_ptr(),
-                retry_config.map_or(std::ptr::null(), |rc| rc.as_ptr()),
+                filename_c.as_ptr(),
+                retry_config
+                    .as_ref()
+                    .map_or(std::ptr::null(), |rc| rc.as_ptr()),
                 timeout_millisec,
-                std::ptr::null_mut(), // renderer
+                renderer,
             )
         };
+        let result = match renderer {
+            Some(renderer) => renderer.render(process)?,
+            None => process(std::ptr::null_mut()),
+        };
         if result.is_null() {
-            Err(TesseractError::ProcessPagesError)
+            // Processing can run for minutes, so its stderr output is not captured: that would
+            // swallow every other thread's output and hold up capture for other engines.
+            Err(TesseractError::ProcessPagesError {
+                path: filename.to_owned(),
+            })
         } else {
             let c_str = unsafe { CStr::from_ptr(result) };
             let output = c_str.to_str()?.to_owned();
@@ -905,6 +1506,42 @@ impl TesseractAPI {
         }
     }
 
+    /// Begins a document rendered page by page from the images set on this engine.
+    ///
+    /// The returned `Document` adds the current image as a page with `add_page`, or sets and
+    /// adds an image with `add_pix`, and ends the document with `finish`. A document that is
+    /// dropped without being finished is ended then, ignoring errors.
+    ///
+    /// ```no_run
+    /// # fn example(api: &tesseract_rs::TesseractAPI, pages: Vec<tesseract_rs::Pix>) -> tesseract_rs::Result<()> {
+    /// use tesseract_rs::TessResultRenderer;
+    ///
+    /// let renderer = TessResultRenderer::new_pdf_renderer("out/scan", "tessdata", false)?;
+    /// let mut document = api.begin_document(&renderer, "Scan")?;
+    /// for pix in &pages {
+    ///     document.add_pix(pix)?;
+    /// }
+    /// document.finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer receiving the document; it must not have begun one.
+    /// * `title` - Title of the document.
+    ///
+    /// # Returns
+    ///
+    /// Returns the document if the renderer began it, otherwise returns `RenderError`.
+    pub fn begin_document<'a>(
+        &'a self,
+        renderer: &'a TessResultRenderer,
+        title: &str,
+    ) -> Result<Document<'a>> {
+        Document::begin(self, renderer, title)
+    }
+
     /// Gets the initial languages as a string.
     ///
     /// This method queries the *current* Tesseract engine instance for the languages it was initialized with.
@@ -922,7 +1559,7 @@ impl TesseractAPI {
             // If Tesseract hasn't been initialized, this might return null.
             // Or if it was initialized but failed, this could also be null.
             // We return an empty string in such cases to represent "no languages loaded".
-            return Ok(String::new());
+            Ok(String::new())
         } else {
             let c_str = unsafe { CStr::from_ptr(result) };
             Ok(c_str.to_str()?.to_owned())
@@ -1009,21 +1646,31 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         unsafe { TessBaseAPIClear(*handle) };
         Ok(())
     }
 
     /// Ends the OCR engine.
     ///
+    /// The engine is uninitialized afterwards, so clones and pools no longer repeat its
+    /// initialization; variables set with `set_variable` are kept.
+    ///
     /// # Returns
     ///
     /// Returns `Ok(())` if ending the OCR engine is successful, otherwise returns an error.
     pub fn end(&self) -> Result<()> {
+        let mut config_guard = self
+            .config
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         unsafe { TessBaseAPIEnd(*handle) };
+        config_guard.init = None;
         Ok(())
     }
 
@@ -1037,7 +1684,7 @@ impl TesseractAPI {
     ///
     /// Returns `true` if the word is valid, otherwise returns `false`.
     pub fn is_valid_word(&self, word: &str) -> Result<i32> {
-        let word = CString::new(word).unwrap();
+        let word = to_cstring(word)?;
         let handle = self
             .handle
             .lock()
@@ -1055,6 +1702,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         let mut out_degrees = 0;
         let mut out_confidence = 0.0;
         unsafe {
@@ -1075,30 +1723,15 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns `Ok(())` if initializing the OCR engine is successful, otherwise returns an error.
-    pub fn init_1(&self, datapath: &str, language: &str, oem: i32, configs: &[&str]) -> Result<()> {
-        let datapath = CString::new(datapath).unwrap();
-        let language = CString::new(language).unwrap();
-        let config_ptrs: Vec<_> = configs.iter().map(|&s| CString::new(s).unwrap()).collect();
-        let config_ptr_ptrs: Vec<_> = config_ptrs.iter().map(|cs| cs.as_ptr()).collect();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe {
-            TessBaseAPIInit1(
-                *handle,
-                datapath.as_ptr(),
-                language.as_ptr(),
-                oem,
-                config_ptr_ptrs.as_ptr(),
-                config_ptrs.len() as c_int,
-            )
-        };
-        if result != 0 {
-            Err(TesseractError::InitError)
-        } else {
-            Ok(())
-        }
+    #[deprecated(note = "use `TesseractBuilder` instead")]
+    pub fn init_1(
+        &self,
+        datapath: &str,
+        language: &str,
+        oem: TessOcrEngineMode,
+        configs: &[&str],
+    ) -> Result<()> {
+        self.init_with(&legacy_builder(datapath, language, oem, configs))
     }
 
     /// Initializes the OCR engine.
@@ -1112,20 +1745,9 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns `Ok(())` if initializing the OCR engine is successful, otherwise returns an error.
-    pub fn init_2(&self, datapath: &str, language: &str, oem: i32) -> Result<()> {
-        let datapath = CString::new(datapath).unwrap();
-        let language = CString::new(language).unwrap();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        let result =
-            unsafe { TessBaseAPIInit2(*handle, datapath.as_ptr(), language.as_ptr(), oem) };
-        if result != 0 {
-            Err(TesseractError::InitError)
-        } else {
-            Ok(())
-        }
+    #[deprecated(note = "use `TesseractBuilder` instead")]
+    pub fn init_2(&self, datapath: &str, language: &str, oem: TessOcrEngineMode) -> Result<()> {
+        self.init_with(&legacy_builder(datapath, language, oem, &[]))
     }
 
     /// Initializes the OCR engine.
@@ -1140,30 +1762,15 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns `Ok(())` if initializing the OCR engine is successful, otherwise returns an error.
-    pub fn init_4(&self, datapath: &str, language: &str, oem: i32, configs: &[&str]) -> Result<()> {
-        let datapath = CString::new(datapath).unwrap();
-        let language = CString::new(language).unwrap();
-        let config_ptrs: Vec<_> = configs.iter().map(|&s| CString::new(s).unwrap()).collect();
-        let config_ptr_ptrs: Vec<_> = config_ptrs.iter().map(|cs| cs.as_ptr()).collect();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe {
-            TessBaseAPIInit4(
-                *handle,
-                datapath.as_ptr(),
-                language.as_ptr(),
-                oem,
-                config_ptr_ptrs.as_ptr(),
-                config_ptrs.len() as c_int,
-            )
-        };
-        if result != 0 {
-            Err(TesseractError::InitError)
-        } else {
-            Ok(())
-        }
+    #[deprecated(note = "use `TesseractBuilder` instead")]
+    pub fn init_4(
+        &self,
+        datapath: &str,
+        language: &str,
+        oem: TessOcrEngineMode,
+        configs: &[&str],
+    ) -> Result<()> {
+        self.init_with(&legacy_builder(datapath, language, oem, configs))
     }
 
     /// Initializes the OCR engine.
@@ -1179,37 +1786,21 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns `Ok(())` if initializing the OCR engine is successful, otherwise returns an error.
+    #[deprecated(note = "use `TesseractBuilder::traineddata` instead")]
     pub fn init_5(
         &self,
         data: &[u8],
         data_size: i32,
         language: &str,
-        oem: i32,
+        oem: TessOcrEngineMode,
         configs: &[&str],
     ) -> Result<()> {
-        let language = CString::new(language).unwrap();
-        let config_ptrs: Vec<_> = configs.iter().map(|&s| CString::new(s).unwrap()).collect();
-        let config_ptr_ptrs: Vec<_> = config_ptrs.iter().map(|cs| cs.as_ptr()).collect();
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
-        let result = unsafe {
-            TessBaseAPIInit5(
-                *handle,
-                data.as_ptr(),
-                data_size,
-                language.as_ptr(),
-                oem,
-                config_ptr_ptrs.as_ptr(),
-                config_ptrs.len() as c_int,
-            )
-        };
-        if result != 0 {
-            Err(TesseractError::InitError)
-        } else {
-            Ok(())
-        }
+        let data = data
+            .get(..data_size as usize)
+            .ok_or(TesseractError::InvalidParameterError)?;
+        let mut builder = legacy_builder("", language, oem, configs).traineddata(data);
+        builder.datapath = None;
+        self.init_with(&builder)
     }
 
     /// Sets the image for OCR processing.
@@ -1252,6 +1843,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
 
         unsafe {
             TessBaseAPISetImage(
@@ -1268,22 +1860,66 @@ impl TesseractAPI {
 
     /// Sets the image for OCR processing.
     ///
+    /// The engine keeps its own copy of the image, so `pix` can be dropped afterwards.
+    ///
     /// # Arguments
     ///
-    /// * `pix` - Pointer to the image data.
+    /// * `pix` - The image to process.
     ///
     /// # Returns
     ///
     /// Returns `Ok(())` if setting the image is successful, otherwise returns an error.
-    pub fn set_image_2(&self, pix: *mut c_void) -> Result<()> {
+    pub fn set_image_2(&self, pix: &Pix) -> Result<()> {
+        let pix_handle = pix
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
-        unsafe { TessBaseAPISetImage2(*handle, pix) };
+        self.results.invalidate();
+        unsafe { TessBaseAPISetImage2(*handle, *pix_handle) };
         Ok(())
     }
 
+    /// Decodes an image file and sets it for OCR processing.
+    ///
+    /// PNG, JPEG, TIFF, BMP and PNM files are supported. The path is also recorded as the
+    /// input name used by the result renderers.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the image file.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if decoding and setting the image is successful, otherwise returns an error.
+    #[cfg(feature = "image")]
+    pub fn set_image_from_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let pix = Pix::read(path.as_ref())?;
+        self.set_image_2(&pix)?;
+        if let Some(name) = path.as_ref().to_str() {
+            self.set_input_name(name)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes an in-memory image and sets it for OCR processing.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Encoded image data (PNG, JPEG, TIFF, BMP or PNM).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if decoding and setting the image is successful, otherwise returns an error.
+    #[cfg(feature = "image")]
+    pub fn set_image_from_bytes(&self, bytes: &[u8]) -> Result<()> {
+        let pix = Pix::read_from_memory(bytes)?;
+        self.set_image_2(&pix)
+    }
+
     /// Sets the source resolution for the image.
     ///
     /// # Arguments
@@ -1319,6 +1955,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         unsafe { TessBaseAPISetRectangle(*handle, left, top, width, height) };
         Ok(())
     }
@@ -1333,6 +1970,7 @@ impl TesseractAPI {
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.recognize_if_needed();
 
         // Check if handle is properly initialized
         if *handle == std::ptr::null_mut() {
@@ -1360,7 +1998,7 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns the iterator for the OCR results as a `ResultIterator` if successful, otherwise returns an error.
-    pub fn get_iterator(&self) -> Result<ResultIterator> {
+    pub fn get_iterator(&self) -> Result<ResultIterator<'_>> {
         let handle = self
             .handle
             .lock()
@@ -1369,15 +2007,21 @@ impl TesseractAPI {
         if iterator.is_null() {
             return Err(TesseractError::NullPointerError);
         }
-        Ok(ResultIterator::new(iterator))
+        Ok(ResultIterator::with_results(
+            iterator,
+            ResultsGuard::new(self),
+        ))
     }
 
     /// Gets the mutable iterator for the OCR results.
     ///
+    /// Words replaced or deleted through the iterator are changed in the results, so the
+    /// text getters and renderers called afterwards include the edits.
+    ///
     /// # Returns
     ///
-    /// Returns the mutable iterator for the OCR results as a `ResultIterator` if successful, otherwise returns an error.
-    pub fn get_mutable_iterator(&self) -> Result<ResultIterator> {
+    /// Returns the mutable iterator for the OCR results as a `MutableIterator` if successful, otherwise returns an error.
+    pub fn get_mutable_iterator(&self) -> Result<MutableIterator<'_>> {
         let handle = self
             .handle
             .lock()
@@ -1386,7 +2030,10 @@ impl TesseractAPI {
         if iterator.is_null() {
             return Err(TesseractError::NullPointerError);
         }
-        Ok(ResultIterator::new(iterator))
+        Ok(MutableIterator::with_results(
+            iterator,
+            ResultsGuard::new(self),
+        ))
     }
 
     /// Analyzes the layout of the image.
@@ -1394,16 +2041,20 @@ impl TesseractAPI {
     /// # Returns
     ///
     /// Returns the layout of the image as a `PageIterator` if successful, otherwise returns an error.
-    pub fn analyse_layout(&self) -> Result<PageIterator> {
+    pub fn analyse_layout(&self) -> Result<PageIterator<'_>> {
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         let iterator = unsafe { TessBaseAPIAnalyseLayout(*handle) };
         if iterator.is_null() {
             return Err(TesseractError::NullPointerError);
         }
-        Ok(PageIterator::new(iterator))
+        Ok(PageIterator::with_results(
+            iterator,
+            ResultsGuard::new(self),
+        ))
     }
 
     /// Gets the Unicode character for a given ID.
@@ -1427,20 +2078,27 @@ impl TesseractAPI {
     }
 
     /// Gets a page iterator for analyzing layout and getting bounding boxes
-    pub fn analyze_layout(&self) -> Result<PageIterator> {
+    pub fn analyze_layout(&self) -> Result<PageIterator<'_>> {
         let handle = self
             .handle
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
+        self.results.invalidate();
         let iterator = unsafe { TessBaseAPIAnalyseLayout(*handle) };
         if iterator.is_null() {
             return Err(TesseractError::NullPointerError);
         }
-        Ok(PageIterator::new(iterator))
+        Ok(PageIterator::with_results(
+            iterator,
+            ResultsGuard::new(self),
+        ))
     }
 
     /// Gets both page and result iterators for full text analysis
-    pub fn get_iterators(&self) -> Result<(PageIterator, ResultIterator)> {
+    ///
+    /// Both iterators walk the recognized results, so the page iterator reports the same
+    /// layout as the result iterator.
+    pub fn get_iterators(&self) -> Result<(PageIterator<'_>, ResultIterator<'_>)> {
         // Perform OCR operation
         self.recognize()?;
 
@@ -1449,27 +2107,45 @@ impl TesseractAPI {
             .lock()
             .map_err(|_| TesseractError::MutexLockError)?;
 
-        // Get both iterators
-        let page_iter = unsafe { TessBaseAPIAnalyseLayout(*handle) };
+        // `AnalyseLayout` would replace the recognized results with an unrecognized layout, so
+        // the page iterator is a second result iterator viewed as its page iterator base.
+        let page_iter = unsafe { TessBaseAPIGetIterator(*handle) };
         let result_iter = unsafe { TessBaseAPIGetIterator(*handle) };
 
         if page_iter.is_null() || result_iter.is_null() {
             if !page_iter.is_null() {
-                unsafe { TessPageIteratorDelete(page_iter) };
+                unsafe { TessResultIteratorDelete(page_iter) };
             }
             if !result_iter.is_null() {
                 unsafe { TessResultIteratorDelete(result_iter) };
             }
             return Err(TesseractError::NullPointerError);
         }
+        let page_iter = unsafe { TessResultIteratorGetPageIterator(page_iter) };
 
         Ok((
-            PageIterator::new(page_iter),
-            ResultIterator::new(result_iter),
+            PageIterator::with_results(page_iter, ResultsGuard::new(self)),
+            ResultIterator::with_results(result_iter, ResultsGuard::new(self)),
         ))
     }
 }
 
+/// Builds the equivalent of the deprecated `init_*` arguments.
+fn legacy_builder(
+    datapath: &str,
+    language: &str,
+    oem: TessOcrEngineMode,
+    configs: &[&str],
+) -> TesseractBuilder {
+    configs.iter().fold(
+        TesseractBuilder::new()
+            .datapath(datapath)
+            .language(language)
+            .oem(oem),
+        |builder, config| builder.config_file(config),
+    )
+}
+
 #[cfg(feature = "build-tesseract")]
 impl Drop for TesseractAPI {
     /// Drops the TesseractAPI instance.
@@ -1488,34 +2164,108 @@ impl Drop for TesseractAPI {
 impl Clone for TesseractAPI {
     /// Clones the TesseractAPI instance.
     /// A new `TessBaseAPI` handle is created and initialized with the cloned configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new instance fails to initialize. Use `try_clone` to handle the error.
     fn clone(&self) -> Self {
-        let config_clone = {
-            let config_guard = self.config.lock().unwrap();
-            config_guard.clone()
-        };
+        self.try_clone()
+            .expect("Failed to initialize cloned TesseractAPI")
+    }
+}
+
+/// Reads a parameter with each typed getter until one knows the name.
+fn read_parameter(handle: *mut c_void, name: &CStr) -> Result<Option<ParameterValue>> {
+    let mut int_value: c_int = 0;
+    if unsafe { TessBaseAPIGetIntVariable(handle, name.as_ptr(), &mut int_value) } != 0 {
+        return Ok(Some(ParameterValue::Int(int_value)));
+    }
+    let mut bool_value: c_int = 0;
+    if unsafe { TessBaseAPIGetBoolVariable(handle, name.as_ptr(), &mut bool_value) } != 0 {
+        return Ok(Some(ParameterValue::Bool(bool_value != 0)));
+    }
+    let mut double_value: c_double = 0.0;
+    if unsafe { TessBaseAPIGetDoubleVariable(handle, name.as_ptr(), &mut double_value) } != 0 {
+        return Ok(Some(ParameterValue::Double(double_value)));
+    }
+    let string_ptr = unsafe { TessBaseAPIGetStringVariable(handle, name.as_ptr()) };
+    if string_ptr.is_null() {
+        return Ok(None);
+    }
+    let value = unsafe { CStr::from_ptr(string_ptr) }.to_str()?;
+    Ok(Some(ParameterValue::String(value.to_owned())))
+}
+
+/// Makes sure the engine's parameters exist, so they can be read before initialization.
+fn allocate_parameters(handle: *mut c_void) -> Result<()> {
+    // Tesseract only allocates an engine's parameters on `Init` or the first `SetVariable`,
+    // which fails for the empty name without changing anything.
+    let empty = to_cstring("")?;
+    unsafe { TessBaseAPISetVariable(handle, empty.as_ptr(), empty.as_ptr()) };
+    Ok(())
+}
 
-        let new_api = TesseractAPI::new(); // Creates a new TessBaseAPI handle and an empty config
-
-        // Initialize the new API instance with the cloned configuration
-        if !config_clone.datapath.is_empty() {
-            new_api
-                .init(&config_clone.datapath, &config_clone.language)
-                .expect("Failed to initialize cloned TesseractAPI");
-            // Re-apply variables to the new instance as init might clear them
-            let mut new_config_guard = new_api.config.lock().unwrap();
-            new_config_guard.variables = config_clone.variables;
-            drop(new_config_guard); // Release lock before calling set_variable
-
-            let handle_guard = new_api.handle.lock().unwrap();
-            for (name, value) in new_api.config.lock().unwrap().variables.clone() {
-                // Clone again to iterate safely
-                new_api
-                    .set_variable_internal(&name, &value, *handle_guard)
-                    .expect("Failed to set variable on cloned TesseractAPI");
+/// A new, uninitialized engine whose parameters all have their default values.
+struct DefaultEngine(TesseractAPI);
+
+impl DefaultEngine {
+    fn new() -> Result<Self> {
+        let api = TesseractAPI::new();
+        {
+            let handle = api
+                .handle
+                .lock()
+                .map_err(|_| TesseractError::MutexLockError)?;
+            allocate_parameters(*handle)?;
+        }
+        Ok(DefaultEngine(api))
+    }
+
+    /// Reads the default value of a parameter, or `None` if there is no such parameter.
+    fn read(&self, name: &CStr) -> Result<Option<ParameterValue>> {
+        let handle = self
+            .0
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        read_parameter(*handle, name)
+    }
+}
+
+/// Builds the error for a parameter read or written with the wrong type.
+fn type_mismatch(name: &str, expected: ParameterType, actual: &ParameterValue) -> TesseractError {
+    TesseractError::ParameterTypeMismatch {
+        name: name.to_owned(),
+        expected,
+        actual: actual.parameter_type(),
+    }
+}
+
+/// Message Tesseract prints when it cannot open a config file.
+const CANT_OPEN_CONFIG: &str = "read_params_file: Can't open";
+
+/// Collects the output of `TessBaseAPIPrintVariables` through a temporary file.
+fn print_variables(handle: *mut c_void) -> Result<String> {
+    let mut output = Vec::new();
+    unsafe {
+        let file = libc::tmpfile();
+        if file.is_null() {
+            return Err(TesseractError::IoError);
+        }
+        TessBaseAPIPrintVariables(handle, file);
+        libc::fflush(file);
+        libc::rewind(file);
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read = libc::fread(buffer.as_mut_ptr() as *mut c_void, 1, buffer.len(), file);
+            if read == 0 {
+                break;
             }
+            output.extend_from_slice(&buffer[..read]);
         }
-        new_api
+        libc::fclose(file);
     }
+    Ok(String::from_utf8_lossy(&output).into_owned())
 }
 
 #[cfg(feature = "build-tesseract")]
@@ -1525,11 +2275,6 @@ extern "C" {
     pub fn TessVersion() -> *const c_char;
     pub fn TessBaseAPICreate() -> *mut c_void;
     pub fn TessBaseAPIDelete(handle: *mut c_void);
-    pub fn TessBaseAPIInit3(
-        handle: *mut c_void,
-        datapath: *const c_char,
-        language: *const c_char,
-    ) -> c_int;
     pub fn TessBaseAPIEnd(handle: *mut c_void);
     pub fn TessDeleteText(text: *mut c_char);
 
@@ -1580,9 +2325,21 @@ extern "C" {
         value: *const c_char,
     ) -> c_int;
     pub fn TessBaseAPIGetStringVariable(handle: *mut c_void, name: *const c_char) -> *const c_char;
-    pub fn TessBaseAPIGetIntVariable(handle: *mut c_void, name: *const c_char) -> c_int;
-    pub fn TessBaseAPIGetBoolVariable(handle: *mut c_void, name: *const c_char) -> c_int;
-    pub fn TessBaseAPIGetDoubleVariable(handle: *mut c_void, name: *const c_char) -> c_double;
+    pub fn TessBaseAPIGetIntVariable(
+        handle: *mut c_void,
+        name: *const c_char,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn TessBaseAPIGetBoolVariable(
+        handle: *mut c_void,
+        name: *const c_char,
+        value: *mut c_int,
+    ) -> c_int;
+    pub fn TessBaseAPIGetDoubleVariable(
+        handle: *mut c_void,
+        name: *const c_char,
+        value: *mut c_double,
+    ) -> c_int;
     pub fn TessBaseAPISetPageSegMode(handle: *mut c_void, mode: c_int);
     pub fn TessBaseAPIGetPageSegMode(handle: *mut c_void) -> c_int;
 
@@ -1611,6 +2368,7 @@ extern "C" {
         name: *const c_char,
         value: *const c_char,
     ) -> c_int;
+    pub fn TessBaseAPIPrintVariables(handle: *mut c_void, fp: *mut libc::FILE);
     pub fn TessBaseAPIPrintVariablesToFile(handle: *mut c_void, filename: *const c_char) -> c_int;
     pub fn TessBaseAPIInitForAnalysePage(handle: *mut c_void);
     pub fn TessBaseAPIReadConfigFile(handle: *mut c_void, filename: *const c_char);
@@ -1635,20 +2393,6 @@ extern "C" {
         out_degrees: *mut c_int,
         out_confidence: *mut c_float,
     );
-    pub fn TessBaseAPIInit1(
-        handle: *mut c_void,
-        datapath: *const c_char,
-        language: *const c_char,
-        oem: c_int,
-        configs: *const *const c_char,
-        configs_size: c_int,
-    ) -> c_int;
-    pub fn TessBaseAPIInit2(
-        handle: *mut c_void,
-        datapath: *const c_char,
-        language: *const c_char,
-        oem: c_int,
-    ) -> c_int;
     pub fn TessBaseAPIInit4(
         handle: *mut c_void,
         datapath: *const c_char,
@@ -1656,6 +2400,10 @@ extern "C" {
         oem: c_int,
         configs: *const *const c_char,
         configs_size: c_int,
+        vars_vec: *const *const c_char,
+        vars_values: *const *const c_char,
+        vars_vec_size: usize,
+        set_only_non_debug_params: c_int,
     ) -> c_int;
     pub fn TessBaseAPIInit5(
         handle: *mut c_void,
@@ -1665,6 +2413,11 @@ extern "C" {
         oem: c_int,
         configs: *const *const c_char,
         configs_size: c_int,
+        vars_vec: *const *const c_char,
+        vars_values: *const *const c_char,
+        vars_vec_size: usize,
+        set_only_non_debug_params: c_int,
     ) -> c_int;
+    pub fn TessBaseAPIOem(handle: *mut c_void) -> c_int;
     pub fn TessBaseAPIGetUnichar(handle: *mut c_void, unichar_id: c_int) -> *const c_char;
 }
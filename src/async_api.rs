@@ -0,0 +1,237 @@
+use crate::error::{Result, TesseractError};
+use crate::page::Page;
+use crate::pix::Pix;
+use crate::{TessMonitor, TesseractAPI};
+use futures_channel::oneshot;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+type Job = Box<dyn FnOnce(&TesseractAPI) + Send>;
+
+/// Asynchronous front end for a `TesseractAPI` running on a dedicated worker thread.
+///
+/// Jobs are executed one at a time in submission order, so calls such as `set_image` followed
+/// by `get_utf8_text` behave as they do on the blocking API. Every method returns a
+/// `TesseractFuture` that can be awaited from any executor; the caller's thread never blocks
+/// on Tesseract.
+///
+/// Dropping the handle does not block either: the jobs already submitted still run, then the
+/// worker releases the engine and exits. Await `shutdown` to wait for that.
+pub struct AsyncTesseract {
+    sender: mpsc::Sender<Job>,
+}
+
+impl AsyncTesseract {
+    /// Moves an engine onto a new worker thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The engine, usually already initialized.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance if the worker thread could be started, otherwise returns an error.
+    pub fn new(api: TesseractAPI) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        // The worker is detached; it exits once every sender is dropped and the queue is empty.
+        std::thread::Builder::new()
+            .name("tesseract-worker".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    job(&api);
+                }
+            })
+            .map_err(|_| TesseractError::IoError)?;
+        Ok(AsyncTesseract { sender })
+    }
+
+    /// Stops the worker once the jobs submitted so far have run.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to `Ok(())` once the earlier jobs have run; the worker then
+    /// releases the engine and exits.
+    pub fn shutdown(self) -> TesseractFuture<()> {
+        // Jobs run in order, so this one completes after every earlier job.
+        self.run(|_| Ok(()))
+    }
+
+    /// Runs a closure on the worker thread.
+    ///
+    /// Dropping the returned future before the job starts skips the job.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Closure receiving the engine.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to the closure's result.
+    pub fn run<F, T>(&self, f: F) -> TesseractFuture<T>
+    where
+        F: FnOnce(&TesseractAPI) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.submit(None, f)
+    }
+
+    /// Runs a closure on the worker thread with a fresh monitor.
+    ///
+    /// Dropping the returned future cancels the monitor, stopping a recognition that is in
+    /// progress at the next word.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Closure receiving the engine and the monitor to recognize with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to the closure's result.
+    pub fn run_with_monitor<F, T>(&self, f: F) -> TesseractFuture<T>
+    where
+        F: FnOnce(&TesseractAPI, &TessMonitor) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let monitor = Arc::new(TessMonitor::new());
+        let job_monitor = Arc::clone(&monitor);
+        self.submit(Some(monitor), move |api| f(api, &job_monitor))
+    }
+
+    /// Sets an image from raw pixel data.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_data` - Raw image data.
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    /// * `bytes_per_pixel` - Number of bytes per pixel.
+    /// * `bytes_per_line` - Number of bytes per line.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to `Ok(())` if setting the image is successful.
+    pub fn set_image(
+        &self,
+        image_data: Vec<u8>,
+        width: i32,
+        height: i32,
+        bytes_per_pixel: i32,
+        bytes_per_line: i32,
+    ) -> TesseractFuture<()> {
+        self.run(move |api| {
+            api.set_image(&image_data, width, height, bytes_per_pixel, bytes_per_line)
+        })
+    }
+
+    /// Sets a `Pix` image.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - The image.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to `Ok(())` if setting the image is successful.
+    pub fn set_pix(&self, pix: Pix) -> TesseractFuture<()> {
+        self.run(move |api| api.set_image_2(&pix))
+    }
+
+    /// Recognizes the text in the current image.
+    ///
+    /// Dropping the future cancels the recognition.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to `Ok(())` if recognition is successful.
+    pub fn recognize(&self) -> TesseractFuture<()> {
+        self.run_with_monitor(|api, monitor| api.recognize_with_monitor(monitor))
+    }
+
+    /// Recognizes the current image and gets the UTF-8 text.
+    ///
+    /// Dropping the future cancels the recognition.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to the recognized text.
+    pub fn get_utf8_text(&self) -> TesseractFuture<String> {
+        self.run_with_monitor(|api, monitor| {
+            api.recognize_with_monitor(monitor)?;
+            api.get_utf8_text()
+        })
+    }
+
+    /// Recognizes the current image and collects the full result hierarchy.
+    ///
+    /// Dropping the future cancels the recognition.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to the recognized `Page`.
+    pub fn recognize_page(&self) -> TesseractFuture<Page> {
+        self.run_with_monitor(|api, monitor| {
+            api.recognize_with_monitor(monitor)?;
+            let iterator = api.get_iterator()?;
+            Page::from_result_iterator(&iterator)
+        })
+    }
+
+    fn submit<F, T>(&self, monitor: Option<Arc<TessMonitor>>, f: F) -> TesseractFuture<T>
+    where
+        F: FnOnce(&TesseractAPI) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (result_sender, receiver) = oneshot::channel();
+        let job: Job = Box::new(move |api| {
+            if result_sender.is_canceled() {
+                return;
+            }
+            let _ = result_sender.send(f(api));
+        });
+        // A send error drops the job, which resolves the future with `WorkerStopped`.
+        let _ = self.sender.send(job);
+        TesseractFuture {
+            receiver,
+            monitor,
+            done: false,
+        }
+    }
+}
+
+/// Future returned by the `AsyncTesseract` methods.
+///
+/// Dropping it before completion skips the job if it has not started yet and cancels a
+/// monitored recognition that is already running.
+#[must_use = "futures do nothing unless awaited; dropping it cancels the job"]
+pub struct TesseractFuture<T> {
+    receiver: oneshot::Receiver<Result<T>>,
+    monitor: Option<Arc<TessMonitor>>,
+    done: bool,
+}
+
+impl<T> Future for TesseractFuture<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(result) => {
+                self.done = true;
+                Poll::Ready(result.unwrap_or(Err(TesseractError::WorkerStopped)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for TesseractFuture<T> {
+    fn drop(&mut self) {
+        if !self.done {
+            if let Some(monitor) = &self.monitor {
+                monitor.cancel();
+            }
+        }
+    }
+}
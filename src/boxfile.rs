@@ -0,0 +1,331 @@
+use crate::error::{Result, TesseractError};
+use crate::page::{BoundingBox, Page};
+use std::path::Path;
+
+/// Layout of a box file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoxFormat {
+    /// One line per character, as written by `TesseractAPI::get_box_text`.
+    Char,
+    /// One line per character with a space entry between words and a tab entry at the end of
+    /// each line, as written by `TesseractAPI::get_lstm_box_text`.
+    Lstm,
+    /// One `WordStr` line per text line followed by a tab entry, as written by
+    /// `TesseractAPI::get_word_str_box_text`.
+    WordStr,
+}
+
+/// An entry of a box file.
+///
+/// Coordinates use the box file convention: the origin is the bottom-left corner of the
+/// image, so `top` is greater than `bottom`. Use `bbox` and `from_bbox` to convert from and
+/// to the top-left origin used by the rest of the crate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoxEntry {
+    /// The character, the line text for `WordStr` entries, `" "` between words or `"\t"` at
+    /// the end of a line.
+    pub text: String,
+    pub left: i32,
+    pub bottom: i32,
+    pub right: i32,
+    pub top: i32,
+    /// Zero-based page number.
+    pub page: u32,
+}
+
+impl BoxEntry {
+    /// Creates an entry from a bounding box in image coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text of the entry.
+    /// * `bbox` - Bounding box with the origin at the top-left corner.
+    /// * `image_height` - Height of the page image.
+    /// * `page` - Zero-based page number.
+    ///
+    /// # Returns
+    ///
+    /// Returns the entry with its coordinates flipped to the bottom-left origin.
+    pub fn from_bbox(text: &str, bbox: BoundingBox, image_height: i32, page: u32) -> Self {
+        BoxEntry {
+            text: text.to_owned(),
+            left: bbox.left,
+            bottom: image_height - bbox.bottom,
+            right: bbox.right,
+            top: image_height - bbox.top,
+            page,
+        }
+    }
+
+    /// Gets the bounding box in image coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_height` - Height of the page image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bounding box with the origin at the top-left corner.
+    pub fn bbox(&self, image_height: i32) -> BoundingBox {
+        BoundingBox::new(
+            self.left,
+            image_height - self.top,
+            self.right,
+            image_height - self.bottom,
+        )
+    }
+
+    /// Checks whether the entry marks the end of a text line.
+    pub fn is_line_end(&self) -> bool {
+        self.text == "\t"
+    }
+
+    /// Checks whether the entry marks the space between two words.
+    pub fn is_space(&self) -> bool {
+        self.text == " "
+    }
+}
+
+/// Contents of a box file, used for training and fine-tuning.
+///
+/// ```no_run
+/// use tesseract_rs::BoxFile;
+///
+/// let mut boxes = BoxFile::read("eng.sample.exp0.box")?;
+/// for entry in &mut boxes.entries {
+///     if entry.text == "0" {
+///         entry.text = "O".to_owned();
+///     }
+/// }
+/// boxes.write("eng.sample.exp0.box")?;
+/// # Ok::<(), tesseract_rs::TesseractError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxFile {
+    pub format: BoxFormat,
+    pub entries: Vec<BoxEntry>,
+}
+
+impl BoxFile {
+    /// Parses a box file.
+    ///
+    /// The format is detected from the contents: `WordStr` lines select `BoxFormat::WordStr`,
+    /// space or tab entries select `BoxFormat::Lstm`, and anything else is `BoxFormat::Char`.
+    /// Fields may be separated by spaces or tabs.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Box file contents.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed entries if successful, otherwise returns a `ParseError` naming the
+    /// offending line.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut format = BoxFormat::Char;
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = parse_entry(line).ok_or_else(|| TesseractError::ParseError {
+                format: "box file",
+                message: format!("line {}: invalid entry '{}'", index + 1, line),
+            })?;
+            if line.starts_with("WordStr ") {
+                format = BoxFormat::WordStr;
+            } else if format == BoxFormat::Char && (entry.is_space() || entry.is_line_end()) {
+                format = BoxFormat::Lstm;
+            }
+            entries.push(entry);
+        }
+        Ok(BoxFile { format, entries })
+    }
+
+    /// Reads a box file from disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the box file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed entries if successful, otherwise returns an error.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| file_error(path, e))?;
+        Self::parse(&text)
+    }
+
+    /// Builds a character-level box file from a recognized page.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The recognized page.
+    /// * `image_height` - Height of the page image.
+    /// * `page_number` - Zero-based page number.
+    ///
+    /// # Returns
+    ///
+    /// Returns one entry per recognized symbol.
+    pub fn from_page(page: &Page, image_height: i32, page_number: u32) -> Self {
+        BoxFile {
+            format: BoxFormat::Char,
+            entries: page
+                .words()
+                .flat_map(|word| &word.symbols)
+                .map(|symbol| {
+                    BoxEntry::from_bbox(&symbol.text, symbol.bbox, image_height, page_number)
+                })
+                .collect(),
+        }
+    }
+
+    /// Formats the entries in the file's format.
+    ///
+    /// # Returns
+    ///
+    /// Returns the box file contents, one entry per line.
+    pub fn to_box_string(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let coordinates = format!(
+                    "{} {} {} {} {}",
+                    entry.left, entry.bottom, entry.right, entry.top, entry.page
+                );
+                if self.format == BoxFormat::WordStr && !entry.is_line_end() {
+                    format!("WordStr {} #{}\n", coordinates, entry.text)
+                } else {
+                    format!("{} {}\n", entry.text, coordinates)
+                }
+            })
+            .collect()
+    }
+
+    /// Writes the entries to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the box file to write.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the file was written, otherwise returns an error.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_box_string()).map_err(|e| file_error(path, e))
+    }
+}
+
+fn file_error(path: &Path, error: std::io::Error) -> TesseractError {
+    TesseractError::FileError {
+        path: path.display().to_string(),
+        output: error.to_string(),
+    }
+}
+
+/// Parses one line, reading the five numbers from the right so that the text may itself be a
+/// space or a tab.
+fn parse_entry(line: &str) -> Option<BoxEntry> {
+    if let Some(rest) = line.strip_prefix("WordStr ") {
+        let (coordinates, text) = rest.split_once('#')?;
+        let numbers: Vec<&str> = coordinates.split_whitespace().collect();
+        return entry_from_fields(text, &numbers);
+    }
+    let mut rest = line;
+    let mut numbers = Vec::with_capacity(5);
+    for _ in 0..5 {
+        let split = rest.rfind([' ', '\t'])?;
+        numbers.push(&rest[split + 1..]);
+        rest = &rest[..split];
+    }
+    numbers.reverse();
+    if rest.is_empty() {
+        return None;
+    }
+    entry_from_fields(rest, &numbers)
+}
+
+fn entry_from_fields(text: &str, numbers: &[&str]) -> Option<BoxEntry> {
+    let [left, bottom, right, top, page] = numbers else {
+        return None;
+    };
+    Some(BoxEntry {
+        text: text.to_owned(),
+        left: left.parse().ok()?,
+        bottom: bottom.parse().ok()?,
+        right: right.parse().ok()?,
+        top: top.parse().ok()?,
+        page: page.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_char_boxes() {
+        let boxes = BoxFile::parse("H 36 84 52 108 0\ni 54 84 60 110 0\n").unwrap();
+        assert_eq!(boxes.format, BoxFormat::Char);
+        assert_eq!(
+            boxes.entries[0],
+            BoxEntry {
+                text: "H".to_owned(),
+                left: 36,
+                bottom: 84,
+                right: 52,
+                top: 108,
+                page: 0,
+            }
+        );
+        assert_eq!(
+            boxes.to_box_string(),
+            "H 36 84 52 108 0\ni 54 84 60 110 0\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_lstm_boxes() {
+        let text = "H 36 84 52 108 0\ni 54 84 60 110 0\n  61 84 70 110 0\n\u{e9} 70 84 80 110 0\n\t 81 84 82 110 0\n";
+        let boxes = BoxFile::parse(text).unwrap();
+        assert_eq!(boxes.format, BoxFormat::Lstm);
+        assert!(boxes.entries[2].is_space());
+        assert_eq!(boxes.entries[3].text, "\u{e9}");
+        assert!(boxes.entries[4].is_line_end());
+        assert_eq!(boxes.to_box_string(), text);
+    }
+
+    #[test]
+    fn test_parse_word_str_boxes() {
+        let text = "WordStr 36 84 80 110 0 #Hi # there\n\t 81 84 82 110 0\n";
+        let boxes = BoxFile::parse(text).unwrap();
+        assert_eq!(boxes.format, BoxFormat::WordStr);
+        assert_eq!(boxes.entries[0].text, "Hi # there");
+        assert_eq!(boxes.entries[0].right, 80);
+        assert!(boxes.entries[1].is_line_end());
+        assert_eq!(boxes.to_box_string(), text);
+    }
+
+    #[test]
+    fn test_parse_tab_separated_fields() {
+        let boxes = BoxFile::parse("a\t1\t2\t3\t4\t1\r\n").unwrap();
+        assert_eq!(boxes.entries[0].text, "a");
+        assert_eq!(boxes.entries[0].page, 1);
+        assert!(matches!(
+            BoxFile::parse("a 1 2 3 4 0\nb 1 2 x 4 0\n"),
+            Err(TesseractError::ParseError { message, .. }) if message.starts_with("line 2")
+        ));
+        assert!(BoxFile::parse("1 2 3 4 0\n").is_err());
+    }
+
+    #[test]
+    fn test_origin_conversion() {
+        let bbox = BoundingBox::new(36, 20, 52, 44);
+        let entry = BoxEntry::from_bbox("H", bbox, 128, 2);
+        assert_eq!((entry.bottom, entry.top), (84, 108));
+        assert_eq!(entry.bbox(128), bbox);
+    }
+}
@@ -0,0 +1,363 @@
+use crate::enums::TessOcrEngineMode;
+use crate::error::Result;
+use crate::TesseractAPI;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Builder describing how a `TesseractAPI` is initialized.
+///
+/// Every way of initializing the engine goes through a builder, which the engine keeps so that
+/// `Clone`, `TesseractAPI::try_clone` and `TesseractPool` can repeat the same initialization.
+///
+/// ```no_run
+/// use tesseract_rs::{TessOcrEngineMode, TesseractBuilder};
+///
+/// let api = TesseractBuilder::new()
+///     .datapath("/usr/share/tessdata")
+///     .language("eng")
+///     .language("tur")
+///     .oem(TessOcrEngineMode::OEM_LSTM_ONLY)
+///     .init_variable("load_system_dawg", "0")
+///     .variable("tessedit_char_whitelist", "0123456789")
+///     .build()?;
+/// # Ok::<(), tesseract_rs::TesseractError>(())
+/// ```
+#[derive(Clone)]
+pub struct TesseractBuilder {
+    pub(crate) datapath: Option<String>,
+    pub(crate) languages: Vec<String>,
+    pub(crate) oem: TessOcrEngineMode,
+    pub(crate) configs: Vec<String>,
+    pub(crate) init_variables: Vec<(String, String)>,
+    pub(crate) set_only_non_debug_params: bool,
+    pub(crate) traineddata: Option<Arc<[u8]>>,
+    pub(crate) analyse_only: bool,
+    pub(crate) variables: Vec<(String, String)>,
+}
+
+impl Default for TesseractBuilder {
+    fn default() -> Self {
+        TesseractBuilder {
+            datapath: None,
+            languages: Vec::new(),
+            oem: TessOcrEngineMode::OEM_DEFAULT,
+            configs: Vec::new(),
+            init_variables: Vec::new(),
+            set_only_non_debug_params: false,
+            traineddata: None,
+            analyse_only: false,
+            variables: Vec::new(),
+        }
+    }
+}
+
+/// Shows the size of in-memory traineddata instead of its bytes.
+impl fmt::Debug for TesseractBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("TesseractBuilder");
+        debug
+            .field("datapath", &self.datapath)
+            .field("languages", &self.languages)
+            .field("oem", &self.oem)
+            .field("configs", &self.configs)
+            .field("init_variables", &self.init_variables)
+            .field("set_only_non_debug_params", &self.set_only_non_debug_params);
+        match &self.traineddata {
+            Some(data) => debug.field("traineddata", &format_args!("<{} bytes>", data.len())),
+            None => debug.field("traineddata", &format_args!("None")),
+        };
+        debug
+            .field("analyse_only", &self.analyse_only)
+            .field("variables", &self.variables)
+            .finish()
+    }
+}
+
+/// Compares in-memory traineddata by identity, so builders are equal only if they share the
+/// same data, as clones do.
+impl PartialEq for TesseractBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        let same_traineddata = match (&self.traineddata, &other.traineddata) {
+            (Some(data), Some(other_data)) => Arc::ptr_eq(data, other_data),
+            (None, None) => true,
+            _ => false,
+        };
+        same_traineddata
+            && self.datapath == other.datapath
+            && self.languages == other.languages
+            && self.oem == other.oem
+            && self.configs == other.configs
+            && self.init_variables == other.init_variables
+            && self.set_only_non_debug_params == other.set_only_non_debug_params
+            && self.analyse_only == other.analyse_only
+            && self.variables == other.variables
+    }
+}
+
+impl TesseractBuilder {
+    /// Creates a builder for the default engine mode and English.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory containing the traineddata files.
+    ///
+    /// Without a datapath, Tesseract uses `TESSDATA_PREFIX` or its compiled-in default.
+    ///
+    /// # Arguments
+    ///
+    /// * `datapath` - Path to the tessdata directory.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn datapath<P: AsRef<Path>>(mut self, datapath: P) -> Self {
+        self.datapath = Some(datapath.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    /// Adds a language to load.
+    ///
+    /// Languages are joined with `+` in the order they were added. Without any language,
+    /// English (`eng`) is loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - Language code (e.g., "eng" for English, "tur" for Turkish).
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn language(mut self, language: &str) -> Self {
+        self.languages.push(language.to_owned());
+        self
+    }
+
+    /// Adds several languages to load.
+    ///
+    /// # Arguments
+    ///
+    /// * `languages` - Language codes.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.languages
+            .extend(languages.into_iter().map(|l| l.as_ref().to_owned()));
+        self
+    }
+
+    /// Sets the OCR engine mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `oem` - The engine mode.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn oem(mut self, oem: TessOcrEngineMode) -> Self {
+        self.oem = oem;
+        self
+    }
+
+    /// Adds a config file to read during initialization.
+    ///
+    /// Names without a path are looked up in the `tessdata/configs` directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Config file name or path.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn config_file(mut self, config: &str) -> Self {
+        self.configs.push(config.to_owned());
+        self
+    }
+
+    /// Sets a variable during initialization.
+    ///
+    /// Init-only variables such as `load_system_dawg` take effect only when passed here.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable.
+    /// * `value` - Value of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn init_variable(mut self, name: &str, value: &str) -> Self {
+        self.init_variables
+            .push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Loads additional dictionary words from a file (`user_words_file`).
+    ///
+    /// The dictionary is loaded during initialization, so unlike the other typed settings this
+    /// is an init variable rather than a `TesseractAPI` setter.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a file with one word per line.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn user_words_file<P: AsRef<Path>>(self, path: P) -> Self {
+        let path = path.as_ref().to_string_lossy().into_owned();
+        self.init_variable("user_words_file", &path)
+    }
+
+    /// Restricts the init variables and config files to non-debug parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `non_debug_only` - Whether debug parameters are ignored.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn set_only_non_debug_params(mut self, non_debug_only: bool) -> Self {
+        self.set_only_non_debug_params = non_debug_only;
+        self
+    }
+
+    /// Loads the language from an in-memory traineddata file instead of the datapath.
+    ///
+    /// The data is shared, not copied, when the engine is cloned or pooled.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Contents of a `.traineddata` file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn traineddata(mut self, data: impl Into<Arc<[u8]>>) -> Self {
+        self.traineddata = Some(data.into());
+        self
+    }
+
+    /// Initializes for layout analysis only, without loading any language.
+    ///
+    /// # Arguments
+    ///
+    /// * `analyse_only` - Whether only layout analysis is needed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn analyse_only(mut self, analyse_only: bool) -> Self {
+        self.analyse_only = analyse_only;
+        self
+    }
+
+    /// Sets a variable after initialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable.
+    /// * `value` - Value of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated builder.
+    pub fn variable(mut self, name: &str, value: &str) -> Self {
+        self.variables.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Creates and initializes an engine.
+    ///
+    /// # Returns
+    ///
+    /// Returns the initialized `TesseractAPI` if successful, otherwise returns an error.
+    pub fn build(&self) -> Result<TesseractAPI> {
+        let api = TesseractAPI::new();
+        api.init_with(self)?;
+        Ok(api)
+    }
+
+    /// Gets the language string passed to Tesseract.
+    pub(crate) fn language_string(&self) -> String {
+        if self.languages.is_empty() {
+            "eng".to_owned()
+        } else {
+            self.languages.join("+")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_string() {
+        assert_eq!(TesseractBuilder::new().language_string(), "eng");
+        assert_eq!(
+            TesseractBuilder::new()
+                .language("eng")
+                .languages(["tur", "deu"])
+                .language_string(),
+            "eng+tur+deu"
+        );
+    }
+
+    #[test]
+    fn test_builder_records_settings() {
+        let builder = TesseractBuilder::new()
+            .datapath("/tmp/tessdata")
+            .oem(TessOcrEngineMode::OEM_LSTM_ONLY)
+            .config_file("digits")
+            .init_variable("load_system_dawg", "0")
+            .variable("tessedit_char_whitelist", "0123456789")
+            .traineddata(vec![1u8, 2, 3]);
+
+        assert_eq!(builder.datapath.as_deref(), Some("/tmp/tessdata"));
+        assert_eq!(builder.oem, TessOcrEngineMode::OEM_LSTM_ONLY);
+        assert_eq!(builder.configs, vec!["digits".to_owned()]);
+        assert_eq!(
+            builder.init_variables,
+            vec![("load_system_dawg".to_owned(), "0".to_owned())]
+        );
+        assert_eq!(builder.variables.len(), 1);
+
+        let clone = builder.clone();
+        assert!(Arc::ptr_eq(
+            builder.traineddata.as_ref().unwrap(),
+            clone.traineddata.as_ref().unwrap()
+        ));
+        assert_eq!(clone, builder);
+    }
+
+    #[test]
+    fn test_builder_debug_and_eq_skip_traineddata_bytes() {
+        let builder = TesseractBuilder::new()
+            .language("eng")
+            .traineddata(vec![7u8; 1024]);
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains("traineddata: <1024 bytes>"));
+        assert!(!debug.contains("7, 7"));
+        assert!(format!("{:?}", TesseractBuilder::new()).contains("traineddata: None"));
+
+        assert_eq!(builder.clone(), builder);
+        assert_ne!(builder.clone().traineddata(vec![7u8; 1024]), builder);
+        assert_ne!(TesseractBuilder::new().language("eng"), builder);
+        assert_eq!(TesseractBuilder::new(), TesseractBuilder::default());
+    }
+}
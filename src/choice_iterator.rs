@@ -1,5 +1,5 @@
-use crate::api::TessDeleteText;
 use crate::error::{Result, TesseractError};
+use crate::result_iterator::ResultIterator;
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_float, c_int, c_void};
 use std::sync::{Arc, Mutex};
@@ -50,10 +50,9 @@ impl ChoiceIterator {
         if text_ptr.is_null() {
             return Err(TesseractError::NullPointerError);
         }
+        // The text belongs to the engine's character set and must not be freed.
         let c_str = unsafe { CStr::from_ptr(text_ptr) };
-        let result = c_str.to_str()?.to_owned();
-        unsafe { TessDeleteText(text_ptr) };
-        Ok(result)
+        Ok(c_str.to_str()?.to_owned())
     }
 
     /// Gets the confidence of the current choice.
@@ -78,9 +77,52 @@ impl Drop for ChoiceIterator {
     }
 }
 
+/// Alternatives for the symbol a `ResultIterator` was positioned at, created by
+/// `ResultIterator::choices`.
+///
+/// Yields each alternative text with its confidence, best first. The alternatives stay valid
+/// while the `ResultIterator` is borrowed, even if it is advanced in the meantime, and end
+/// early if the results are cleared or replaced.
+pub struct Choices<'a> {
+    iterator: ChoiceIterator,
+    result: &'a ResultIterator<'a>,
+    exhausted: bool,
+}
+
+impl<'a> Choices<'a> {
+    pub(crate) fn new(iterator: ChoiceIterator, result: &'a ResultIterator<'a>) -> Self {
+        Choices {
+            iterator,
+            result,
+            exhausted: false,
+        }
+    }
+}
+
+impl Iterator for Choices<'_> {
+    type Item = (String, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        let Ok(_results) = self.result.lock() else {
+            self.exhausted = true;
+            return None;
+        };
+        let choice = match (self.iterator.get_utf8_text(), self.iterator.confidence()) {
+            (Ok(text), Ok(confidence)) => Some((text, confidence)),
+            _ => None,
+        };
+        // The iterator starts at the first choice, so it is advanced after reading.
+        self.exhausted = choice.is_none() || !self.iterator.next().unwrap_or(false);
+        choice
+    }
+}
+
 extern "C" {
     fn TessChoiceIteratorDelete(handle: *mut c_void);
     fn TessChoiceIteratorNext(handle: *mut c_void) -> c_int;
-    fn TessChoiceIteratorGetUTF8Text(handle: *mut c_void) -> *mut c_char;
+    fn TessChoiceIteratorGetUTF8Text(handle: *mut c_void) -> *const c_char;
     fn TessChoiceIteratorConfidence(handle: *mut c_void) -> c_float;
 }
@@ -0,0 +1,99 @@
+use crate::error::{Result, TesseractError};
+use crate::pix::Pix;
+use crate::{TessResultRenderer, TesseractAPI};
+
+/// A document being rendered page by page, created by `TesseractAPI::begin_document`.
+///
+/// The renderer's document is begun when the session is created and ended by `finish`, or
+/// when the session is dropped if `finish` was not called. Every call reports failures as a
+/// `TesseractError` instead of the `bool` returned by `TessResultRenderer`.
+pub struct Document<'a> {
+    api: &'a TesseractAPI,
+    renderer: &'a TessResultRenderer,
+    pages: usize,
+    finished: bool,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn begin(
+        api: &'a TesseractAPI,
+        renderer: &'a TessResultRenderer,
+        title: &str,
+    ) -> Result<Self> {
+        if !renderer.begin_document(title) {
+            return Err(TesseractError::RenderError);
+        }
+        Ok(Document {
+            api,
+            renderer,
+            pages: 0,
+            finished: false,
+        })
+    }
+
+    /// Adds the image currently set on the engine as the next page.
+    ///
+    /// The image is recognized first unless it already was, so recognition errors are
+    /// reported instead of producing an empty page.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the page was added, otherwise returns `OcrError` if recognition
+    /// failed or `RenderError` if the renderer failed.
+    pub fn add_page(&mut self) -> Result<()> {
+        if !self.api.results.is_recognized() {
+            self.api.recognize()?;
+        }
+        if !self.renderer.add_image(self.api) {
+            return Err(TesseractError::RenderError);
+        }
+        self.pages += 1;
+        Ok(())
+    }
+
+    /// Sets an image on the engine and adds it as the next page.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - The page image.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the page was added, otherwise returns an error.
+    pub fn add_pix(&mut self, pix: &Pix) -> Result<()> {
+        self.api.set_image_2(pix)?;
+        self.add_page()
+    }
+
+    /// Gets the number of pages added so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of pages added successfully.
+    pub fn page_count(&self) -> usize {
+        self.pages
+    }
+
+    /// Ends the document, writing what the renderer keeps until the end, such as the PDF
+    /// trailer.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the document was ended, otherwise returns `RenderError`.
+    pub fn finish(mut self) -> Result<()> {
+        self.finished = true;
+        if self.renderer.end_document() {
+            Ok(())
+        } else {
+            Err(TesseractError::RenderError)
+        }
+    }
+}
+
+impl Drop for Document<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.renderer.end_document();
+        }
+    }
+}
@@ -196,6 +196,51 @@ impl TessTextlineOrder {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum TessOcrEngineMode {
+    OEM_TESSERACT_ONLY = 0,
+    OEM_LSTM_ONLY = 1,
+    OEM_TESSERACT_LSTM_COMBINED = 2,
+    OEM_DEFAULT = 3,
+}
+
+impl TessOcrEngineMode {
+    pub fn from_int(value: i32) -> Self {
+        match value {
+            0 => TessOcrEngineMode::OEM_TESSERACT_ONLY,
+            1 => TessOcrEngineMode::OEM_LSTM_ONLY,
+            2 => TessOcrEngineMode::OEM_TESSERACT_LSTM_COMBINED,
+            _ => TessOcrEngineMode::OEM_DEFAULT,
+        }
+    }
+}
+
+/// Values of the `lstm_choice_mode` parameter, which controls the alternatives the LSTM
+/// engine keeps for each symbol.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum TessLstmChoiceMode {
+    /// No alternatives are kept (the default).
+    LSTM_CHOICE_OFF = 0,
+    /// Alternatives are kept for every timestep of the network output.
+    LSTM_CHOICE_TIMESTEPS = 1,
+    /// Alternatives are extracted from the CTC decoding and mapped to each symbol.
+    LSTM_CHOICE_SYMBOLS = 2,
+}
+
+impl TessLstmChoiceMode {
+    pub fn from_int(value: i32) -> Self {
+        match value {
+            1 => TessLstmChoiceMode::LSTM_CHOICE_TIMESTEPS,
+            2 => TessLstmChoiceMode::LSTM_CHOICE_SYMBOLS,
+            _ => TessLstmChoiceMode::LSTM_CHOICE_OFF,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,5 +365,40 @@ mod tests {
         assert_copy::<TessParagraphJustification>();
         assert_copy::<TessWritingDirection>();
         assert_copy::<TessTextlineOrder>();
+        assert_copy::<TessOcrEngineMode>();
+        assert_copy::<TessLstmChoiceMode>();
+    }
+
+    #[test]
+    fn test_lstm_choice_mode_from_int() {
+        assert_eq!(
+            TessLstmChoiceMode::from_int(2),
+            TessLstmChoiceMode::LSTM_CHOICE_SYMBOLS
+        );
+        assert_eq!(
+            TessLstmChoiceMode::from_int(1),
+            TessLstmChoiceMode::LSTM_CHOICE_TIMESTEPS
+        );
+        assert_eq!(
+            TessLstmChoiceMode::from_int(7),
+            TessLstmChoiceMode::LSTM_CHOICE_OFF
+        );
+    }
+
+    #[test]
+    fn test_ocr_engine_mode_from_int() {
+        assert_eq!(
+            TessOcrEngineMode::from_int(0),
+            TessOcrEngineMode::OEM_TESSERACT_ONLY
+        );
+        assert_eq!(
+            TessOcrEngineMode::from_int(1),
+            TessOcrEngineMode::OEM_LSTM_ONLY
+        );
+        assert_eq!(
+            TessOcrEngineMode::from_int(42),
+            TessOcrEngineMode::OEM_DEFAULT
+        );
+        assert_eq!(TessOcrEngineMode::OEM_DEFAULT as i32, 3);
     }
 }
@@ -1,11 +1,23 @@
+use crate::enums::TessOcrEngineMode;
+use crate::parameters::ParameterType;
+use std::ffi::CString;
 use std::str::Utf8Error;
 use thiserror::Error;
 
 /// Errors that can occur when using the Tesseract API.
 #[derive(Error, Debug)]
 pub enum TesseractError {
-    #[error("Failed to initialize Tesseract")]
-    InitError,
+    #[error(
+        "Failed to initialize Tesseract with language '{language}' ({oem:?}) from datapath {}{}",
+        datapath_display(.datapath),
+        output_suffix(.output)
+    )]
+    InitError {
+        datapath: Option<String>,
+        language: String,
+        oem: TessOcrEngineMode,
+        output: String,
+    },
     #[error("Failed to set image")]
     SetImageError,
     #[error("OCR operation failed")]
@@ -14,18 +26,24 @@ pub enum TesseractError {
     Utf8Error(#[from] Utf8Error),
     #[error("Failed to lock mutex")]
     MutexLockError,
-    #[error("Failed to set variable")]
-    SetVariableError,
-    #[error("Failed to get variable")]
-    GetVariableError,
+    #[error("Failed to set variable '{name}' to '{value}'")]
+    SetVariableError { name: String, value: String },
+    #[error("Unknown Tesseract parameter '{name}'")]
+    UnknownParameter { name: String },
+    #[error("Tesseract parameter '{name}' is of type {actual}, not {expected}")]
+    ParameterTypeMismatch {
+        name: String,
+        expected: ParameterType,
+        actual: ParameterType,
+    },
     #[error("Null pointer error")]
     NullPointerError,
     #[error("Invalid parameter")]
     InvalidParameterError,
     #[error("Layout analysis failed")]
     AnalyseLayoutError,
-    #[error("Page processing failed")]
-    ProcessPagesError,
+    #[error("Page processing failed for '{path}'")]
+    ProcessPagesError { path: String },
     #[error("I/O error")]
     IoError,
     #[error("Mutex error")]
@@ -40,6 +58,54 @@ pub enum TesseractError {
     InvalidImageData,
     #[error("Uninitialized error")]
     UninitializedError,
+    #[error("Failed to decode image: {0}")]
+    ImageDecodeError(String),
+    #[error("Failed to add page to renderer")]
+    RenderError,
+    #[error("Recognition was cancelled")]
+    Cancelled,
+    #[error("Tesseract worker thread has stopped")]
+    WorkerStopped,
+    #[error("Tesseract (legacy) engine requested, but components are not present in {language}.traineddata")]
+    LegacyEngineUnavailable { language: String },
+    #[error("Failed to access file '{path}'{}", output_suffix(.output))]
+    FileError { path: String, output: String },
+    #[error("Failed to parse {format}: {message}")]
+    ParseError {
+        format: &'static str,
+        message: String,
+    },
+    #[error("String contains an interior NUL byte: {value:?}")]
+    InvalidString { value: String },
+    #[error("Iterator used after the results it reads were cleared or replaced")]
+    IteratorInvalidated,
+    #[error("Failed to write output: {0}")]
+    WriteError(#[source] std::io::Error),
+}
+
+/// Formats an optional datapath for error messages.
+fn datapath_display(datapath: &Option<String>) -> String {
+    match datapath {
+        Some(path) => format!("'{}'", path),
+        None => "<default>".to_owned(),
+    }
+}
+
+/// Formats captured Tesseract output as a suffix for error messages.
+fn output_suffix(output: &str) -> String {
+    let output = output.trim();
+    if output.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", output)
+    }
+}
+
+/// Converts a string for the C API, rejecting interior NUL bytes.
+pub(crate) fn to_cstring(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|_| TesseractError::InvalidString {
+        value: value.to_owned(),
+    })
 }
 
 /// Result type for Tesseract operations.
@@ -51,8 +117,16 @@ mod tests {
 
     #[test]
     fn test_error_display() {
-        let error = TesseractError::InitError;
-        assert_eq!(error.to_string(), "Failed to initialize Tesseract");
+        let error = TesseractError::InitError {
+            datapath: Some("/tessdata".to_owned()),
+            language: "eng".to_owned(),
+            oem: TessOcrEngineMode::OEM_DEFAULT,
+            output: String::new(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to initialize Tesseract with language 'eng' (OEM_DEFAULT) from datapath '/tessdata'"
+        );
 
         let error = TesseractError::SetImageError;
         assert_eq!(error.to_string(), "Failed to set image");
@@ -61,6 +135,57 @@ mod tests {
         assert_eq!(error.to_string(), "OCR operation failed");
     }
 
+    #[test]
+    fn test_error_display_with_context() {
+        let error = TesseractError::InitError {
+            datapath: None,
+            language: "deu".to_owned(),
+            oem: TessOcrEngineMode::OEM_LSTM_ONLY,
+            output: "Failed loading language 'deu'\n".to_owned(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to initialize Tesseract with language 'deu' (OEM_LSTM_ONLY) from datapath <default>: Failed loading language 'deu'"
+        );
+
+        let error = TesseractError::SetVariableError {
+            name: "no_such_variable".to_owned(),
+            value: "1".to_owned(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to set variable 'no_such_variable' to '1'"
+        );
+
+        let error = TesseractError::FileError {
+            path: "missing.cfg".to_owned(),
+            output: "read_params_file: Can't open missing.cfg".to_owned(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to access file 'missing.cfg': read_params_file: Can't open missing.cfg"
+        );
+
+        let error = TesseractError::ParameterTypeMismatch {
+            name: "user_defined_dpi".to_owned(),
+            expected: ParameterType::String,
+            actual: ParameterType::Int,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Tesseract parameter 'user_defined_dpi' is of type int, not string"
+        );
+    }
+
+    #[test]
+    fn test_to_cstring_rejects_interior_nul() {
+        assert_eq!(to_cstring("eng").unwrap().as_bytes(), b"eng");
+        match to_cstring("e\0ng") {
+            Err(TesseractError::InvalidString { value }) => assert_eq!(value, "e\0ng"),
+            other => panic!("Expected InvalidString, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_utf8_error_conversion() {
         let invalid_utf8 = vec![0xFF, 0xFE];
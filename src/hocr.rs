@@ -0,0 +1,679 @@
+use crate::enums::TessPolyBlockType;
+use crate::error::{Result, TesseractError};
+use crate::page::{
+    Baseline, Block, BoundingBox, FontAttributes, Line, Page, Paragraph, Symbol, Word,
+};
+use roxmltree::Node;
+use std::path::Path;
+
+/// An hOCR document holding one or more pages.
+///
+/// Reads both the fragment returned by `TesseractAPI::get_hocr_text` and complete files
+/// written by `TessResultRenderer::new_hocr_renderer`, so stored results can be processed
+/// again without running OCR.
+///
+/// ```no_run
+/// use tesseract_rs::HocrDocument;
+///
+/// let document = HocrDocument::read("scan.hocr")?;
+/// for page in &document.pages {
+///     for word in page.to_page().words() {
+///         println!("{} {:?} {}", word.text, word.bbox, word.confidence);
+///     }
+/// }
+/// # Ok::<(), tesseract_rs::TesseractError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrDocument {
+    pub pages: Vec<HocrPage>,
+}
+
+/// A page (`ocr_page`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrPage {
+    pub id: Option<String>,
+    /// Image file name from the `image` property.
+    pub image: Option<String>,
+    pub bbox: BoundingBox,
+    /// Zero-based page number from the `ppageno` property.
+    pub page_number: Option<u32>,
+    /// Horizontal and vertical resolution from the `scan_res` property.
+    pub scan_res: Option<(u32, u32)>,
+    pub areas: Vec<HocrArea>,
+}
+
+/// A content area (`ocr_carea`, or `ocr_photo` and `ocr_separator` for non-text areas).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrArea {
+    pub id: Option<String>,
+    pub class: String,
+    pub bbox: BoundingBox,
+    pub paragraphs: Vec<HocrParagraph>,
+}
+
+/// A paragraph (`ocr_par`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrParagraph {
+    pub id: Option<String>,
+    pub bbox: BoundingBox,
+    pub language: Option<String>,
+    /// Text direction from the `dir` attribute, `ltr` or `rtl`.
+    pub direction: Option<String>,
+    pub lines: Vec<HocrLine>,
+}
+
+/// A text line (`ocr_line`, `ocr_header`, `ocr_textfloat` or `ocr_caption`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrLine {
+    pub id: Option<String>,
+    pub class: String,
+    pub bbox: BoundingBox,
+    pub baseline: Option<HocrBaseline>,
+    /// Height of lowercase letters from the `x_size` property.
+    pub x_size: Option<f32>,
+    pub x_descenders: Option<f32>,
+    pub x_ascenders: Option<f32>,
+    pub words: Vec<HocrWord>,
+}
+
+/// Baseline of a line, relative to the bottom-left corner of its bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HocrBaseline {
+    pub slope: f32,
+    pub offset: f32,
+}
+
+/// A word (`ocrx_word`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrWord {
+    pub id: Option<String>,
+    pub text: String,
+    pub bbox: BoundingBox,
+    /// Word confidence (0-100) from the `x_wconf` property.
+    pub confidence: Option<f32>,
+    /// Language of the word, inherited from the paragraph when not set on the word.
+    pub language: Option<String>,
+    pub direction: Option<String>,
+    pub is_bold: bool,
+    pub is_italic: bool,
+    pub font: Option<String>,
+    pub font_size: Option<f32>,
+    /// Character boxes, present when `hocr_char_boxes` was enabled.
+    pub symbols: Vec<HocrSymbol>,
+}
+
+/// A character (`ocrx_cinfo`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HocrSymbol {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: Option<f32>,
+}
+
+impl HocrDocument {
+    /// Parses hOCR markup.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A complete hOCR document or one or more `ocr_page` elements.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed document if successful, otherwise returns a `ParseError`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let trimmed = text.trim_start();
+        let options = roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        };
+        let wrapped;
+        // `get_hocr_text` returns bare page elements, which need a common root.
+        let source = if trimmed.starts_with("<?xml") || trimmed.starts_with("<!DOCTYPE") {
+            text
+        } else {
+            wrapped = format!("<hocr>{}</hocr>", text);
+            &wrapped
+        };
+        let document = roxmltree::Document::parse_with_options(source, options)
+            .map_err(|e| parse_error(e.to_string()))?;
+        let pages = document
+            .descendants()
+            .filter(|node| has_class(node, "ocr_page"))
+            .map(|node| parse_page(&node))
+            .collect::<Result<_>>()?;
+        Ok(HocrDocument { pages })
+    }
+
+    /// Reads an hOCR file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the hOCR file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed document if successful, otherwise returns an error.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| TesseractError::FileError {
+            path: path.display().to_string(),
+            output: e.to_string(),
+        })?;
+        Self::parse(&text)
+    }
+}
+
+impl HocrPage {
+    /// Iterates over every word on the page in reading order.
+    pub fn words(&self) -> impl Iterator<Item = &HocrWord> {
+        self.areas
+            .iter()
+            .flat_map(|area| &area.paragraphs)
+            .flat_map(|paragraph| &paragraph.lines)
+            .flat_map(|line| &line.words)
+    }
+
+    /// Converts the page into the result model produced by `TesseractAPI::recognize_page`.
+    ///
+    /// hOCR has no confidence above the word level, so lines, paragraphs and blocks get the
+    /// mean confidence of their words. The block type is derived from the area and line
+    /// classes, and dictionary and numeric flags are not recorded and are left `false`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted `Page`.
+    pub fn to_page(&self) -> Page {
+        Page {
+            blocks: self.areas.iter().map(HocrArea::to_block).collect(),
+        }
+    }
+}
+
+impl HocrArea {
+    fn to_block(&self) -> Block {
+        let paragraphs: Vec<Paragraph> = self
+            .paragraphs
+            .iter()
+            .map(HocrParagraph::to_paragraph)
+            .collect();
+        let mut text: String = paragraphs.iter().map(|p| p.text.as_str()).collect();
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        let words = self
+            .paragraphs
+            .iter()
+            .flat_map(|paragraph| &paragraph.lines)
+            .flat_map(|line| &line.words);
+        Block {
+            text,
+            bbox: self.bbox,
+            confidence: mean_confidence(words),
+            block_type: self.block_type(),
+            paragraphs,
+        }
+    }
+
+    fn block_type(&self) -> TessPolyBlockType {
+        match self.class.as_str() {
+            "ocr_photo" => TessPolyBlockType::PT_FLOWING_IMAGE,
+            "ocr_separator" => TessPolyBlockType::PT_HORZ_LINE,
+            _ => match self
+                .paragraphs
+                .iter()
+                .flat_map(|paragraph| &paragraph.lines)
+                .map(|line| line.class.as_str())
+                .next()
+            {
+                Some("ocr_header") => TessPolyBlockType::PT_HEADING_TEXT,
+                Some("ocr_textfloat") => TessPolyBlockType::PT_PULLOUT_TEXT,
+                Some("ocr_caption") => TessPolyBlockType::PT_CAPTION_TEXT,
+                _ => TessPolyBlockType::PT_FLOWING_TEXT,
+            },
+        }
+    }
+}
+
+impl HocrParagraph {
+    fn to_paragraph(&self) -> Paragraph {
+        let lines: Vec<Line> = self.lines.iter().map(HocrLine::to_line).collect();
+        Paragraph {
+            text: lines.iter().map(|line| line.text.as_str()).collect(),
+            bbox: self.bbox,
+            confidence: mean_confidence(self.lines.iter().flat_map(|line| &line.words)),
+            info: None,
+            lines,
+        }
+    }
+}
+
+impl HocrLine {
+    /// Gets the baseline as two points in image coordinates.
+    ///
+    /// # Returns
+    ///
+    /// Returns the baseline from the left to the right edge of the line, or `None` if the
+    /// line has no `baseline` property.
+    pub fn baseline_points(&self) -> Option<Baseline> {
+        let baseline = self.baseline?;
+        let y1 = self.bbox.bottom as f32 + baseline.offset;
+        let y2 = y1 + baseline.slope * self.bbox.width() as f32;
+        Some(Baseline {
+            x1: self.bbox.left,
+            y1: y1.round() as i32,
+            x2: self.bbox.right,
+            y2: y2.round() as i32,
+        })
+    }
+
+    fn to_line(&self) -> Line {
+        let mut text = self
+            .words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        text.push('\n');
+        Line {
+            text,
+            bbox: self.bbox,
+            baseline: self.baseline_points(),
+            confidence: mean_confidence(&self.words),
+            words: self.words.iter().map(HocrWord::to_word).collect(),
+        }
+    }
+}
+
+impl HocrWord {
+    fn to_word(&self) -> Word {
+        let has_font = self.is_bold || self.is_italic || self.font_size.is_some();
+        Word {
+            text: self.text.clone(),
+            bbox: self.bbox,
+            baseline: None,
+            confidence: self.confidence.unwrap_or(0.0),
+            font: has_font.then(|| FontAttributes {
+                is_bold: self.is_bold,
+                is_italic: self.is_italic,
+                pointsize: self.font_size.map_or(0, |size| size.round() as i32),
+                ..FontAttributes::default()
+            }),
+            language: self.language.clone(),
+            is_from_dictionary: false,
+            is_numeric: false,
+            symbols: self
+                .symbols
+                .iter()
+                .map(|symbol| Symbol {
+                    text: symbol.text.clone(),
+                    bbox: symbol.bbox,
+                    confidence: symbol.confidence.unwrap_or(0.0),
+                    is_superscript: false,
+                    is_subscript: false,
+                    is_dropcap: false,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn mean_confidence<'a>(words: impl IntoIterator<Item = &'a HocrWord>) -> f32 {
+    let (sum, count) = words
+        .into_iter()
+        .filter_map(|word| word.confidence)
+        .fold((0.0, 0), |(sum, count), confidence| {
+            (sum + confidence, count + 1)
+        });
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f32
+    }
+}
+
+fn parse_error(message: String) -> TesseractError {
+    TesseractError::ParseError {
+        format: "hOCR",
+        message,
+    }
+}
+
+fn has_class(node: &Node, class: &str) -> bool {
+    node.attribute("class")
+        .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+}
+
+fn class_of<'a>(node: &Node<'a, '_>, prefix: &str) -> Option<&'a str> {
+    node.attribute("class")?
+        .split_whitespace()
+        .find(|class| class.starts_with(prefix))
+}
+
+/// Finds the nearest descendants with one of the classes, without descending into them.
+fn children_with_class<'a, 'input>(
+    node: &Node<'a, 'input>,
+    classes: &[&str],
+) -> Vec<Node<'a, 'input>> {
+    let mut found = Vec::new();
+    for child in node.children().filter(Node::is_element) {
+        if classes.iter().any(|class| has_class(&child, class)) {
+            found.push(child);
+        } else {
+            found.extend(children_with_class(&child, classes));
+        }
+    }
+    found
+}
+
+/// Properties from an hOCR `title` attribute, e.g. `bbox 0 0 10 10; x_wconf 96`.
+struct Title<'a> {
+    properties: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Title<'a> {
+    fn of(node: &Node<'a, '_>) -> Self {
+        let properties = node
+            .attribute("title")
+            .unwrap_or("")
+            .split(';')
+            .filter_map(|property| {
+                let property = property.trim();
+                let (name, value) = property
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((property, ""));
+                (!name.is_empty()).then(|| (name, value.trim()))
+            })
+            .collect();
+        Title { properties }
+    }
+
+    fn get(&self, name: &str) -> Option<&'a str> {
+        self.properties
+            .iter()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn numbers(&self, name: &str) -> Result<Option<Vec<f32>>> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .split_whitespace()
+                    .map(|number| {
+                        number
+                            .parse::<f32>()
+                            .map_err(|_| parse_error(format!("invalid {} value '{}'", name, value)))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+
+    fn number(&self, name: &str) -> Result<Option<f32>> {
+        Ok(self
+            .numbers(name)?
+            .and_then(|values| values.first().copied()))
+    }
+
+    fn bbox(&self, name: &str) -> Result<BoundingBox> {
+        match self.numbers(name)?.as_deref() {
+            Some([left, top, right, bottom]) => Ok(BoundingBox::new(
+                *left as i32,
+                *top as i32,
+                *right as i32,
+                *bottom as i32,
+            )),
+            Some(_) => Err(parse_error(format!("{} needs four coordinates", name))),
+            None => Ok(BoundingBox::default()),
+        }
+    }
+
+    fn quoted(&self, name: &str) -> Option<String> {
+        self.get(name)
+            .map(|value| value.trim_matches('"').to_owned())
+    }
+}
+
+fn parse_page(node: &Node) -> Result<HocrPage> {
+    let title = Title::of(node);
+    let scan_res = match title.numbers("scan_res")?.as_deref() {
+        Some([x, y]) => Some((*x as u32, *y as u32)),
+        _ => None,
+    };
+    Ok(HocrPage {
+        id: node.attribute("id").map(str::to_owned),
+        image: title.quoted("image"),
+        bbox: title.bbox("bbox")?,
+        page_number: title.number("ppageno")?.map(|n| n as u32),
+        scan_res,
+        areas: children_with_class(node, &["ocr_carea", "ocr_photo", "ocr_separator"])
+            .iter()
+            .map(|area| parse_area(area, node.attribute("lang")))
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn parse_area(node: &Node, language: Option<&str>) -> Result<HocrArea> {
+    let language = node.attribute("lang").or(language);
+    Ok(HocrArea {
+        id: node.attribute("id").map(str::to_owned),
+        class: class_of(node, "ocr").unwrap_or("ocr_carea").to_owned(),
+        bbox: Title::of(node).bbox("bbox")?,
+        paragraphs: children_with_class(node, &["ocr_par"])
+            .iter()
+            .map(|paragraph| parse_paragraph(paragraph, language))
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn parse_paragraph(node: &Node, language: Option<&str>) -> Result<HocrParagraph> {
+    let language = node.attribute("lang").or(language);
+    Ok(HocrParagraph {
+        id: node.attribute("id").map(str::to_owned),
+        bbox: Title::of(node).bbox("bbox")?,
+        language: language.map(str::to_owned),
+        direction: node.attribute("dir").map(str::to_owned),
+        lines: children_with_class(
+            node,
+            &["ocr_line", "ocr_header", "ocr_textfloat", "ocr_caption"],
+        )
+        .iter()
+        .map(|line| parse_line(line, language))
+        .collect::<Result<_>>()?,
+    })
+}
+
+fn parse_line(node: &Node, language: Option<&str>) -> Result<HocrLine> {
+    let title = Title::of(node);
+    let baseline = match title.numbers("baseline")?.as_deref() {
+        Some([slope, offset]) => Some(HocrBaseline {
+            slope: *slope,
+            offset: *offset,
+        }),
+        Some(_) => return Err(parse_error("baseline needs two values".to_owned())),
+        None => None,
+    };
+    Ok(HocrLine {
+        id: node.attribute("id").map(str::to_owned),
+        class: class_of(node, "ocr_").unwrap_or("ocr_line").to_owned(),
+        bbox: title.bbox("bbox")?,
+        baseline,
+        x_size: title.number("x_size")?,
+        x_descenders: title.number("x_descenders")?,
+        x_ascenders: title.number("x_ascenders")?,
+        words: children_with_class(node, &["ocrx_word"])
+            .iter()
+            .map(|word| parse_word(word, language))
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn parse_word(node: &Node, language: Option<&str>) -> Result<HocrWord> {
+    let title = Title::of(node);
+    let has_tag = |tag: &str| {
+        node.descendants()
+            .any(|child| child.is_element() && child.tag_name().name() == tag)
+    };
+    Ok(HocrWord {
+        id: node.attribute("id").map(str::to_owned),
+        text: text_content(node),
+        bbox: title.bbox("bbox")?,
+        confidence: title.number("x_wconf")?,
+        language: node.attribute("lang").or(language).map(str::to_owned),
+        direction: node.attribute("dir").map(str::to_owned),
+        is_bold: has_tag("strong"),
+        is_italic: has_tag("em"),
+        font: title.quoted("x_font"),
+        font_size: title.number("x_fsize")?,
+        symbols: children_with_class(node, &["ocrx_cinfo"])
+            .iter()
+            .map(|symbol| {
+                let title = Title::of(symbol);
+                Ok(HocrSymbol {
+                    text: text_content(symbol),
+                    bbox: title.bbox("x_bboxes")?,
+                    confidence: title.number("x_conf")?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn text_content(node: &Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"  <div class='ocr_page' id='page_1' title='image "sample.png"; bbox 0 0 400 200; ppageno 0; scan_res 300 300'>
+   <div class='ocr_carea' id='block_1_1' title="bbox 10 20 390 80">
+    <p class='ocr_par' id='par_1_1' lang='eng' title="bbox 10 20 390 80">
+     <span class='ocr_line' id='line_1_1' title="bbox 10 20 390 80; baseline 0.01 -5; x_size 30; x_descenders 6; x_ascenders 8">
+      <span class='ocrx_word' id='word_1_1' title='bbox 10 20 120 75; x_wconf 96'><strong>Hello</strong></span>
+      <span class='ocrx_word' id='word_1_2' title='bbox 140 22 390 80; x_wconf 90' lang='deu'>W&amp;lt</span>
+     </span>
+    </p>
+   </div>
+  </div>
+"#;
+
+    #[test]
+    fn test_parse_get_hocr_text_fragment() {
+        let document = HocrDocument::parse(PAGE).unwrap();
+        assert_eq!(document.pages.len(), 1);
+        let page = &document.pages[0];
+        assert_eq!(page.id.as_deref(), Some("page_1"));
+        assert_eq!(page.image.as_deref(), Some("sample.png"));
+        assert_eq!(page.bbox, BoundingBox::new(0, 0, 400, 200));
+        assert_eq!(page.page_number, Some(0));
+        assert_eq!(page.scan_res, Some((300, 300)));
+
+        let line = &page.areas[0].paragraphs[0].lines[0];
+        assert_eq!(line.class, "ocr_line");
+        assert_eq!(
+            line.baseline,
+            Some(HocrBaseline {
+                slope: 0.01,
+                offset: -5.0
+            })
+        );
+        assert_eq!(line.x_size, Some(30.0));
+        assert_eq!(line.x_descenders, Some(6.0));
+        assert_eq!(line.x_ascenders, Some(8.0));
+        assert_eq!(
+            line.baseline_points(),
+            Some(Baseline {
+                x1: 10,
+                y1: 75,
+                x2: 390,
+                y2: 79
+            })
+        );
+
+        let words: Vec<&HocrWord> = page.words().collect();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hello");
+        assert!(words[0].is_bold);
+        assert_eq!(words[0].confidence, Some(96.0));
+        assert_eq!(words[0].language.as_deref(), Some("eng"));
+        assert_eq!(words[1].text, "W&lt");
+        assert_eq!(words[1].language.as_deref(), Some("deu"));
+        assert_eq!(words[1].bbox, BoundingBox::new(140, 22, 390, 80));
+    }
+
+    #[test]
+    fn test_parse_renderer_document() {
+        let document = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\"\n    \
+             \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">\n\
+             <html xmlns=\"http://www.w3.org/1999/xhtml\" xml:lang=\"en\" lang=\"en\">\n\
+             <head><title>sample</title>\n\
+             <meta name='ocr-system' content='tesseract 5.5.0' />\n\
+             </head>\n<body>\n{}{}</body>\n</html>\n",
+            PAGE,
+            PAGE.replace("page_1", "page_2")
+                .replace("ppageno 0", "ppageno 1")
+        );
+        let document = HocrDocument::parse(&document).unwrap();
+        assert_eq!(document.pages.len(), 2);
+        assert_eq!(document.pages[1].id.as_deref(), Some("page_2"));
+        assert_eq!(document.pages[1].page_number, Some(1));
+    }
+
+    #[test]
+    fn test_parse_character_boxes() {
+        let hocr = "<div class='ocr_page' title='bbox 0 0 50 20'><div class='ocr_carea'>\
+                    <p class='ocr_par'><span class='ocr_caption' title='bbox 0 0 50 20'>\
+                    <span class='ocrx_word' title='bbox 0 0 20 20; x_wconf 88'>\
+                    <span class='ocrx_cinfo' title='x_bboxes 0 0 9 20; x_conf 91.5'>o</span>\
+                    <span class='ocrx_cinfo' title='x_bboxes 10 0 20 20; x_conf 84.25'>k</span>\
+                    </span></span></p></div></div>";
+        let page = &HocrDocument::parse(hocr).unwrap().pages[0];
+        let word = page.words().next().unwrap();
+        assert_eq!(word.text, "ok");
+        assert_eq!(word.symbols.len(), 2);
+        assert_eq!(word.symbols[1].bbox, BoundingBox::new(10, 0, 20, 20));
+        assert_eq!(word.symbols[1].confidence, Some(84.25));
+
+        let converted = page.to_page();
+        assert_eq!(
+            converted.blocks[0].block_type,
+            TessPolyBlockType::PT_CAPTION_TEXT
+        );
+        assert_eq!(converted.words().next().unwrap().symbols.len(), 2);
+    }
+
+    #[test]
+    fn test_to_page() {
+        let page = HocrDocument::parse(PAGE).unwrap().pages.remove(0).to_page();
+        assert_eq!(page.text(), "Hello W&lt\n\n");
+        let line = page.lines().next().unwrap();
+        assert_eq!(line.confidence, 93.0);
+        assert_eq!(line.words[0].font.map(|font| font.is_bold), Some(true));
+        assert_eq!(line.words[1].font, None);
+        assert_eq!(
+            page.blocks[0].block_type,
+            TessPolyBlockType::PT_FLOWING_TEXT
+        );
+    }
+
+    #[test]
+    fn test_invalid_hocr() {
+        assert!(matches!(
+            HocrDocument::parse("<div class='ocr_page'"),
+            Err(TesseractError::ParseError { .. })
+        ));
+        assert!(matches!(
+            HocrDocument::parse("<div class='ocr_page' title='bbox 1 2'></div>"),
+            Err(TesseractError::ParseError { .. })
+        ));
+    }
+}
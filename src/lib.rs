@@ -129,15 +129,66 @@ mod page_iterator;
 pub use page_iterator::PageIterator;
 mod result_iterator;
 pub use result_iterator::ResultIterator;
+mod views;
+pub use views::{BlockView, Elements, LineView, SymbolView, WordView};
 mod choice_iterator;
-pub use choice_iterator::ChoiceIterator;
+pub use choice_iterator::{ChoiceIterator, Choices};
 mod monitor;
-pub use monitor::TessMonitor;
+pub use monitor::{MonitorProgress, TessMonitor};
 mod result_renderer;
-pub use result_renderer::TessResultRenderer;
+pub use result_renderer::{OutputBuffer, TessResultRenderer};
+mod document;
+pub use document::Document;
 mod mutable_iterator;
 pub use mutable_iterator::MutableIterator;
 mod enums;
-pub use enums::{TessPageIteratorLevel, TessPageSegMode, TessPolyBlockType};
+pub use enums::{
+    TessLstmChoiceMode, TessOcrEngineMode, TessOrientation, TessPageIteratorLevel, TessPageSegMode,
+    TessParagraphJustification, TessPolyBlockType, TessTextlineOrder, TessWritingDirection,
+};
+mod page;
+pub use page::{
+    Baseline, Block, BoundingBox, FontAttributes, Line, Page, Paragraph, ParagraphInfo, Symbol,
+    Word,
+};
+mod pix;
+pub use pix::Pix;
+#[cfg(feature = "pdf")]
+mod pdf;
+#[cfg(feature = "pdf")]
+pub use pdf::{PdfImage, PdfImageCompression, PdfMetadata, PdfOptions, PdfWriter};
+mod page_source;
+mod preprocess;
+pub use preprocess::{detect_skew, GrayImage, Pipeline};
+#[cfg(feature = "pdf")]
+mod pdf_font;
+#[cfg(feature = "pdf")]
+mod pdf_object;
+#[cfg(feature = "image")]
+pub use page_source::TiffPages;
+pub use page_source::{DocumentPages, RecognizedPage};
+mod hocr;
+pub use hocr::{
+    HocrArea, HocrBaseline, HocrDocument, HocrLine, HocrPage, HocrParagraph, HocrSymbol, HocrWord,
+};
+mod alto;
+pub use alto::{AltoLayout, AltoPage, AltoString, AltoTextBlock, AltoTextLine};
+mod boxfile;
+pub use boxfile::{BoxEntry, BoxFile, BoxFormat};
+mod tsv;
+pub use tsv::{TsvLevel, TsvRow};
+mod parameters;
+pub use parameters::{Parameter, ParameterSnapshot, ParameterType, ParameterValue};
+mod builder;
+mod stderr;
+mod traineddata;
+pub use builder::TesseractBuilder;
 mod api;
+mod results;
 pub use api::TesseractAPI;
+mod pool;
+pub use pool::{PooledEngine, TesseractPool};
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::{AsyncTesseract, TesseractFuture};
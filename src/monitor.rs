@@ -1,8 +1,70 @@
+use crate::error::{Result, TesseractError};
+use crate::page::BoundingBox;
+use std::any::Any;
 use std::os::raw::{c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// Progress report passed to a monitor callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorProgress {
+    /// Recognition progress in percent (0-100).
+    pub percent: i32,
+    /// Bounding box of the word that was just processed, with a top-left origin.
+    pub word_bbox: BoundingBox,
+}
+
+type ProgressCallback = Box<dyn FnMut(&MonitorProgress) -> bool + Send>;
+
+/// State shared with the Tesseract callbacks through the monitor's `cancel_this` pointer.
+struct MonitorState {
+    callback: Mutex<Option<ProgressCallback>>,
+    cancelled: AtomicBool,
+    image_height: AtomicI32,
+    /// Payload of a panic in the callback, re-raised once recognition has returned.
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+impl MonitorState {
+    /// Forwards a word box reported by Tesseract to the callback.
+    ///
+    /// Tesseract reports boxes with a bottom-left origin; they are flipped using the height of
+    /// the image being recognized when it is known.
+    fn report(&self, percent: i32, left: i32, right: i32, top: i32, bottom: i32) {
+        let height = self.image_height.load(Ordering::Relaxed);
+        let word_bbox = if height > 0 {
+            BoundingBox::new(left, height - top, right, height - bottom)
+        } else {
+            BoundingBox::new(left, top, right, bottom)
+        };
+        let progress = MonitorProgress { percent, word_bbox };
+        if let Ok(mut callback) = self.callback.lock() {
+            if let Some(callback) = callback.as_mut() {
+                // Unwinding into C++ is undefined behavior, so a panic cancels the recognition
+                // and is kept to be resumed after it.
+                match catch_unwind(AssertUnwindSafe(|| callback(&progress))) {
+                    Ok(true) => {}
+                    Ok(false) => self.cancelled.store(true, Ordering::Relaxed),
+                    Err(payload) => {
+                        if let Ok(mut panic) = self.panic.lock() {
+                            panic.get_or_insert(payload);
+                        }
+                        self.cancelled.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Progress reporting and cancellation for `TesseractAPI::recognize_with_monitor`.
+///
+/// The progress callback runs while the `TesseractAPI` is locked for recognition, so calling
+/// any method of that `TesseractAPI` from inside the callback deadlocks.
 pub struct TessMonitor {
     handle: Arc<Mutex<*mut c_void>>,
+    state: Box<MonitorState>,
 }
 
 unsafe impl Send for TessMonitor {}
@@ -16,8 +78,20 @@ impl TessMonitor {
     /// Returns the new instance of the TessMonitor.
     pub fn new() -> Self {
         let handle = unsafe { TessMonitorCreate() };
+        let state = Box::new(MonitorState {
+            callback: Mutex::new(None),
+            cancelled: AtomicBool::new(false),
+            image_height: AtomicI32::new(0),
+            panic: Mutex::new(None),
+        });
+        unsafe {
+            TessMonitorSetCancelThis(handle, &*state as *const MonitorState as *mut c_void);
+            TessMonitorSetCancelFunc(handle, Some(cancel_trampoline));
+            TessMonitorSetProgressFunc(handle, Some(progress_trampoline));
+        }
         TessMonitor {
             handle: Arc::new(Mutex::new(handle)),
+            state,
         }
     }
 
@@ -26,9 +100,17 @@ impl TessMonitor {
     /// # Arguments
     ///
     /// * `deadline` - Deadline in milliseconds.
-    pub fn set_deadline(&self, deadline: i32) {
-        let handle = self.handle.lock().unwrap();
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the deadline was set, otherwise returns an error.
+    pub fn set_deadline(&self, deadline: i32) -> Result<()> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         unsafe { TessMonitorSetDeadlineMSecs(*handle, deadline) };
+        Ok(())
     }
 
     /// Gets the progress of the monitor.
@@ -36,22 +118,185 @@ impl TessMonitor {
     /// # Returns
     ///
     /// Returns the progress as an `i32`.
-    pub fn get_progress(&self) -> i32 {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessMonitorGetProgress(*handle) }
+    pub fn get_progress(&self) -> Result<i32> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { TessMonitorGetProgress(*handle) })
+    }
+
+    /// Registers a callback invoked after each word is recognized.
+    ///
+    /// The callback runs on the thread performing the recognition. Returning `false` cancels
+    /// the recognition, which then fails with `TesseractError::Cancelled`. A panic in the
+    /// callback cancels the recognition too and is resumed once it has stopped.
+    ///
+    /// The `TesseractAPI` stays locked while the callback runs, so the callback must not call
+    /// methods of that `TesseractAPI`; doing so deadlocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Closure receiving the current progress and the processed word's box.
+    pub fn set_progress_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&MonitorProgress) -> bool + Send + 'static,
+    {
+        if let Ok(mut slot) = self.state.callback.lock() {
+            *slot = Some(Box::new(callback));
+        }
+    }
+
+    /// Requests cancellation of the recognition using this monitor.
+    ///
+    /// This may be called from any thread. Tesseract checks for cancellation between words.
+    /// A cancelled monitor stays cancelled, so use a new monitor for the next job.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether cancellation was requested.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if `cancel` was called or the progress callback returned `false`.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Gets the raw monitor handle and records the height of the image being recognized.
+    pub(crate) fn prepare(&self, image_height: i32) -> Result<*mut c_void> {
+        self.state
+            .image_height
+            .store(image_height, Ordering::Relaxed);
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(*handle)
+    }
+
+    /// Takes the payload of a panic raised by the progress callback.
+    pub(crate) fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.state.panic.lock().ok()?.take()
     }
 }
 
 impl Drop for TessMonitor {
     fn drop(&mut self) {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessMonitorDelete(*handle) };
+        if let Ok(handle) = self.handle.lock() {
+            unsafe { TessMonitorDelete(*handle) };
+        }
+    }
+}
+
+extern "C" fn cancel_trampoline(cancel_this: *mut c_void, _words: c_int) -> bool {
+    if cancel_this.is_null() {
+        return false;
     }
+    let state = unsafe { &*(cancel_this as *const MonitorState) };
+    state.cancelled.load(Ordering::Relaxed)
 }
 
+extern "C" fn progress_trampoline(
+    monitor: *mut c_void,
+    left: c_int,
+    right: c_int,
+    top: c_int,
+    bottom: c_int,
+) -> bool {
+    let cancel_this = unsafe { TessMonitorGetCancelThis(monitor) };
+    if cancel_this.is_null() {
+        return true;
+    }
+    let state = unsafe { &*(cancel_this as *const MonitorState) };
+    let percent = unsafe { TessMonitorGetProgress(monitor) };
+    state.report(percent, left, right, top, bottom);
+    true
+}
+
+pub type TessCancelFunc = Option<extern "C" fn(cancel_this: *mut c_void, words: c_int) -> bool>;
+pub type TessProgressFunc = Option<
+    extern "C" fn(
+        monitor: *mut c_void,
+        left: c_int,
+        right: c_int,
+        top: c_int,
+        bottom: c_int,
+    ) -> bool,
+>;
+
 extern "C" {
     pub fn TessMonitorCreate() -> *mut c_void;
     pub fn TessMonitorDelete(monitor: *mut c_void);
     pub fn TessMonitorSetDeadlineMSecs(monitor: *mut c_void, deadline: c_int);
     pub fn TessMonitorGetProgress(monitor: *mut c_void) -> c_int;
+    pub fn TessMonitorSetCancelFunc(monitor: *mut c_void, cancel_func: TessCancelFunc);
+    pub fn TessMonitorSetCancelThis(monitor: *mut c_void, cancel_this: *mut c_void);
+    pub fn TessMonitorGetCancelThis(monitor: *mut c_void) -> *mut c_void;
+    pub fn TessMonitorSetProgressFunc(monitor: *mut c_void, progress_func: TessProgressFunc);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> MonitorState {
+        MonitorState {
+            callback: Mutex::new(None),
+            cancelled: AtomicBool::new(false),
+            image_height: AtomicI32::new(0),
+            panic: Mutex::new(None),
+        }
+    }
+
+    #[test]
+    fn test_report_flips_word_box() {
+        let state = state();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        *state.callback.lock().unwrap() = Some(Box::new(move |progress: &MonitorProgress| {
+            sink.lock().unwrap().push(*progress);
+            true
+        }));
+
+        state.report(10, 5, 25, 90, 80);
+        state.image_height.store(100, Ordering::Relaxed);
+        state.report(20, 5, 25, 90, 80);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].percent, 10);
+        assert_eq!(seen[0].word_bbox, BoundingBox::new(5, 90, 25, 80));
+        assert_eq!(seen[1].word_bbox, BoundingBox::new(5, 10, 25, 20));
+        assert!(!state.cancelled.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_callback_can_cancel() {
+        let state = state();
+        *state.callback.lock().unwrap() =
+            Some(Box::new(|progress: &MonitorProgress| progress.percent < 50));
+
+        state.report(30, 0, 1, 1, 0);
+        assert!(!cancel_trampoline(&state as *const _ as *mut c_void, 0));
+        state.report(60, 0, 1, 1, 0);
+        assert!(cancel_trampoline(&state as *const _ as *mut c_void, 0));
+        assert!(!cancel_trampoline(std::ptr::null_mut(), 0));
+    }
+
+    #[test]
+    fn test_callback_panic_cancels() {
+        let state = state();
+        *state.callback.lock().unwrap() = Some(Box::new(|_: &MonitorProgress| panic!("boom")));
+
+        state.report(10, 0, 1, 1, 0);
+        assert!(cancel_trampoline(&state as *const _ as *mut c_void, 0));
+        let payload = state
+            .panic
+            .lock()
+            .unwrap()
+            .take()
+            .expect("panic was not kept");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+    }
 }
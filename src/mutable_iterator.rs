@@ -1,233 +1,135 @@
-use crate::error::{Result, TesseractError};
-use std::ffi::CStr;
-use std::os::raw::{c_char, c_void};
-use std::sync::Arc;
-use std::sync::Mutex;
-
-use crate::result_iterator::{
-    TessResultIteratorConfidence, TessResultIteratorGetUTF8Text, TessResultIteratorNext,
-    TessResultIteratorSymbolIsDropcap, TessResultIteratorSymbolIsSubscript,
-    TessResultIteratorSymbolIsSuperscript, TessResultIteratorWordFontAttributes,
-    TessResultIteratorWordIsFromDictionary, TessResultIteratorWordIsNumeric,
-    TessResultIteratorWordRecognitionLanguage,
-};
-
-pub struct MutableIterator {
-    handle: Arc<Mutex<*mut c_void>>,
+use crate::error::{to_cstring, Result, TesseractError};
+use crate::result_iterator::ResultIterator;
+use crate::results::ResultsGuard;
+use std::ops::Deref;
+use std::os::raw::{c_char, c_int, c_void};
+
+/// Iterator over the recognition results of a `TesseractAPI` that can edit them.
+///
+/// The reading methods are those of `ResultIterator`, which the iterator dereferences to.
+/// Edits change the results in place, so `get_utf8_text`, `get_hocr_text`, the renderers and
+/// every other output produced afterwards include them. An edit invalidates every other
+/// iterator over the same results.
+///
+/// ```no_run
+/// # fn example(api: &tesseract_rs::TesseractAPI) -> tesseract_rs::Result<()> {
+/// # fn correct(word: &str) -> Option<String> { None }
+/// use tesseract_rs::TessPageIteratorLevel;
+///
+/// api.recognize()?;
+/// {
+///     let iterator = api.get_mutable_iterator()?;
+///     loop {
+///         let text = iterator.get_utf8_text(TessPageIteratorLevel::RIL_WORD)?;
+///         if let Some(corrected) = correct(&text) {
+///             iterator.replace_word(&corrected)?;
+///         }
+///         if !iterator.next(TessPageIteratorLevel::RIL_WORD)? {
+///             break;
+///         }
+///     }
+/// }
+/// println!("{}", api.get_hocr_text(0)?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct MutableIterator<'api> {
+    iterator: ResultIterator<'api>,
 }
 
-unsafe impl Send for MutableIterator {}
-unsafe impl Sync for MutableIterator {}
+unsafe impl Send for MutableIterator<'_> {}
+unsafe impl Sync for MutableIterator<'_> {}
 
-impl MutableIterator {
+impl<'api> MutableIterator<'api> {
     /// Creates a new instance of the MutableIterator.
     ///
-    /// # Arguments
-    ///
-    /// * `handle` - Pointer to the MutableIterator.
-    pub fn new(handle: *mut c_void) -> Self {
-        MutableIterator {
-            handle: Arc::new(Mutex::new(handle)),
-        }
-    }
-
-    /// Gets the UTF-8 text for the current iterator.
+    /// The iterator is not tied to a `TesseractAPI`, so it cannot detect that the results it
+    /// edits were freed. Use `TesseractAPI::get_mutable_iterator` instead where possible.
     ///
-    /// # Arguments
+    /// # Safety
     ///
-    /// * `level` - Level of the text.
-    pub fn get_utf8_text(&self, level: i32) -> Result<String> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        let text_ptr = unsafe { TessResultIteratorGetUTF8Text(*handle, level) };
-        if text_ptr.is_null() {
-            return Err(TesseractError::NullPointerError);
-        }
-        let c_str = unsafe { CStr::from_ptr(text_ptr) };
-        let result = c_str.to_str()?.to_owned();
-        unsafe { TessDeleteText(text_ptr as *mut c_char) };
-        Ok(result)
-    }
-
-    /// Gets the confidence of the current iterator.
+    /// `handle` must be a valid ResultIterator handle that nothing else deletes, as the returned
+    /// iterator deletes it when dropped. The results it reads must stay alive and unchanged
+    /// while the iterator is in use: the engine must not be dropped, and `set_image`,
+    /// `recognize`, `clear`, `end` and the other calls replacing the results must not be made.
     ///
     /// # Arguments
     ///
-    /// * `level` - Level of the confidence.
-    pub fn confidence(&self, level: i32) -> Result<f32> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorConfidence(*handle, level) })
-    }
-
-    /// Gets the recognition language of the current iterator.
+    /// * `handle` - Pointer to the MutableIterator.
     ///
     /// # Returns
     ///
-    /// Returns the recognition language as a `String` if successful, otherwise returns an error.
-    pub fn word_recognition_language(&self) -> Result<String> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        let lang_ptr = unsafe { TessResultIteratorWordRecognitionLanguage(*handle) };
-        if lang_ptr.is_null() {
-            return Err(TesseractError::NullPointerError);
-        }
-        let c_str = unsafe { CStr::from_ptr(lang_ptr) };
-        Ok(c_str.to_str()?.to_owned())
+    /// Returns the new instance of the MutableIterator.
+    pub unsafe fn new(handle: *mut c_void) -> Self {
+        Self::with_results(handle, ResultsGuard::unchecked())
     }
 
-    /// Gets the font attributes of the current iterator.
-    ///
-    /// # Returns
-    ///
-    /// Returns the font attributes as a tuple if successful, otherwise returns an error.
-    pub fn word_font_attributes(&self) -> Result<(bool, bool, bool, bool, bool, bool, i32, i32)> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        let mut is_bold = 0;
-        let mut is_italic = 0;
-        let mut is_underlined = 0;
-        let mut is_monospace = 0;
-        let mut is_serif = 0;
-        let mut is_smallcaps = 0;
-        let mut pointsize = 0;
-        let mut font_id = 0;
-
-        let result = unsafe {
-            TessResultIteratorWordFontAttributes(
-                *handle,
-                &mut is_bold,
-                &mut is_italic,
-                &mut is_underlined,
-                &mut is_monospace,
-                &mut is_serif,
-                &mut is_smallcaps,
-                &mut pointsize,
-                &mut font_id,
-            )
-        };
-
-        if result == 0 {
-            Err(TesseractError::InvalidParameterError)
-        } else {
-            Ok((
-                is_bold != 0,
-                is_italic != 0,
-                is_underlined != 0,
-                is_monospace != 0,
-                is_serif != 0,
-                is_smallcaps != 0,
-                pointsize,
-                font_id,
-            ))
+    pub(crate) fn with_results(handle: *mut c_void, results: ResultsGuard<'api>) -> Self {
+        MutableIterator {
+            iterator: ResultIterator::with_results(handle, results),
         }
     }
 
-    /// Checks if the current word is from the dictionary.
+    /// Replaces the text of the current word.
     ///
-    /// # Returns
+    /// The word keeps its bounding box and confidence. Its symbols keep their boxes when the
+    /// number of characters is unchanged; otherwise the word box is split evenly between
+    /// the new characters.
     ///
-    /// Returns `Ok(true)` if the current word is from the dictionary, otherwise returns `Ok(false)`.
-    pub fn word_is_from_dictionary(&self) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorWordIsFromDictionary(*handle) != 0 })
-    }
-
-    /// Checks if the current word is numeric.
-    ///
-    /// # Returns
-    ///
-    /// Returns `Ok(true)` if the current word is numeric, otherwise returns `Ok(false)`.
-    pub fn word_is_numeric(&self) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorWordIsNumeric(*handle) != 0 })
-    }
-
-    /// Checks if the current symbol is superscript.
-    ///
-    /// # Returns
-    ///
-    /// Returns `Ok(true)` if the current symbol is superscript, otherwise returns `Ok(false)`.
-    pub fn symbol_is_superscript(&self) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorSymbolIsSuperscript(*handle) != 0 })
-    }
-
-    /// Checks if the current symbol is subscript.
-    ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// Returns `Ok(true)` if the current symbol is subscript, otherwise returns `Ok(false)`.
-    pub fn symbol_is_subscript(&self) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorSymbolIsSubscript(*handle) != 0 })
-    }
-
-    /// Checks if the current symbol is dropcap.
+    /// * `text` - New text of the word, without whitespace.
     ///
     /// # Returns
     ///
-    /// Returns `Ok(true)` if the current symbol is dropcap, otherwise returns `Ok(false)`.
-    pub fn symbol_is_dropcap(&self) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorSymbolIsDropcap(*handle) != 0 })
+    /// Returns `Ok(())` if the word was replaced, otherwise returns `InvalidParameterError`
+    /// when the text is empty or contains whitespace, the iterator is not on a recognized
+    /// word, or the text has characters outside the character set of the recognition
+    /// language.
+    pub fn replace_word(&self, text: &str) -> Result<()> {
+        if text.is_empty() || text.chars().any(char::is_whitespace) {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        let text = to_cstring(text)?;
+        let (_results, handle) = self.iterator.lock()?;
+        if unsafe { TessRsMutableIteratorReplaceWord(*handle, text.as_ptr()) } == 0 {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        self.iterator.results.edit();
+        Ok(())
     }
 
-    /// Gets the next iterator.
-    ///
-    /// # Arguments
+    /// Deletes the current word and moves to the word that followed it.
     ///
-    /// * `level` - Level of the iterator.
+    /// Call `next` only when moving on without deleting, or the following word is skipped.
     ///
     /// # Returns
     ///
-    /// Returns `true` if the next iterator is successful, otherwise returns `false`.
-    pub fn next(&self, level: i32) -> Result<bool> {
-        let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-        Ok(unsafe { TessResultIteratorNext(*handle, level) != 0 })
+    /// Returns `Ok(true)` if the iterator is on the following word, `Ok(false)` if the
+    /// deleted word was the last one, otherwise returns `NullPointerError` when the iterator
+    /// is not on a word.
+    pub fn delete_word(&self) -> Result<bool> {
+        let (_results, handle) = self.iterator.lock()?;
+        let mut has_next: c_int = 0;
+        if unsafe { TessRsMutableIteratorDeleteWord(*handle, &mut has_next) } == 0 {
+            return Err(TesseractError::NullPointerError);
+        }
+        self.iterator.results.edit();
+        Ok(has_next != 0)
     }
-
-    // /// Sets the value for the current iterator.
-    // ///
-    // /// # Arguments
-    // ///
-    // /// * `level` - Level of the value.
-    // /// * `value` - Value to set.
-    // ///
-    // /// # Returns
-    // ///
-    // /// Returns `true` if setting the value is successful, otherwise returns `false`.
-    // pub fn set_value(&self, level: i32, value: &str) -> Result<bool> {
-    //     let c_value = CString::new(value).unwrap();
-    //     let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-    //     let result = unsafe { TessMutableIteratorSetValue(*handle, level, c_value.as_ptr()) };
-    //     Ok(result != 0)
-    // }
-
-    // /// Deletes the MutableIterator.
-    // ///
-    // /// # Returns
-    // ///
-    // /// Returns `true` if deleting the MutableIterator is successful, otherwise returns `false`.
-    // pub fn delete(&self) -> Result<bool> {
-    //     let handle = self.handle.lock().map_err(|_| TesseractError::MutexError)?;
-    //     let result = unsafe { TessMutableIteratorDelete(*handle) };
-    //     Ok(result != 0)
-    // }
 }
 
-impl Drop for MutableIterator {
-    fn drop(&mut self) {
-        if let Ok(handle) = self.handle.lock() {
-            unsafe { TessResultIteratorDelete(*handle) };
-        }
+impl<'api> Deref for MutableIterator<'api> {
+    type Target = ResultIterator<'api>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.iterator
     }
 }
 
+// Edits compiled from `shim/mutable_iterator.cpp` by the build script.
+#[cfg(feature = "build-tesseract")]
 extern "C" {
-    // These functions don't exist in Tesseract C API
-    // pub fn TessMutableIteratorSetValue(
-    //     handle: *mut c_void,
-    //     level: c_int,
-    //     value: *const c_char,
-    // ) -> c_int;
-    // pub fn TessMutableIteratorDelete(handle: *mut c_void) -> c_int;
-    pub fn TessResultIteratorDelete(handle: *mut c_void);
-    pub fn TessDeleteText(text: *mut c_char);
+    fn TessRsMutableIteratorReplaceWord(handle: *mut c_void, text: *const c_char) -> c_int;
+    fn TessRsMutableIteratorDeleteWord(handle: *mut c_void, has_next: *mut c_int) -> c_int;
 }
@@ -0,0 +1,394 @@
+use crate::enums::{TessPageIteratorLevel, TessParagraphJustification, TessPolyBlockType};
+use crate::error::{Result, TesseractError};
+use crate::result_iterator::ResultIterator;
+
+/// Axis-aligned bounding box in image coordinates (origin at the top-left corner).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BoundingBox {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl BoundingBox {
+    /// Creates a new bounding box from its edges.
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        BoundingBox {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Gets the width of the bounding box.
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    /// Gets the height of the bounding box.
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+}
+
+impl From<(i32, i32, i32, i32)> for BoundingBox {
+    fn from((left, top, right, bottom): (i32, i32, i32, i32)) -> Self {
+        BoundingBox::new(left, top, right, bottom)
+    }
+}
+
+/// Baseline of a text line or word, given as two points in image coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Baseline {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+}
+
+impl From<(i32, i32, i32, i32)> for Baseline {
+    fn from((x1, y1, x2, y2): (i32, i32, i32, i32)) -> Self {
+        Baseline { x1, y1, x2, y2 }
+    }
+}
+
+/// Font attributes reported for a word.
+///
+/// These are only available from the legacy engine; LSTM-only recognition leaves them unset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FontAttributes {
+    pub is_bold: bool,
+    pub is_italic: bool,
+    pub is_underlined: bool,
+    pub is_monospace: bool,
+    pub is_serif: bool,
+    pub is_smallcaps: bool,
+    pub pointsize: i32,
+    pub font_id: i32,
+}
+
+/// Layout information for a paragraph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParagraphInfo {
+    pub justification: TessParagraphJustification,
+    pub is_list_item: bool,
+    pub is_crown: bool,
+    pub first_line_indent: i32,
+}
+
+/// A single recognized symbol (usually one character).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: f32,
+    pub is_superscript: bool,
+    pub is_subscript: bool,
+    pub is_dropcap: bool,
+}
+
+/// A recognized word and its symbols.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub baseline: Option<Baseline>,
+    pub confidence: f32,
+    pub font: Option<FontAttributes>,
+    pub language: Option<String>,
+    pub is_from_dictionary: bool,
+    pub is_numeric: bool,
+    pub symbols: Vec<Symbol>,
+}
+
+/// A text line and its words.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub baseline: Option<Baseline>,
+    pub confidence: f32,
+    pub words: Vec<Word>,
+}
+
+/// A paragraph and its lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paragraph {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: f32,
+    pub info: Option<ParagraphInfo>,
+    pub lines: Vec<Line>,
+}
+
+/// A layout block and its paragraphs.
+///
+/// Image, line and other non-text blocks have no paragraphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: f32,
+    pub block_type: TessPolyBlockType,
+    pub paragraphs: Vec<Paragraph>,
+}
+
+/// Owned recognition result for a single page.
+///
+/// Unlike `ResultIterator`, a `Page` holds no handles into the engine and can be kept,
+/// cloned or sent to other threads after the `TesseractAPI` has moved on.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Page {
+    pub blocks: Vec<Block>,
+}
+
+impl Page {
+    /// Collects the full result hierarchy from a result iterator in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - Result iterator positioned at the start of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns the collected `Page` if successful, otherwise returns an error.
+    pub(crate) fn from_result_iterator(iterator: &ResultIterator) -> Result<Self> {
+        let mut page = Page::default();
+
+        if iterator.is_empty(TessPageIteratorLevel::RIL_BLOCK)? {
+            return Ok(page);
+        }
+
+        loop {
+            if page.blocks.is_empty()
+                || iterator.is_at_beginning_of(TessPageIteratorLevel::RIL_BLOCK)?
+            {
+                page.blocks.push(Block {
+                    text: text_at(iterator, TessPageIteratorLevel::RIL_BLOCK)?,
+                    bbox: iterator
+                        .get_bounding_box(TessPageIteratorLevel::RIL_BLOCK)?
+                        .into(),
+                    confidence: iterator.confidence(TessPageIteratorLevel::RIL_BLOCK)?,
+                    block_type: iterator.block_type()?,
+                    paragraphs: Vec::new(),
+                });
+            }
+            let block = page.blocks.last_mut().unwrap();
+
+            // Non-text blocks have nothing below the block level.
+            if iterator.is_empty(TessPageIteratorLevel::RIL_PARA)? {
+                if !iterator.next(TessPageIteratorLevel::RIL_BLOCK)? {
+                    break;
+                }
+                continue;
+            }
+
+            if block.paragraphs.is_empty()
+                || iterator.is_at_beginning_of(TessPageIteratorLevel::RIL_PARA)?
+            {
+                block.paragraphs.push(Paragraph {
+                    text: text_at(iterator, TessPageIteratorLevel::RIL_PARA)?,
+                    bbox: iterator
+                        .get_bounding_box(TessPageIteratorLevel::RIL_PARA)?
+                        .into(),
+                    confidence: iterator.confidence(TessPageIteratorLevel::RIL_PARA)?,
+                    info: iterator.paragraph_info().ok().map(
+                        |(justification, is_list_item, is_crown, first_line_indent)| {
+                            ParagraphInfo {
+                                justification,
+                                is_list_item,
+                                is_crown,
+                                first_line_indent,
+                            }
+                        },
+                    ),
+                    lines: Vec::new(),
+                });
+            }
+            let paragraph = block.paragraphs.last_mut().unwrap();
+
+            if paragraph.lines.is_empty()
+                || iterator.is_at_beginning_of(TessPageIteratorLevel::RIL_TEXTLINE)?
+            {
+                paragraph.lines.push(Line {
+                    text: text_at(iterator, TessPageIteratorLevel::RIL_TEXTLINE)?,
+                    bbox: iterator
+                        .get_bounding_box(TessPageIteratorLevel::RIL_TEXTLINE)?
+                        .into(),
+                    baseline: iterator
+                        .baseline(TessPageIteratorLevel::RIL_TEXTLINE)
+                        .ok()
+                        .map(Baseline::from),
+                    confidence: iterator.confidence(TessPageIteratorLevel::RIL_TEXTLINE)?,
+                    words: Vec::new(),
+                });
+            }
+            let line = paragraph.lines.last_mut().unwrap();
+
+            if line.words.is_empty()
+                || iterator.is_at_beginning_of(TessPageIteratorLevel::RIL_WORD)?
+            {
+                line.words.push(Word {
+                    text: text_at(iterator, TessPageIteratorLevel::RIL_WORD)?,
+                    bbox: iterator
+                        .get_bounding_box(TessPageIteratorLevel::RIL_WORD)?
+                        .into(),
+                    baseline: iterator
+                        .baseline(TessPageIteratorLevel::RIL_WORD)
+                        .ok()
+                        .map(Baseline::from),
+                    confidence: iterator.confidence(TessPageIteratorLevel::RIL_WORD)?,
+                    font: iterator.word_font_attributes().ok().map(
+                        |(
+                            is_bold,
+                            is_italic,
+                            is_underlined,
+                            is_monospace,
+                            is_serif,
+                            is_smallcaps,
+                            pointsize,
+                            font_id,
+                        )| FontAttributes {
+                            is_bold,
+                            is_italic,
+                            is_underlined,
+                            is_monospace,
+                            is_serif,
+                            is_smallcaps,
+                            pointsize,
+                            font_id,
+                        },
+                    ),
+                    language: iterator.word_recognition_language().ok(),
+                    is_from_dictionary: iterator.word_is_from_dictionary()?,
+                    is_numeric: iterator.word_is_numeric()?,
+                    symbols: Vec::new(),
+                });
+            }
+            let word = line.words.last_mut().unwrap();
+
+            word.symbols.push(Symbol {
+                text: text_at(iterator, TessPageIteratorLevel::RIL_SYMBOL)?,
+                bbox: iterator
+                    .get_bounding_box(TessPageIteratorLevel::RIL_SYMBOL)?
+                    .into(),
+                confidence: iterator.confidence(TessPageIteratorLevel::RIL_SYMBOL)?,
+                is_superscript: iterator.symbol_is_superscript()?,
+                is_subscript: iterator.symbol_is_subscript()?,
+                is_dropcap: iterator.symbol_is_dropcap()?,
+            });
+
+            if !iterator.next(TessPageIteratorLevel::RIL_SYMBOL)? {
+                break;
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Gets the recognized text of the whole page.
+    ///
+    /// # Returns
+    ///
+    /// Returns the text of all blocks, concatenated in reading order.
+    pub fn text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|block| block.text.as_str())
+            .collect()
+    }
+
+    /// Iterates over every word on the page in reading order.
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.blocks
+            .iter()
+            .flat_map(|block| &block.paragraphs)
+            .flat_map(|paragraph| &paragraph.lines)
+            .flat_map(|line| &line.words)
+    }
+
+    /// Iterates over every text line on the page in reading order.
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.blocks
+            .iter()
+            .flat_map(|block| &block.paragraphs)
+            .flat_map(|paragraph| &paragraph.lines)
+    }
+}
+
+/// Gets the text at the given level, treating a missing element as empty text.
+pub(crate) fn text_at(iterator: &ResultIterator, level: TessPageIteratorLevel) -> Result<String> {
+    match iterator.get_utf8_text(level) {
+        Err(TesseractError::NullPointerError) => Ok(String::new()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, left: i32) -> Word {
+        Word {
+            text: text.to_owned(),
+            bbox: BoundingBox::new(left, 10, left + 40, 30),
+            baseline: None,
+            confidence: 90.0,
+            font: None,
+            language: None,
+            is_from_dictionary: true,
+            is_numeric: false,
+            symbols: Vec::new(),
+        }
+    }
+
+    fn sample_page() -> Page {
+        Page {
+            blocks: vec![Block {
+                text: "Hello world\n".to_owned(),
+                bbox: BoundingBox::new(0, 10, 90, 30),
+                confidence: 90.0,
+                block_type: TessPolyBlockType::PT_FLOWING_TEXT,
+                paragraphs: vec![Paragraph {
+                    text: "Hello world\n".to_owned(),
+                    bbox: BoundingBox::new(0, 10, 90, 30),
+                    confidence: 90.0,
+                    info: None,
+                    lines: vec![Line {
+                        text: "Hello world\n".to_owned(),
+                        bbox: BoundingBox::new(0, 10, 90, 30),
+                        baseline: Some(Baseline::from((0, 28, 90, 28))),
+                        confidence: 90.0,
+                        words: vec![word("Hello", 0), word("world", 50)],
+                    }],
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_bounding_box_dimensions() {
+        let bbox = BoundingBox::from((10, 20, 110, 70));
+        assert_eq!(bbox.width(), 100);
+        assert_eq!(bbox.height(), 50);
+    }
+
+    #[test]
+    fn test_page_words_and_text() {
+        let page = sample_page();
+        let words: Vec<&str> = page.words().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, vec!["Hello", "world"]);
+        assert_eq!(page.lines().count(), 1);
+        assert_eq!(page.text(), "Hello world\n");
+    }
+
+    #[test]
+    fn test_page_is_clone_send_sync() {
+        fn assert_traits<T: Clone + Send + Sync>() {}
+        assert_traits::<Page>();
+        let page = sample_page();
+        assert_eq!(page.clone(), page);
+    }
+}
@@ -2,21 +2,37 @@ use crate::enums::{
     TessOrientation, TessPageIteratorLevel, TessParagraphJustification, TessPolyBlockType,
     TessTextlineOrder, TessWritingDirection,
 };
+use crate::results::ResultsGuard;
 use crate::TesseractError;
 use std::os::raw::{c_float, c_int, c_void};
-use std::sync::Arc;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard};
 
-pub struct PageIterator {
+/// Iterator over the layout of a page.
+///
+/// Like `ResultIterator`, the iterator borrows the `TesseractAPI` it was created from and is
+/// invalidated by calls that clear or replace the results; its methods then return
+/// `TesseractError::IteratorInvalidated`.
+pub struct PageIterator<'api> {
     pub handle: Arc<Mutex<*mut c_void>>,
+    results: ResultsGuard<'api>,
 }
 
-unsafe impl Send for PageIterator {}
-unsafe impl Sync for PageIterator {}
+unsafe impl Send for PageIterator<'_> {}
+unsafe impl Sync for PageIterator<'_> {}
 
-impl PageIterator {
+impl<'api> PageIterator<'api> {
     /// Creates a new instance of the PageIterator.
     ///
+    /// The iterator is not tied to a `TesseractAPI`, so it cannot detect that the layout it
+    /// reads was freed. Use `TesseractAPI::analyse_layout` instead where possible.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid PageIterator handle that nothing else deletes, as the returned
+    /// iterator deletes it when dropped. The layout it reads must stay alive and unchanged
+    /// while the iterator is in use: the engine must not be dropped, and `set_image`,
+    /// `recognize`, `clear`, `end` and the other calls replacing the layout must not be made.
+    ///
     /// # Arguments
     ///
     /// * `handle` - Pointer to the PageIterator.
@@ -24,16 +40,44 @@ impl PageIterator {
     /// # Returns
     ///
     /// Returns the new instance of the PageIterator.
-    pub fn new(handle: *mut c_void) -> Self {
+    pub unsafe fn new(handle: *mut c_void) -> Self {
+        Self::with_results(handle, ResultsGuard::unchecked())
+    }
+
+    pub(crate) fn with_results(handle: *mut c_void, results: ResultsGuard<'api>) -> Self {
         PageIterator {
             handle: Arc::new(Mutex::new(handle)),
+            results,
         }
     }
 
+    /// Locks the engine and the iterator after checking that the layout is still alive.
+    fn lock(
+        &self,
+    ) -> Result<
+        (
+            Option<MutexGuard<'api, *mut c_void>>,
+            MutexGuard<'_, *mut c_void>,
+        ),
+        TesseractError,
+    > {
+        let results = self.results.lock()?;
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok((results, handle))
+    }
+
     /// Begins the iteration.
-    pub fn begin(&self) {
-        let handle = self.handle.lock().unwrap();
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if successful, otherwise returns an error.
+    pub fn begin(&self) -> Result<(), TesseractError> {
+        let (_results, handle) = self.lock()?;
         unsafe { TessPageIteratorBegin(*handle) };
+        Ok(())
     }
 
     /// Gets the next iterator.
@@ -45,9 +89,9 @@ impl PageIterator {
     /// # Returns
     ///
     /// Returns `true` if the next iterator is successful, otherwise returns `false`.
-    pub fn next(&self, level: TessPageIteratorLevel) -> bool {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessPageIteratorNext(*handle, level as c_int) != 0 }
+    pub fn next(&self, level: TessPageIteratorLevel) -> Result<bool, TesseractError> {
+        let (_results, handle) = self.lock()?;
+        Ok(unsafe { TessPageIteratorNext(*handle, level as c_int) != 0 })
     }
 
     /// Checks if the current iterator is at the beginning of the specified level.
@@ -59,9 +103,9 @@ impl PageIterator {
     /// # Returns
     ///
     /// Returns `true` if the current iterator is at the beginning of the specified level, otherwise returns `false`.
-    pub fn is_at_beginning_of(&self, level: TessPageIteratorLevel) -> bool {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessPageIteratorIsAtBeginningOf(*handle, level as c_int) != 0 }
+    pub fn is_at_beginning_of(&self, level: TessPageIteratorLevel) -> Result<bool, TesseractError> {
+        let (_results, handle) = self.lock()?;
+        Ok(unsafe { TessPageIteratorIsAtBeginningOf(*handle, level as c_int) != 0 })
     }
 
     /// Checks if the current iterator is at the final element of the specified level.
@@ -78,9 +122,11 @@ impl PageIterator {
         &self,
         level: TessPageIteratorLevel,
         element: TessPageIteratorLevel,
-    ) -> bool {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessPageIteratorIsAtFinalElement(*handle, level as c_int, element as c_int) != 0 }
+    ) -> Result<bool, TesseractError> {
+        let (_results, handle) = self.lock()?;
+        Ok(unsafe {
+            TessPageIteratorIsAtFinalElement(*handle, level as c_int, element as c_int) != 0
+        })
     }
 
     /// Gets the bounding box of the current iterator.
@@ -100,7 +146,7 @@ impl PageIterator {
         let mut top = 0;
         let mut right = 0;
         let mut bottom = 0;
-        let handle = self.handle.lock().unwrap();
+        let (_results, handle) = self.lock()?;
         let result = unsafe {
             TessPageIteratorBoundingBox(
                 *handle,
@@ -123,10 +169,10 @@ impl PageIterator {
     /// # Returns
     ///
     /// Returns the block type as a `TessPolyBlockType`.
-    pub fn block_type(&self) -> TessPolyBlockType {
-        let handle = self.handle.lock().unwrap();
+    pub fn block_type(&self) -> Result<TessPolyBlockType, TesseractError> {
+        let (_results, handle) = self.lock()?;
         let block_type = unsafe { TessPageIteratorBlockType(*handle) };
-        unsafe { std::mem::transmute(block_type) }
+        Ok(unsafe { std::mem::transmute(block_type) })
     }
 
     /// Gets the baseline of the current iterator.
@@ -143,7 +189,7 @@ impl PageIterator {
         let mut y1 = 0;
         let mut x2 = 0;
         let mut y2 = 0;
-        let handle = self.handle.lock().unwrap();
+        let (_results, handle) = self.lock()?;
         let result =
             unsafe { TessPageIteratorBaseline(*handle, level, &mut x1, &mut y1, &mut x2, &mut y2) };
         if result == 0 {
@@ -173,7 +219,7 @@ impl PageIterator {
         let mut writing_direction = 0;
         let mut textline_order = 0;
         let mut deskew_angle = 0.0;
-        let handle = self.handle.lock().unwrap();
+        let (_results, handle) = self.lock()?;
         let result = unsafe {
             TessPageIteratorOrientation(
                 *handle,
@@ -207,7 +253,7 @@ impl PageIterator {
         let mut is_list_item = false;
         let mut is_crown = false;
         let mut first_line_indent = 0;
-        let handle = self.handle.lock().unwrap();
+        let (_results, handle) = self.lock()?;
         let result = unsafe {
             TessPageIteratorParagraphInfo(
                 *handle,
@@ -230,7 +276,7 @@ impl PageIterator {
     }
 }
 
-impl Drop for PageIterator {
+impl Drop for PageIterator<'_> {
     fn drop(&mut self) {
         if let Ok(handle) = self.handle.lock() {
             unsafe { TessPageIteratorDelete(*handle) };
@@ -0,0 +1,442 @@
+use crate::error::{Result, TesseractError};
+use crate::page::Page;
+use crate::pix::Pix;
+use crate::{TessResultRenderer, TesseractAPI};
+#[cfg(feature = "image")]
+use std::fs::File;
+#[cfg(feature = "image")]
+use std::io::{BufReader, Cursor, Read, Seek};
+#[cfg(feature = "image")]
+use std::path::Path;
+#[cfg(feature = "image")]
+use tiff::decoder::{Decoder, DecodingResult};
+#[cfg(feature = "image")]
+use tiff::tags::{ResolutionUnit, Tag};
+#[cfg(feature = "image")]
+use tiff::ColorType;
+
+/// A recognized page of a multi-page document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecognizedPage {
+    /// Zero-based position of the page in the document.
+    pub index: usize,
+    /// The recognition result for the page.
+    pub page: Page,
+}
+
+/// Iterator returned by `TesseractAPI::recognize_document`.
+///
+/// Each call to `next` sets the next page image on the engine and recognizes it. Pages that
+/// fail to load or recognize yield an error without ending the iteration, so the remaining
+/// pages are still processed.
+pub struct DocumentPages<'a, I> {
+    api: &'a TesseractAPI,
+    pages: I,
+    renderer: Option<&'a TessResultRenderer>,
+    index: usize,
+}
+
+impl<'a, I> DocumentPages<'a, I>
+where
+    I: Iterator<Item = Result<Pix>>,
+{
+    pub(crate) fn new(api: &'a TesseractAPI, pages: I) -> Self {
+        DocumentPages {
+            api,
+            pages,
+            renderer: None,
+            index: 0,
+        }
+    }
+
+    /// Adds every recognized page to a renderer.
+    ///
+    /// The renderer must already have begun its document. Ending the document is left to the
+    /// caller once the iterator is exhausted.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer receiving the pages.
+    ///
+    /// # Returns
+    ///
+    /// Returns the iterator with the renderer attached.
+    pub fn with_renderer(mut self, renderer: &'a TessResultRenderer) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
+
+    fn recognize(&self, index: usize, pix: &Pix) -> Result<RecognizedPage> {
+        self.api.set_image_2(pix)?;
+        let page = self.api.recognize_page()?;
+        if let Some(renderer) = self.renderer {
+            if !renderer.add_image(self.api) {
+                return Err(TesseractError::RenderError);
+            }
+        }
+        Ok(RecognizedPage { index, page })
+    }
+}
+
+impl<I> Iterator for DocumentPages<'_, I>
+where
+    I: Iterator<Item = Result<Pix>>,
+{
+    type Item = Result<RecognizedPage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pix = self.pages.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(pix.and_then(|pix| self.recognize(index, &pix)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pages.size_hint()
+    }
+}
+
+/// Page images read from a multi-page TIFF file or buffer.
+///
+/// Pages are decoded lazily, one directory at a time. Bilevel pages, including CCITT Group 4
+/// (fax) compressed ones, 2, 4, 8 and 16 bit grayscale, RGB and RGBA pages are supported; the
+/// page resolution is carried over when the file records one.
+#[cfg(feature = "image")]
+pub struct TiffPages<R: Read + Seek> {
+    decoder: Option<Decoder<R>>,
+    index: usize,
+}
+
+#[cfg(feature = "image")]
+impl TiffPages<BufReader<File>> {
+    /// Opens a TIFF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the TIFF file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the page source if the file is a readable TIFF, otherwise returns an error.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| TesseractError::FileError {
+            path: path.display().to_string(),
+            output: e.to_string(),
+        })?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'a> TiffPages<Cursor<&'a [u8]>> {
+    /// Reads a TIFF held in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Encoded TIFF data.
+    ///
+    /// # Returns
+    ///
+    /// Returns the page source if the data is a readable TIFF, otherwise returns an error.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self> {
+        Self::new(Cursor::new(bytes))
+    }
+}
+
+#[cfg(feature = "image")]
+impl<R: Read + Seek> TiffPages<R> {
+    /// Reads a TIFF from any seekable reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader positioned at the start of the TIFF data.
+    ///
+    /// # Returns
+    ///
+    /// Returns the page source if the data is a readable TIFF, otherwise returns an error.
+    pub fn new(reader: R) -> Result<Self> {
+        let decoder = Decoder::new(reader).map_err(decode_error)?;
+        Ok(TiffPages {
+            decoder: Some(decoder),
+            index: 0,
+        })
+    }
+}
+
+#[cfg(feature = "image")]
+impl<R: Read + Seek> Iterator for TiffPages<R> {
+    type Item = Result<Pix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decoder = self.decoder.as_mut()?;
+        if self.index > 0 {
+            if !decoder.more_images() {
+                self.decoder = None;
+                return None;
+            }
+            if let Err(e) = decoder.next_image() {
+                self.decoder = None;
+                return Some(Err(decode_error(e)));
+            }
+        }
+        self.index += 1;
+        Some(read_page(decoder))
+    }
+}
+
+#[cfg(feature = "image")]
+fn decode_error(error: tiff::TiffError) -> TesseractError {
+    TesseractError::ImageDecodeError(error.to_string())
+}
+
+/// The current directory of a TIFF, decoded to 8 bit samples.
+#[cfg(feature = "image")]
+struct DecodedPage {
+    bytes: Vec<u8>,
+    width: u32,
+    height: u32,
+    bytes_per_pixel: i32,
+    dpi: Option<i32>,
+}
+
+#[cfg(feature = "image")]
+fn decode_page<R: Read + Seek>(decoder: &mut Decoder<R>) -> Result<DecodedPage> {
+    let (width, height) = decoder.dimensions().map_err(decode_error)?;
+    let color_type = decoder.colortype().map_err(decode_error)?;
+    let data = decoder.read_image().map_err(decode_error)?;
+    let (bytes, bytes_per_pixel) = tiff_to_bytes(color_type, data, width as usize)?;
+    Ok(DecodedPage {
+        bytes,
+        width,
+        height,
+        bytes_per_pixel,
+        dpi: read_resolution(decoder),
+    })
+}
+
+/// Decodes the current directory of the TIFF into a `Pix`.
+#[cfg(feature = "image")]
+fn read_page<R: Read + Seek>(decoder: &mut Decoder<R>) -> Result<Pix> {
+    let page = decode_page(decoder)?;
+    let pix = Pix::from_bytes(
+        &page.bytes,
+        page.width as i32,
+        page.height as i32,
+        page.bytes_per_pixel,
+        page.width as i32 * page.bytes_per_pixel,
+    )?;
+    if let Some(dpi) = page.dpi {
+        pix.set_resolution(dpi, dpi)?;
+    }
+    Ok(pix)
+}
+
+/// Reads the horizontal resolution of the current directory in pixels per inch.
+#[cfg(feature = "image")]
+fn read_resolution<R: Read + Seek>(decoder: &mut Decoder<R>) -> Option<i32> {
+    let (numerator, denominator) = match decoder.find_tag(Tag::XResolution).ok()?? {
+        tiff::decoder::ifd::Value::Rational(n, d) => (n as f64, d as f64),
+        _ => return None,
+    };
+    if denominator == 0.0 {
+        return None;
+    }
+    let unit = decoder
+        .find_tag_unsigned::<u16>(Tag::ResolutionUnit)
+        .ok()
+        .flatten();
+    let dpi = match unit {
+        Some(u) if u == ResolutionUnit::Centimeter.to_u16() => numerator / denominator * 2.54,
+        Some(u) if u == ResolutionUnit::None.to_u16() => return None,
+        _ => numerator / denominator,
+    };
+    Some(dpi.round() as i32).filter(|&dpi| dpi > 0)
+}
+
+/// Converts decoded TIFF samples to 8 bit gray, RGB or RGBA bytes.
+///
+/// Gray pages with alpha drop the alpha channel, 16 bit samples keep their high byte, and
+/// gray samples of fewer than 8 bits, packed into rows of whole bytes, are scaled to 0..=255.
+/// The decoder has already inverted `WhiteIsZero` pages, so 0 is black either way.
+#[cfg(feature = "image")]
+fn tiff_to_bytes(
+    color_type: ColorType,
+    data: DecodingResult,
+    width: usize,
+) -> Result<(Vec<u8>, i32)> {
+    let samples: Vec<u8> = match (color_type, data) {
+        (ColorType::Gray(bits @ (1 | 2 | 4)), DecodingResult::U8(data)) => {
+            return Ok((unpack_gray(&data, bits, width), 1))
+        }
+        (
+            ColorType::Gray(8) | ColorType::GrayA(8) | ColorType::RGB(8) | ColorType::RGBA(8),
+            DecodingResult::U8(data),
+        ) => data,
+        (
+            ColorType::Gray(16) | ColorType::GrayA(16) | ColorType::RGB(16) | ColorType::RGBA(16),
+            DecodingResult::U16(data),
+        ) => data.into_iter().map(|v| (v >> 8) as u8).collect(),
+        (color_type, _) => {
+            return Err(TesseractError::ImageDecodeError(format!(
+                "unsupported TIFF color type {:?}",
+                color_type
+            )))
+        }
+    };
+    match color_type {
+        ColorType::Gray(_) => Ok((samples, 1)),
+        ColorType::GrayA(_) => Ok((samples.into_iter().step_by(2).collect(), 1)),
+        ColorType::RGB(_) => Ok((samples, 3)),
+        _ => Ok((samples, 4)),
+    }
+}
+
+/// Expands gray samples of 1, 2 or 4 bits, stored most significant bits first, to bytes.
+#[cfg(feature = "image")]
+fn unpack_gray(data: &[u8], bits: u8, width: usize) -> Vec<u8> {
+    let row_len = (width * usize::from(bits)).div_ceil(8);
+    let max = (1u16 << bits) - 1;
+    let mut bytes = Vec::with_capacity(width * (data.len() / row_len.max(1)));
+    for row in data.chunks_exact(row_len.max(1)) {
+        bytes.extend((0..width).map(|x| {
+            let bit = x * usize::from(bits);
+            let value = row[bit / 8] >> (8 - usize::from(bits) - bit % 8) & max as u8;
+            (u16::from(value) * 255 / max) as u8
+        }));
+    }
+    bytes
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiff_to_bytes_gray() {
+        let (bytes, bpp) =
+            tiff_to_bytes(ColorType::Gray(8), DecodingResult::U8(vec![0, 128, 255]), 3).unwrap();
+        assert_eq!((bytes, bpp), (vec![0, 128, 255], 1));
+
+        let (bytes, bpp) = tiff_to_bytes(
+            ColorType::Gray(16),
+            DecodingResult::U16(vec![0x0000, 0x80FF, 0xFFFF]),
+            3,
+        )
+        .unwrap();
+        assert_eq!((bytes, bpp), (vec![0x00, 0x80, 0xFF], 1));
+
+        let (bytes, bpp) = tiff_to_bytes(
+            ColorType::GrayA(8),
+            DecodingResult::U8(vec![10, 255, 20, 0]),
+            2,
+        )
+        .unwrap();
+        assert_eq!((bytes, bpp), (vec![10, 20], 1));
+    }
+
+    #[test]
+    fn test_tiff_to_bytes_color() {
+        let (bytes, bpp) =
+            tiff_to_bytes(ColorType::RGB(8), DecodingResult::U8(vec![1, 2, 3]), 1).unwrap();
+        assert_eq!((bytes, bpp), (vec![1, 2, 3], 3));
+
+        let (bytes, bpp) = tiff_to_bytes(
+            ColorType::RGBA(16),
+            DecodingResult::U16(vec![0x0100, 0x0200, 0x0300, 0xFF00]),
+            1,
+        )
+        .unwrap();
+        assert_eq!((bytes, bpp), (vec![1, 2, 3, 255], 4));
+    }
+
+    #[test]
+    fn test_tiff_to_bytes_rejects_unsupported() {
+        assert!(tiff_to_bytes(ColorType::Gray(1), DecodingResult::U16(vec![0]), 1).is_err());
+        assert!(tiff_to_bytes(ColorType::CMYK(8), DecodingResult::U8(vec![0; 4]), 1).is_err());
+        assert!(tiff_to_bytes(ColorType::RGB(8), DecodingResult::U16(vec![0; 3]), 1).is_err());
+    }
+
+    #[test]
+    fn test_tiff_to_bytes_packed_gray() {
+        // Two rows of 10 pixels, each padded to 2 bytes.
+        let data = vec![0b1010_0000, 0b0100_0000, 0b1111_1111, 0b1100_0000];
+        let (bytes, bpp) = tiff_to_bytes(ColorType::Gray(1), DecodingResult::U8(data), 10).unwrap();
+        assert_eq!(bpp, 1);
+        assert_eq!(
+            bytes,
+            [
+                [255, 0, 255, 0, 0, 0, 0, 0, 0, 255],
+                [255, 255, 255, 255, 255, 255, 255, 255, 255, 255]
+            ]
+            .concat()
+        );
+
+        let (bytes, _) =
+            tiff_to_bytes(ColorType::Gray(4), DecodingResult::U8(vec![0x0F, 0x80]), 3).unwrap();
+        assert_eq!(bytes, vec![0, 255, 136]);
+    }
+
+    /// Builds a single page, uncompressed, 1 bit TIFF of 8 by 2 pixels.
+    fn bilevel_tiff(photometric: u16, rows: [u8; 2]) -> Vec<u8> {
+        let mut file = b"II*\0\x08\0\0\0".to_vec();
+        let entries: [(u16, u16, u32); 8] = [
+            (256, 3, 8),                  // ImageWidth
+            (257, 3, 2),                  // ImageLength
+            (258, 3, 1),                  // BitsPerSample
+            (259, 3, 1),                  // Compression: none
+            (262, 3, photometric as u32), // PhotometricInterpretation
+            (273, 4, 8 + 2 + 8 * 12 + 4), // StripOffsets
+            (278, 3, 2),                  // RowsPerStrip
+            (279, 4, 2),                  // StripByteCounts
+        ];
+        file.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        for (tag, kind, value) in entries {
+            file.extend_from_slice(&tag.to_le_bytes());
+            file.extend_from_slice(&kind.to_le_bytes());
+            file.extend_from_slice(&1u32.to_le_bytes());
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+        file.extend_from_slice(&0u32.to_le_bytes());
+        file.extend_from_slice(&rows);
+        file
+    }
+
+    fn decode_first_page(tiff: &[u8]) -> DecodedPage {
+        decode_page(&mut Decoder::new(Cursor::new(tiff)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_bilevel_photometric_interpretation() {
+        let expected = [[0, 0, 0, 0, 255, 255, 255, 255], [255; 8]].concat();
+        // The same image stored with 1 as black and with 1 as white.
+        let white_is_zero = decode_first_page(&bilevel_tiff(0, [0xF0, 0x00]));
+        assert_eq!(white_is_zero.bytes, expected);
+        assert_eq!(white_is_zero.bytes_per_pixel, 1);
+        let black_is_zero = decode_first_page(&bilevel_tiff(1, [0x0F, 0xFF]));
+        assert_eq!(black_is_zero.bytes, expected);
+    }
+
+    #[test]
+    fn test_group4_fixture() {
+        let tiff = include_bytes!("../tests/test_images/sample_text_g4.tif");
+        let mut decoder = Decoder::new(Cursor::new(&tiff[..])).unwrap();
+        let page = decode_page(&mut decoder).unwrap();
+        assert_eq!((page.width, page.height), (800, 200));
+        assert_eq!((page.bytes_per_pixel, page.dpi), (1, Some(300)));
+        assert!(page.bytes.iter().all(|&value| value == 0 || value == 255));
+        // Mostly white paper with some black text.
+        let black = page.bytes.iter().filter(|&&value| value == 0).count();
+        assert!(black > 0 && black < page.bytes.len() / 4);
+
+        assert!(decoder.more_images());
+        decoder.next_image().unwrap();
+        assert_eq!(decode_page(&mut decoder).unwrap().bytes, page.bytes);
+        assert!(!decoder.more_images());
+    }
+
+    #[test]
+    fn test_tiff_pages_rejects_non_tiff() {
+        assert!(TiffPages::from_bytes(b"not a tiff").is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Type of a Tesseract parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParameterType {
+    Int,
+    Bool,
+    Double,
+    String,
+}
+
+impl fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParameterType::Int => "int",
+            ParameterType::Bool => "bool",
+            ParameterType::Double => "double",
+            ParameterType::String => "string",
+        })
+    }
+}
+
+/// Value of a Tesseract parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterValue {
+    Int(i32),
+    Bool(bool),
+    Double(f64),
+    String(String),
+}
+
+impl ParameterValue {
+    /// Gets the type of the value.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `ParameterType` matching the variant.
+    pub fn parameter_type(&self) -> ParameterType {
+        match self {
+            ParameterValue::Int(_) => ParameterType::Int,
+            ParameterValue::Bool(_) => ParameterType::Bool,
+            ParameterValue::Double(_) => ParameterType::Double,
+            ParameterValue::String(_) => ParameterType::String,
+        }
+    }
+}
+
+/// Formats the value the way `TesseractAPI::set_variable` expects it.
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterValue::Int(value) => write!(f, "{}", value),
+            ParameterValue::Bool(value) => f.write_str(if *value { "1" } else { "0" }),
+            ParameterValue::Double(value) => write!(f, "{}", value),
+            ParameterValue::String(value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<i32> for ParameterValue {
+    fn from(value: i32) -> Self {
+        ParameterValue::Int(value)
+    }
+}
+
+impl From<bool> for ParameterValue {
+    fn from(value: bool) -> Self {
+        ParameterValue::Bool(value)
+    }
+}
+
+impl From<f64> for ParameterValue {
+    fn from(value: f64) -> Self {
+        ParameterValue::Double(value)
+    }
+}
+
+impl From<&str> for ParameterValue {
+    fn from(value: &str) -> Self {
+        ParameterValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for ParameterValue {
+    fn from(value: String) -> Self {
+        ParameterValue::String(value)
+    }
+}
+
+/// A Tesseract parameter with its current and default values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub value: ParameterValue,
+    /// Value the parameter has on a newly created engine.
+    pub default: ParameterValue,
+    pub description: String,
+}
+
+impl Parameter {
+    /// Gets the type of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `ParameterType` of the parameter's value.
+    pub fn parameter_type(&self) -> ParameterType {
+        self.value.parameter_type()
+    }
+
+    /// Checks whether the parameter still has its default value.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if `value` equals `default`.
+    pub fn is_default(&self) -> bool {
+        self.value == self.default
+    }
+}
+
+/// Values of a set of parameters, as exported by `TesseractAPI::export_parameters`.
+///
+/// A snapshot converts to and from the format of Tesseract config files: one parameter per
+/// line, the name followed by whitespace and the value. Blank lines and lines starting with
+/// `#` are ignored.
+///
+/// ```
+/// use tesseract_rs::ParameterSnapshot;
+///
+/// let mut snapshot = ParameterSnapshot::from_config_str("# digits\ntessedit_char_whitelist 0123456789\n");
+/// snapshot.set("user_defined_dpi", 300);
+/// assert_eq!(snapshot.get("tessedit_char_whitelist"), Some("0123456789"));
+/// assert_eq!(
+///     snapshot.to_config_string(),
+///     "tessedit_char_whitelist 0123456789\nuser_defined_dpi 300\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParameterSnapshot {
+    values: BTreeMap<String, String>,
+}
+
+impl ParameterSnapshot {
+    /// Creates an empty snapshot.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the contents of a Tesseract config file.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Config file contents.
+    ///
+    /// # Returns
+    ///
+    /// Returns the snapshot holding every parameter in the file; later lines win.
+    pub fn from_config_str(text: &str) -> Self {
+        let mut snapshot = Self::new();
+        for line in text.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = match line.find([' ', '\t']) {
+                Some(end) => (&line[..end], line[end..].trim_start_matches([' ', '\t'])),
+                None => (line, ""),
+            };
+            snapshot.values.insert(name.to_owned(), value.to_owned());
+        }
+        snapshot
+    }
+
+    /// Formats the snapshot as a Tesseract config file.
+    ///
+    /// # Returns
+    ///
+    /// Returns one line per parameter, sorted by name.
+    pub fn to_config_string(&self) -> String {
+        self.values
+            .iter()
+            .map(|(name, value)| format!("{} {}\n", name, value))
+            .collect()
+    }
+
+    /// Gets the value of a parameter as written in a config file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value, or `None` if the snapshot does not contain the parameter.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Sets the value of a parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the parameter.
+    /// * `value` - Value of the parameter.
+    pub fn set(&mut self, name: &str, value: impl Into<ParameterValue>) {
+        self.values
+            .insert(name.to_owned(), value.into().to_string());
+    }
+
+    /// Removes a parameter from the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the parameter.
+    ///
+    /// # Returns
+    ///
+    /// Returns the removed value, or `None` if the snapshot did not contain the parameter.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    /// Iterates over the parameters in name order.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator of names and values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Gets the number of parameters in the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of parameters.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks whether the snapshot is empty.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the snapshot contains no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Splits the output of `TessBaseAPIPrintVariables` into names and descriptions.
+///
+/// Each line holds the name, the current value and the description separated by tabs. The
+/// printed value is not used, as doubles are rounded and the type is not part of the output.
+pub(crate) fn parse_print_variables(text: &str) -> Vec<(&str, &str)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let name = fields.next()?.trim();
+            let _value = fields.next()?;
+            let description = fields.next().unwrap_or("").trim();
+            (!name.is_empty()).then_some((name, description))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_print_variables() {
+        let text = "tessedit_pageseg_mode\t6\tPage seg mode: 0=osd only\n\
+                    tessedit_char_whitelist\t\tWhitelist of chars to recognize\n\
+                    textord_noise_sizefraction\t10\tFraction of size for maxima\n\
+                    \n";
+        assert_eq!(
+            parse_print_variables(text),
+            vec![
+                ("tessedit_pageseg_mode", "Page seg mode: 0=osd only"),
+                ("tessedit_char_whitelist", "Whitelist of chars to recognize"),
+                ("textord_noise_sizefraction", "Fraction of size for maxima"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_config_round_trip() {
+        let text = "# comment\r\n\
+                    tessedit_pageseg_mode\t6\n\
+                    tessedit_char_whitelist   0123 456\n\
+                    tessedit_char_blacklist\n\
+                    \n\
+                    tessedit_pageseg_mode 7\n";
+        let snapshot = ParameterSnapshot::from_config_str(text);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.get("tessedit_pageseg_mode"), Some("7"));
+        assert_eq!(snapshot.get("tessedit_char_whitelist"), Some("0123 456"));
+        assert_eq!(snapshot.get("tessedit_char_blacklist"), Some(""));
+        assert_eq!(
+            snapshot.to_config_string(),
+            "tessedit_char_blacklist \ntessedit_char_whitelist 0123 456\ntessedit_pageseg_mode 7\n"
+        );
+        assert_eq!(
+            ParameterSnapshot::from_config_str(&snapshot.to_config_string()),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn test_snapshot_set_and_remove() {
+        let mut snapshot = ParameterSnapshot::new();
+        assert!(snapshot.is_empty());
+        snapshot.set("preserve_interword_spaces", true);
+        snapshot.set("textord_noise_hfract", 0.015625);
+        assert_eq!(snapshot.get("preserve_interword_spaces"), Some("1"));
+        assert_eq!(snapshot.get("textord_noise_hfract"), Some("0.015625"));
+        assert_eq!(
+            snapshot.remove("preserve_interword_spaces"),
+            Some("1".to_owned())
+        );
+        assert_eq!(
+            snapshot.iter().collect::<Vec<_>>(),
+            vec![("textord_noise_hfract", "0.015625")]
+        );
+    }
+
+    #[test]
+    fn test_parameter_value_formatting() {
+        assert_eq!(ParameterValue::from(300).to_string(), "300");
+        assert_eq!(ParameterValue::from(true).to_string(), "1");
+        assert_eq!(ParameterValue::from(false).to_string(), "0");
+        assert_eq!(ParameterValue::from(0.00001).to_string(), "0.00001");
+        assert_eq!(ParameterValue::from("0123456789").to_string(), "0123456789");
+        assert_eq!(
+            ParameterValue::from(String::new()).parameter_type(),
+            ParameterType::String
+        );
+    }
+
+    #[test]
+    fn test_parameter_is_default() {
+        let mut parameter = Parameter {
+            name: "user_defined_dpi".to_owned(),
+            value: ParameterValue::Int(0),
+            default: ParameterValue::Int(0),
+            description: "Specify DPI for input image".to_owned(),
+        };
+        assert!(parameter.is_default());
+        parameter.value = ParameterValue::Int(300);
+        assert!(!parameter.is_default());
+    }
+}
@@ -0,0 +1,1310 @@
+use crate::error::{Result, TesseractError};
+use crate::page::{Baseline, Page, Word};
+use crate::pdf_font::{self, GLYPH_WIDTH};
+use crate::pdf_object::{deflate, format_number, write_object, Dictionary, ExistingPdf, Object};
+use crate::pix::Pix;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Resolution assumed for images that do not record one, in pixels per inch.
+const DEFAULT_DPI: u32 = 300;
+
+/// Name of the text layer font in the page resources.
+const FONT_RESOURCE: &str = "F0";
+
+/// Name of the page image in the page resources.
+const IMAGE_RESOURCE: &str = "Im0";
+
+/// Compression of the page images written by `PdfWriter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PdfImageCompression {
+    /// Stores the pixels uncompressed.
+    None,
+    /// Compresses the pixels losslessly with Deflate.
+    #[default]
+    Flate,
+    /// Encodes the pixels as JPEG with the given quality, from 1 (smallest) to 100 (best).
+    #[cfg(feature = "image")]
+    Jpeg { quality: u8 },
+}
+
+/// Document information of a PDF, shown by viewers as the document properties.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    pub creator: Option<String>,
+    pub producer: Option<String>,
+    pub creation_date: Option<SystemTime>,
+}
+
+impl PdfMetadata {
+    /// Gets the entries of the document information dictionary, in the order they are written.
+    fn entries(&self) -> Vec<(&'static str, Object)> {
+        let texts = [
+            ("Title", &self.title),
+            ("Author", &self.author),
+            ("Subject", &self.subject),
+            ("Keywords", &self.keywords),
+            ("Creator", &self.creator),
+            ("Producer", &self.producer),
+        ];
+        let mut entries: Vec<_> = texts
+            .into_iter()
+            .filter_map(|(key, value)| value.as_deref().map(|value| (key, text_string(value))))
+            .collect();
+        if let Some(date) = self.creation_date {
+            entries.push(("CreationDate", Object::String(pdf_date(date).into_bytes())));
+        }
+        entries
+    }
+}
+
+/// Options of a `PdfWriter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfOptions {
+    /// Compression of the page images.
+    pub image_compression: PdfImageCompression,
+    /// Whether the page contents, which hold the text layer, are compressed with Deflate.
+    pub compress_text: bool,
+    /// Page size in points as `(width, height)`, with the image scaled to fit and centered.
+    /// Both must be finite and positive. When `None`, each page has the size of its image at
+    /// the image resolution.
+    pub page_size: Option<(f32, f32)>,
+    /// Whether to leave the images out and write only the invisible text, for example to
+    /// lay it over pages that are already in another PDF.
+    pub text_only: bool,
+    /// Document information. When appending, these entries replace those of the existing
+    /// document and its other entries are kept.
+    pub metadata: PdfMetadata,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            image_compression: PdfImageCompression::Flate,
+            compress_text: true,
+            page_size: None,
+            text_only: false,
+            metadata: PdfMetadata::default(),
+        }
+    }
+}
+
+/// A page image for `PdfWriter`, as 8-bit grayscale or RGB pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfImage {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    color: bool,
+    dpi: u32,
+}
+
+impl PdfImage {
+    /// Creates a page image from packed pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Pixels, `width * bytes_per_pixel` bytes per line without padding.
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    /// * `bytes_per_pixel` - 1 for grayscale, 3 for RGB or 4 for RGBA, whose alpha is dropped.
+    /// * `dpi` - Resolution of the image, which sets the page size.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image if the arguments describe a valid image, otherwise returns an error.
+    pub fn new(
+        data: &[u8],
+        width: u32,
+        height: u32,
+        bytes_per_pixel: u32,
+        dpi: u32,
+    ) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(TesseractError::InvalidDimensions);
+        }
+        if dpi == 0 {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        if ![1, 3, 4].contains(&bytes_per_pixel) {
+            return Err(TesseractError::InvalidBytesPerPixel);
+        }
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(bytes_per_pixel as usize));
+        if expected != Some(data.len()) {
+            return Err(TesseractError::InvalidImageData);
+        }
+        let data = if bytes_per_pixel == 4 {
+            data.chunks_exact(4)
+                .flat_map(|pixel| &pixel[..3])
+                .copied()
+                .collect()
+        } else {
+            data.to_vec()
+        };
+        Ok(PdfImage {
+            data,
+            width,
+            height,
+            color: bytes_per_pixel != 1,
+            dpi,
+        })
+    }
+
+    /// Creates a page image from a Leptonica image.
+    ///
+    /// Images without a resolution are taken to be 300 DPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - The image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image if successful, otherwise returns an error.
+    pub fn from_pix(pix: &Pix) -> Result<Self> {
+        let (data, bytes_per_pixel) = pix.to_bytes()?;
+        let dpi = match pix.resolution()?.0 {
+            dpi if dpi > 0 => dpi as u32,
+            _ => DEFAULT_DPI,
+        };
+        Self::new(
+            &data,
+            pix.width()? as u32,
+            pix.height()? as u32,
+            bytes_per_pixel as u32,
+            dpi,
+        )
+    }
+
+    /// Creates a page image from a decoded `image` crate image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The decoded image.
+    /// * `dpi` - Resolution of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image if successful, otherwise returns an error.
+    #[cfg(feature = "image")]
+    pub fn from_dynamic_image(image: &image::DynamicImage, dpi: u32) -> Result<Self> {
+        let (data, width, height, bytes_per_pixel) = crate::pix::dynamic_image_to_bytes(image);
+        Self::new(&data, width, height, bytes_per_pixel, dpi)
+    }
+
+    /// Gets the width of the image.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height of the image.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the resolution of the image.
+    pub fn dpi(&self) -> u32 {
+        self.dpi
+    }
+}
+
+/// Writes searchable PDF files: page images with the recognized text as an invisible layer
+/// that can be searched, selected and copied.
+///
+/// Unlike `TessResultRenderer::new_pdf_renderer`, the writer needs no font in the tessdata
+/// directory and works from owned `Page` results, so the text can be corrected before it is
+/// written. It writes either a new document or an update appended to an existing one, which
+/// adds the new pages after the existing pages without rewriting them.
+///
+/// Objects are written as pages are added, so the output only becomes a valid PDF once
+/// `finish` has been called.
+///
+/// ```no_run
+/// # fn example(api: &tesseract_rs::TesseractAPI, pix: &tesseract_rs::Pix) -> tesseract_rs::Result<()> {
+/// use tesseract_rs::{PdfImage, PdfMetadata, PdfOptions, PdfWriter};
+///
+/// let options = PdfOptions {
+///     metadata: PdfMetadata {
+///         title: Some("Scanned letter".to_owned()),
+///         ..PdfMetadata::default()
+///     },
+///     ..PdfOptions::default()
+/// };
+/// let file = std::fs::File::create("letter.pdf").map_err(tesseract_rs::TesseractError::WriteError)?;
+/// let mut writer = PdfWriter::new(std::io::BufWriter::new(file), options)?;
+/// api.set_image_2(pix)?;
+/// writer.add_page(&PdfImage::from_pix(pix)?, &api.recognize_page()?)?;
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PdfWriter<W: Write> {
+    output: W,
+    position: u64,
+    options: PdfOptions,
+    /// Objects written so far, as (number, generation, offset).
+    objects: Vec<(u32, u16, u64)>,
+    next_number: u32,
+    font: u32,
+    /// The page tree root that the pages are added to, and its dictionary to be rewritten.
+    pages: (u32, u16),
+    pages_dictionary: Dictionary,
+    kids: Vec<Object>,
+    count: i64,
+    /// The catalog, which is only written for a new document.
+    catalog: Option<u32>,
+    /// Entries carried over into the trailer: the catalog, information and file identifier.
+    trailer: Dictionary,
+    info: Dictionary,
+    /// Offset of the previous cross-reference section and whether it is a stream.
+    previous: Option<(usize, bool)>,
+}
+
+impl<W: Write> PdfWriter<W> {
+    /// Starts a new document.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Where the document is written.
+    /// * `options` - Compression, page size and document information.
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer if the document header was written, otherwise returns `WriteError`.
+    pub fn new(output: W, options: PdfOptions) -> Result<Self> {
+        let mut writer = Self::with_output(output, options);
+        writer.emit(b"%PDF-1.5\n%\xe2\xe3\xcf\xd3\n")?;
+        let catalog = writer.allocate();
+        writer.pages = (writer.allocate(), 0);
+        writer.pages_dictionary = Dictionary::new().with("Type", Object::name("Pages"));
+        writer.catalog = Some(catalog);
+        writer.trailer.set("Root", Object::Reference(catalog, 0));
+        writer.write_font()?;
+        Ok(writer)
+    }
+
+    /// Starts an update that appends pages to an existing document.
+    ///
+    /// The existing document is copied to the output unchanged, followed by the new pages
+    /// and a new version of its page tree, as an incremental update.
+    ///
+    /// # Arguments
+    ///
+    /// * `existing` - The complete existing PDF file.
+    /// * `output` - Where the updated document is written.
+    /// * `options` - Compression, page size and document information for the new pages.
+    ///
+    /// # Returns
+    ///
+    /// Returns the writer if the existing document could be read and copied, otherwise
+    /// returns `ParseError` for documents that cannot be read or are encrypted, or
+    /// `WriteError`.
+    pub fn append(existing: &[u8], output: W, options: PdfOptions) -> Result<Self> {
+        let pdf = ExistingPdf::parse(existing)?;
+        let root = pdf
+            .trailer
+            .get("Root")
+            .ok_or_else(|| structure_error("trailer has no /Root"))?;
+        let catalog = pdf.resolve(root)?;
+        let pages = match catalog
+            .as_dictionary()
+            .and_then(|catalog| catalog.get("Pages"))
+        {
+            Some(Object::Reference(number, _)) => *number,
+            _ => return Err(structure_error("catalog has no page tree")),
+        };
+        let Object::Dictionary(mut pages_dictionary) = pdf.object(pages)? else {
+            return Err(structure_error("page tree root is not a dictionary"));
+        };
+        let kids = match pages_dictionary.get("Kids").map(|kids| pdf.resolve(kids)) {
+            Some(Ok(Object::Array(kids))) => kids,
+            None => Vec::new(),
+            _ => return Err(structure_error("page tree root has no /Kids array")),
+        };
+        let count = match pages_dictionary
+            .get("Count")
+            .map(|count| pdf.resolve(count))
+        {
+            Some(Ok(Object::Integer(count))) => count,
+            _ => kids.len() as i64,
+        };
+        pages_dictionary.remove("Kids");
+        pages_dictionary.remove("Count");
+        let info = match pdf.trailer.get("Info").map(|info| pdf.resolve(info)) {
+            Some(Ok(Object::Dictionary(info))) => info,
+            _ => Dictionary::new(),
+        };
+
+        let mut writer = Self::with_output(output, options);
+        writer.emit(existing)?;
+        if !existing.ends_with(b"\n") && !existing.ends_with(b"\r") {
+            writer.emit(b"\n")?;
+        }
+        writer.next_number = pdf.size().max(1);
+        writer.pages = (pages, pdf.generation(pages));
+        writer.pages_dictionary = pages_dictionary;
+        writer.kids = kids;
+        writer.count = count;
+        for key in ["Root", "Info", "ID"] {
+            if let Some(value) = pdf.trailer.get(key) {
+                writer.trailer.set(key, value.clone());
+            }
+        }
+        writer.info = info;
+        writer.previous = Some((pdf.startxref, pdf.xref_stream));
+        writer.write_font()?;
+        Ok(writer)
+    }
+
+    fn with_output(output: W, options: PdfOptions) -> Self {
+        PdfWriter {
+            output,
+            position: 0,
+            options,
+            objects: Vec::new(),
+            next_number: 1,
+            font: 0,
+            pages: (0, 0),
+            pages_dictionary: Dictionary::new(),
+            kids: Vec::new(),
+            count: 0,
+            catalog: None,
+            trailer: Dictionary::new(),
+            info: Dictionary::new(),
+            previous: None,
+        }
+    }
+
+    /// Adds a page with an image and its recognized text.
+    ///
+    /// Each word is placed on its baseline, or on the baseline of its line or the bottom of
+    /// its bounding box when it has none, and stretched to the width of its bounding box.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The page image.
+    /// * `page` - Text recognized from the same image, whose coordinates are image pixels.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the page was written, otherwise returns an error.
+    pub fn add_page(&mut self, image: &PdfImage, page: &Page) -> Result<()> {
+        let placement = Placement::new(image, self.options.page_size)?;
+        let mut resources = Dictionary::new().with(
+            "Font",
+            Object::Dictionary(Dictionary::new().with(FONT_RESOURCE, Object::reference(self.font))),
+        );
+        let mut content = Vec::new();
+        if !self.options.text_only {
+            let image_number = self.write_image(image)?;
+            resources.set(
+                "XObject",
+                Object::Dictionary(
+                    Dictionary::new().with(IMAGE_RESOURCE, Object::reference(image_number)),
+                ),
+            );
+            content.extend_from_slice(
+                format!(
+                    "q {} 0 0 {} {} {} cm /{} Do Q\n",
+                    format_number(placement.scale * f64::from(image.width)),
+                    format_number(placement.scale * f64::from(image.height)),
+                    format_number(placement.x),
+                    format_number(placement.y),
+                    IMAGE_RESOURCE
+                )
+                .as_bytes(),
+            );
+        }
+        content.extend_from_slice(&text_layer(page, &placement));
+
+        let content_number = self.allocate();
+        let mut content_dictionary = Dictionary::new();
+        if self.options.compress_text {
+            content = deflate(&content);
+            content_dictionary.set("Filter", Object::name("FlateDecode"));
+        }
+        self.write_stream(content_number, content_dictionary, &content)?;
+
+        let page_number = self.allocate();
+        let media_box = [0.0, 0.0, placement.page_width, placement.page_height]
+            .into_iter()
+            .map(Object::Real)
+            .collect();
+        let page_dictionary = Dictionary::new()
+            .with("Type", Object::name("Page"))
+            .with("Parent", Object::Reference(self.pages.0, self.pages.1))
+            .with("MediaBox", Object::Array(media_box))
+            // Page trees of existing documents may pass on a rotation.
+            .with("Rotate", Object::Integer(0))
+            .with("Resources", Object::Dictionary(resources))
+            .with("Contents", Object::reference(content_number));
+        self.write_object(page_number, 0, &Object::Dictionary(page_dictionary))?;
+        self.kids.push(Object::reference(page_number));
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Completes the document with its page tree, document information and
+    /// cross-reference section.
+    ///
+    /// # Returns
+    ///
+    /// Returns the output if the document was completed, otherwise returns `WriteError`.
+    pub fn finish(mut self) -> Result<W> {
+        let mut pages = self.pages_dictionary.clone();
+        pages.set("Kids", Object::Array(std::mem::take(&mut self.kids)));
+        pages.set("Count", Object::Integer(self.count));
+        self.write_object(self.pages.0, self.pages.1, &Object::Dictionary(pages))?;
+
+        if let Some(catalog) = self.catalog {
+            let dictionary = Dictionary::new()
+                .with("Type", Object::name("Catalog"))
+                .with("Pages", Object::reference(self.pages.0));
+            self.write_object(catalog, 0, &Object::Dictionary(dictionary))?;
+        }
+
+        let entries = self.options.metadata.entries();
+        if !entries.is_empty() {
+            let mut info = std::mem::take(&mut self.info);
+            for (key, value) in entries {
+                info.set(key, value);
+            }
+            let number = self.allocate();
+            self.write_object(number, 0, &Object::Dictionary(info))?;
+            self.trailer.set("Info", Object::reference(number));
+        }
+
+        match self.previous {
+            Some((_, true)) => self.write_xref_stream()?,
+            _ => self.write_xref_table()?,
+        }
+        self.output.flush().map_err(TesseractError::WriteError)?;
+        Ok(self.output)
+    }
+
+    fn write_xref_table(&mut self) -> Result<()> {
+        let offset = self.position;
+        let mut entries: Vec<(u32, String)> = self
+            .objects
+            .iter()
+            .map(|(number, generation, offset)| {
+                (*number, format!("{:010} {:05} n\r\n", offset, generation))
+            })
+            .collect();
+        if self.previous.is_none() {
+            entries.push((0, "0000000000 65535 f\r\n".to_owned()));
+        }
+        entries.sort_by_key(|(number, _)| *number);
+
+        let mut table = b"xref\n".to_vec();
+        for run in runs(&entries, |(number, _)| *number) {
+            table.extend_from_slice(format!("{} {}\n", run[0].0, run.len()).as_bytes());
+            for (_, entry) in run {
+                table.extend_from_slice(entry.as_bytes());
+            }
+        }
+        table.extend_from_slice(b"trailer\n");
+        write_object(&mut table, &Object::Dictionary(self.trailer_dictionary()));
+        table.extend_from_slice(format!("\nstartxref\n{}\n%%EOF\n", offset).as_bytes());
+        self.emit(&table)
+    }
+
+    fn write_xref_stream(&mut self) -> Result<()> {
+        let number = self.allocate();
+        let offset = self.position;
+        let mut entries = self.objects.clone();
+        entries.push((number, 0, offset));
+        entries.sort_by_key(|(number, _, _)| *number);
+
+        let width = (8 - (offset.leading_zeros() / 8) as usize).max(1);
+        let mut data = Vec::new();
+        let mut index = Vec::new();
+        for run in runs(&entries, |(number, _, _)| *number) {
+            index.push(Object::Integer(i64::from(run[0].0)));
+            index.push(Object::Integer(run.len() as i64));
+            for (_, generation, offset) in run {
+                data.push(1);
+                data.extend_from_slice(&offset.to_be_bytes()[8 - width..]);
+                data.extend_from_slice(&generation.to_be_bytes());
+            }
+        }
+        let mut dictionary = self.trailer_dictionary();
+        dictionary.set("Type", Object::name("XRef"));
+        dictionary.set(
+            "W",
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(width as i64),
+                Object::Integer(2),
+            ]),
+        );
+        dictionary.set("Index", Object::Array(index));
+        dictionary.set("Filter", Object::name("FlateDecode"));
+        self.write_stream(number, dictionary, &deflate(&data))?;
+        self.emit(format!("startxref\n{}\n%%EOF\n", offset).as_bytes())
+    }
+
+    fn trailer_dictionary(&self) -> Dictionary {
+        let mut trailer =
+            Dictionary::new().with("Size", Object::Integer(i64::from(self.next_number)));
+        for key in ["Root", "Info", "ID"] {
+            if let Some(value) = self.trailer.get(key) {
+                trailer.set(key, value.clone());
+            }
+        }
+        if let Some((previous, _)) = self.previous {
+            trailer.set("Prev", Object::Integer(previous as i64));
+        }
+        trailer
+    }
+
+    /// Writes the text layer font, which is shared by all pages.
+    fn write_font(&mut self) -> Result<()> {
+        let font = self.allocate();
+        let descendant = self.allocate();
+        let descriptor = self.allocate();
+        let file = self.allocate();
+        let cid_to_gid = self.allocate();
+        let to_unicode = self.allocate();
+        self.font = font;
+
+        let type0 = Dictionary::new()
+            .with("Type", Object::name("Font"))
+            .with("Subtype", Object::name("Type0"))
+            .with("BaseFont", Object::name("GlyphLessFont"))
+            .with("Encoding", Object::name("Identity-H"))
+            .with(
+                "DescendantFonts",
+                Object::Array(vec![Object::reference(descendant)]),
+            )
+            .with("ToUnicode", Object::reference(to_unicode));
+        self.write_object(font, 0, &Object::Dictionary(type0))?;
+
+        let system_info = Dictionary::new()
+            .with("Registry", Object::String(b"Adobe".to_vec()))
+            .with("Ordering", Object::String(b"Identity".to_vec()))
+            .with("Supplement", Object::Integer(0));
+        let cid_font = Dictionary::new()
+            .with("Type", Object::name("Font"))
+            .with("Subtype", Object::name("CIDFontType2"))
+            .with("BaseFont", Object::name("GlyphLessFont"))
+            .with("CIDSystemInfo", Object::Dictionary(system_info))
+            .with("FontDescriptor", Object::reference(descriptor))
+            .with("DW", Object::Integer(i64::from(GLYPH_WIDTH)))
+            .with("CIDToGIDMap", Object::reference(cid_to_gid));
+        self.write_object(descendant, 0, &Object::Dictionary(cid_font))?;
+
+        let bounds = [0, 0, i64::from(GLYPH_WIDTH), 1000]
+            .into_iter()
+            .map(Object::Integer)
+            .collect();
+        let font_descriptor = Dictionary::new()
+            .with("Type", Object::name("FontDescriptor"))
+            .with("FontName", Object::name("GlyphLessFont"))
+            // Fixed pitch and symbolic.
+            .with("Flags", Object::Integer(5))
+            .with("FontBBox", Object::Array(bounds))
+            .with("ItalicAngle", Object::Integer(0))
+            .with("Ascent", Object::Integer(1000))
+            .with("Descent", Object::Integer(0))
+            .with("CapHeight", Object::Integer(1000))
+            .with("StemV", Object::Integer(80))
+            .with("FontFile2", Object::reference(file));
+        self.write_object(descriptor, 0, &Object::Dictionary(font_descriptor))?;
+
+        // The font data is always compressed; the glyph map alone is 128 KiB uncompressed.
+        let flate = Dictionary::new().with("Filter", Object::name("FlateDecode"));
+        let font_file = pdf_font::glyphless_font();
+        let length = Object::Integer(font_file.len() as i64);
+        self.write_stream(
+            file,
+            flate.clone().with("Length1", length),
+            &deflate(&font_file),
+        )?;
+        self.write_stream(
+            cid_to_gid,
+            flate.clone(),
+            &deflate(&pdf_font::cid_to_gid_map()),
+        )?;
+        self.write_stream(
+            to_unicode,
+            flate,
+            &deflate(pdf_font::TO_UNICODE_CMAP.as_bytes()),
+        )
+    }
+
+    fn write_image(&mut self, image: &PdfImage) -> Result<u32> {
+        let mut dictionary = Dictionary::new()
+            .with("Type", Object::name("XObject"))
+            .with("Subtype", Object::name("Image"))
+            .with("Width", Object::Integer(i64::from(image.width)))
+            .with("Height", Object::Integer(i64::from(image.height)))
+            .with(
+                "ColorSpace",
+                Object::name(if image.color {
+                    "DeviceRGB"
+                } else {
+                    "DeviceGray"
+                }),
+            )
+            .with("BitsPerComponent", Object::Integer(8));
+        let data = match self.options.image_compression {
+            PdfImageCompression::None => image.data.clone(),
+            PdfImageCompression::Flate => {
+                dictionary.set("Filter", Object::name("FlateDecode"));
+                deflate(&image.data)
+            }
+            #[cfg(feature = "image")]
+            PdfImageCompression::Jpeg { quality } => {
+                dictionary.set("Filter", Object::name("DCTDecode"));
+                encode_jpeg(image, quality)?
+            }
+        };
+        let number = self.allocate();
+        self.write_stream(number, dictionary, &data)?;
+        Ok(number)
+    }
+
+    fn allocate(&mut self) -> u32 {
+        let number = self.next_number;
+        self.next_number += 1;
+        number
+    }
+
+    fn write_object(&mut self, number: u32, generation: u16, object: &Object) -> Result<()> {
+        let mut bytes = format!("{} {} obj\n", number, generation).into_bytes();
+        write_object(&mut bytes, object);
+        bytes.extend_from_slice(b"\nendobj\n");
+        self.objects.push((number, generation, self.position));
+        self.emit(&bytes)
+    }
+
+    fn write_stream(&mut self, number: u32, dictionary: Dictionary, data: &[u8]) -> Result<()> {
+        let dictionary = dictionary.with("Length", Object::Integer(data.len() as i64));
+        let mut bytes = format!("{} 0 obj\n", number).into_bytes();
+        write_object(&mut bytes, &Object::Dictionary(dictionary));
+        bytes.extend_from_slice(b"\nstream\n");
+        self.objects.push((number, 0, self.position));
+        self.emit(&bytes)?;
+        self.emit(data)?;
+        self.emit(b"\nendstream\nendobj\n")
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<()> {
+        self.output
+            .write_all(bytes)
+            .map_err(TesseractError::WriteError)?;
+        self.position += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+fn structure_error(message: &str) -> TesseractError {
+    TesseractError::ParseError {
+        format: "PDF",
+        message: message.to_owned(),
+    }
+}
+
+/// Splits entries sorted by object number into runs of consecutive numbers.
+fn runs<T>(entries: &[T], number: impl Fn(&T) -> u32) -> Vec<&[T]> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=entries.len() {
+        if i == entries.len() || number(&entries[i]) != number(&entries[i - 1]) + 1 {
+            runs.push(&entries[start..i]);
+            start = i;
+        }
+    }
+    runs
+}
+
+/// Where an image is drawn on its page, in points.
+struct Placement {
+    /// Points per image pixel.
+    scale: f64,
+    x: f64,
+    y: f64,
+    image_height: f64,
+    page_width: f64,
+    page_height: f64,
+}
+
+impl Placement {
+    /// Places an image on a page of the given size, or on a page of its own size.
+    ///
+    /// # Returns
+    ///
+    /// Returns the placement, or `InvalidParameterError` if the page size is not finite and
+    /// positive.
+    fn new(image: &PdfImage, page_size: Option<(f32, f32)>) -> Result<Self> {
+        let (width, height) = (f64::from(image.width), f64::from(image.height));
+        Ok(match page_size {
+            Some((page_width, page_height)) => {
+                let valid = |size: f32| size.is_finite() && size > 0.0;
+                if !valid(page_width) || !valid(page_height) {
+                    return Err(TesseractError::InvalidParameterError);
+                }
+                let (page_width, page_height) = (f64::from(page_width), f64::from(page_height));
+                let scale = (page_width / width).min(page_height / height);
+                Placement {
+                    scale,
+                    x: (page_width - width * scale) / 2.0,
+                    y: (page_height - height * scale) / 2.0,
+                    image_height: height,
+                    page_width,
+                    page_height,
+                }
+            }
+            None => {
+                let scale = 72.0 / f64::from(image.dpi);
+                Placement {
+                    scale,
+                    x: 0.0,
+                    y: 0.0,
+                    image_height: height,
+                    page_width: width * scale,
+                    page_height: height * scale,
+                }
+            }
+        })
+    }
+
+    /// Converts image coordinates, with the origin at the top left, to page coordinates.
+    fn point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.x + x * self.scale,
+            self.y + (self.image_height - y) * self.scale,
+        )
+    }
+}
+
+/// Writes the invisible text of a page, one text object per line.
+fn text_layer(page: &Page, placement: &Placement) -> Vec<u8> {
+    let mut content = Vec::new();
+    for line in page.lines() {
+        let words: Vec<&Word> = line
+            .words
+            .iter()
+            .filter(|word| !word.text.trim().is_empty())
+            .collect();
+        if words.is_empty() {
+            continue;
+        }
+        let font_size = f64::from(line.bbox.height().max(1)) * placement.scale;
+        // Render mode 3 neither fills nor strokes the glyphs.
+        content.extend_from_slice(b"BT\n3 Tr\n");
+        content.extend_from_slice(
+            format!("/{} {} Tf\n", FONT_RESOURCE, format_number(font_size)).as_bytes(),
+        );
+        for (i, word) in words.iter().enumerate() {
+            let baseline = word.baseline.or(line.baseline).unwrap_or(Baseline {
+                x1: word.bbox.left,
+                y1: word.bbox.bottom,
+                x2: word.bbox.right,
+                y2: word.bbox.bottom,
+            });
+            let (dx, dy) = (
+                f64::from(baseline.x2 - baseline.x1),
+                f64::from(baseline.y2 - baseline.y1),
+            );
+            let length = dx.hypot(dy);
+            // Image coordinates point down, page coordinates up.
+            let (cos, sin) = if length > 0.0 {
+                (dx / length, -dy / length)
+            } else {
+                (1.0, 0.0)
+            };
+            let x = f64::from(word.bbox.left);
+            let y = if dx != 0.0 {
+                f64::from(baseline.y1) + (x - f64::from(baseline.x1)) * dy / dx
+            } else {
+                f64::from(word.bbox.bottom)
+            };
+            let (x, y) = placement.point(x, y);
+
+            // A space after each word but the last lets text extraction separate the words,
+            // so the text is stretched up to the next word when it follows on the right.
+            let mut text = word.text.trim().to_owned();
+            let mut span = word.bbox.width();
+            if let Some(next) = words.get(i + 1) {
+                text.push(' ');
+                if next.bbox.left >= word.bbox.right {
+                    span = next.bbox.left - word.bbox.left;
+                }
+            }
+            let units: Vec<u16> = text.encode_utf16().collect();
+            let natural_width = units.len() as f64 * font_size * f64::from(GLYPH_WIDTH) / 1000.0;
+            let stretch = 100.0 * f64::from(span.max(1)) * placement.scale / natural_width;
+
+            let hex: String = units.iter().map(|unit| format!("{:04X}", unit)).collect();
+            content.extend_from_slice(
+                format!(
+                    "{} {} {} {} {} {} Tm\n{} Tz\n<{}> Tj\n",
+                    format_number(cos),
+                    format_number(sin),
+                    format_number(-sin),
+                    format_number(cos),
+                    format_number(x),
+                    format_number(y),
+                    format_number(stretch),
+                    hex
+                )
+                .as_bytes(),
+            );
+        }
+        content.extend_from_slice(b"ET\n");
+    }
+    content
+}
+
+#[cfg(feature = "image")]
+fn encode_jpeg(image: &PdfImage, quality: u8) -> Result<Vec<u8>> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::ExtendedColorType;
+
+    let color_type = if image.color {
+        ExtendedColorType::Rgb8
+    } else {
+        ExtendedColorType::L8
+    };
+    let mut encoded = Vec::new();
+    JpegEncoder::new_with_quality(&mut encoded, quality.clamp(1, 100))
+        .encode(&image.data, image.width, image.height, color_type)
+        // JPEG images are limited to 65535 pixels on each side.
+        .map_err(|_| TesseractError::InvalidDimensions)?;
+    Ok(encoded)
+}
+
+/// Converts a document information string, using UTF-16 when it is not plain ASCII.
+fn text_string(value: &str) -> Object {
+    if value.bytes().all(|byte| (0x20..0x7f).contains(&byte)) {
+        return Object::String(value.as_bytes().to_vec());
+    }
+    let mut bytes = vec![0xfe, 0xff];
+    for unit in value.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    Object::String(bytes)
+}
+
+/// Formats a time as a PDF date in UTC, such as `D:20240131235959Z`.
+fn pdf_date(time: SystemTime) -> String {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs() as i64),
+    };
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    // Converts days since 1970-01-01 to a civil date, counting in 400-year eras that start
+    // on March 1st.
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "D:{:04}{:02}{:02}{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::{Block, BoundingBox, Line, Paragraph};
+    use crate::TessPolyBlockType;
+    use std::time::Duration;
+
+    fn word(text: &str, left: i32, right: i32) -> Word {
+        Word {
+            text: text.to_owned(),
+            bbox: BoundingBox::new(left, 10, right, 30),
+            baseline: Some(Baseline {
+                x1: left,
+                y1: 28,
+                x2: right,
+                y2: 28,
+            }),
+            confidence: 90.0,
+            font: None,
+            language: None,
+            is_from_dictionary: true,
+            is_numeric: false,
+            symbols: Vec::new(),
+        }
+    }
+
+    fn sample_page() -> Page {
+        let line = Line {
+            text: "sample text\n".to_owned(),
+            bbox: BoundingBox::new(10, 10, 110, 30),
+            baseline: None,
+            confidence: 90.0,
+            words: vec![word("sample", 10, 60), word("text", 70, 110)],
+        };
+        let paragraph = Paragraph {
+            text: line.text.clone(),
+            bbox: line.bbox,
+            confidence: 90.0,
+            info: None,
+            lines: vec![line],
+        };
+        Page {
+            blocks: vec![Block {
+                text: paragraph.text.clone(),
+                bbox: paragraph.bbox,
+                confidence: 90.0,
+                block_type: TessPolyBlockType::PT_FLOWING_TEXT,
+                paragraphs: vec![paragraph],
+            }],
+        }
+    }
+
+    fn sample_image() -> PdfImage {
+        PdfImage::new(&[255; 120 * 40], 120, 40, 1, 72).unwrap()
+    }
+
+    fn uncompressed() -> PdfOptions {
+        PdfOptions {
+            image_compression: PdfImageCompression::None,
+            compress_text: false,
+            ..PdfOptions::default()
+        }
+    }
+
+    fn write_document(options: PdfOptions, pages: usize) -> Vec<u8> {
+        let mut writer = PdfWriter::new(Vec::new(), options).unwrap();
+        for _ in 0..pages {
+            writer.add_page(&sample_image(), &sample_page()).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    /// Reads the page dictionaries of a document through its page tree.
+    fn page_dictionaries(file: &[u8]) -> Vec<Dictionary> {
+        let pdf = ExistingPdf::parse(file).unwrap();
+        let catalog = pdf.resolve(pdf.trailer.get("Root").unwrap()).unwrap();
+        let pages = pdf
+            .resolve(catalog.as_dictionary().unwrap().get("Pages").unwrap())
+            .unwrap();
+        let pages = pages.as_dictionary().unwrap();
+        let Some(Object::Array(kids)) = pages.get("Kids") else {
+            panic!("page tree without kids");
+        };
+        assert_eq!(
+            pages.get("Count").and_then(Object::as_integer),
+            Some(kids.len() as i64)
+        );
+        kids.iter()
+            .map(|kid| pdf.resolve(kid).unwrap().as_dictionary().unwrap().clone())
+            .collect()
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn test_image_validation() {
+        assert!(matches!(
+            PdfImage::new(&[0; 4], 0, 2, 1, 300),
+            Err(TesseractError::InvalidDimensions)
+        ));
+        assert!(matches!(
+            PdfImage::new(&[0; 3], 2, 2, 1, 300),
+            Err(TesseractError::InvalidImageData)
+        ));
+        assert!(matches!(
+            PdfImage::new(&[0; 8], 2, 2, 2, 300),
+            Err(TesseractError::InvalidBytesPerPixel)
+        ));
+        let rgba = PdfImage::new(&[1, 2, 3, 4, 5, 6, 7, 8], 2, 1, 4, 300).unwrap();
+        assert_eq!(rgba.data, vec![1, 2, 3, 5, 6, 7]);
+        assert!(rgba.color);
+    }
+
+    #[test]
+    fn test_text_layer_places_words_on_baseline() {
+        let image = sample_image();
+        let content = text_layer(&sample_page(), &Placement::new(&image, None).unwrap());
+        let content = String::from_utf8(content).unwrap();
+        // At 72 DPI a pixel is a point; the baseline at y = 28 is 12 points above the bottom.
+        assert!(content.starts_with("BT\n3 Tr\n/F0 20 Tf\n1 0 0 1 10 12 Tm\n"));
+        // "sample " is 7 glyphs of 10 points stretched over the 60 points to the next word.
+        assert!(content.contains("85.714 Tz\n<00730061006D0070006C00650020> Tj\n"));
+        // The last word has no trailing space and spans its own box.
+        assert!(content.contains("1 0 0 1 70 12 Tm\n100 Tz\n<0074006500780074> Tj\nET\n"));
+    }
+
+    #[test]
+    fn test_fixed_page_size_centers_image() {
+        let placement = Placement::new(&sample_image(), Some((240.0, 240.0))).unwrap();
+        assert_eq!(placement.scale, 2.0);
+        assert_eq!((placement.x, placement.y), (0.0, 80.0));
+        assert_eq!(placement.point(0.0, 0.0), (0.0, 160.0));
+    }
+
+    #[test]
+    fn test_invalid_page_size() {
+        for page_size in [
+            (0.0, 240.0),
+            (240.0, -1.0),
+            (f32::NAN, 240.0),
+            (240.0, f32::INFINITY),
+        ] {
+            assert!(matches!(
+                Placement::new(&sample_image(), Some(page_size)),
+                Err(TesseractError::InvalidParameterError)
+            ));
+        }
+
+        let mut writer = PdfWriter::new(
+            Vec::new(),
+            PdfOptions {
+                page_size: Some((0.0, 0.0)),
+                ..PdfOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            writer.add_page(&sample_image(), &sample_page()),
+            Err(TesseractError::InvalidParameterError)
+        ));
+    }
+
+    #[test]
+    fn test_new_document_structure() {
+        let file = write_document(uncompressed(), 2);
+        assert!(file.starts_with(b"%PDF-1.5"));
+        assert!(file.ends_with(b"%%EOF\n"));
+
+        let pages = page_dictionaries(&file);
+        assert_eq!(pages.len(), 2);
+        assert!(contains(&file, b"/MediaBox [0 0 120 40]"));
+        assert!(contains(&file, b"q 120 0 0 40 0 0 cm /Im0 Do Q"));
+        assert!(contains(
+            &file,
+            b"/Subtype /Image /Width 120 /Height 40 /ColorSpace /DeviceGray"
+        ));
+
+        // Every cross-reference entry points at its object.
+        let pdf = ExistingPdf::parse(&file).unwrap();
+        assert!(!pdf.xref_stream);
+        for number in 1..pdf.size() {
+            assert_ne!(pdf.object(number).unwrap(), Object::Null);
+        }
+    }
+
+    #[test]
+    fn test_compression_options() {
+        let compressed = write_document(PdfOptions::default(), 1);
+        assert!(!contains(&compressed, b"3 Tr"));
+        assert!(contains(&compressed, b"/Filter /FlateDecode"));
+        assert!(compressed.len() < write_document(uncompressed(), 1).len());
+
+        let text_only = write_document(
+            PdfOptions {
+                text_only: true,
+                ..uncompressed()
+            },
+            1,
+        );
+        assert!(!contains(&text_only, b"/Im0"));
+        assert!(contains(&text_only, b"3 Tr"));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_jpeg_compression() {
+        let file = write_document(
+            PdfOptions {
+                image_compression: PdfImageCompression::Jpeg { quality: 50 },
+                ..PdfOptions::default()
+            },
+            1,
+        );
+        assert!(contains(&file, b"/Filter /DCTDecode"));
+        assert!(contains(&file, b"stream\n\xff\xd8"));
+    }
+
+    #[test]
+    fn test_metadata() {
+        let file = write_document(
+            PdfOptions {
+                metadata: PdfMetadata {
+                    title: Some("Letter".to_owned()),
+                    author: Some("Zoë".to_owned()),
+                    creation_date: Some(UNIX_EPOCH + Duration::from_secs(951_782_400 + 3_661)),
+                    ..PdfMetadata::default()
+                },
+                ..uncompressed()
+            },
+            1,
+        );
+        let pdf = ExistingPdf::parse(&file).unwrap();
+        let info = pdf.resolve(pdf.trailer.get("Info").unwrap()).unwrap();
+        let info = info.as_dictionary().unwrap();
+        assert_eq!(info.get("Title"), Some(&Object::String(b"Letter".to_vec())));
+        assert_eq!(
+            info.get("Author"),
+            Some(&Object::String(vec![0xfe, 0xff, 0, b'Z', 0, b'o', 0, 0xeb]))
+        );
+        assert_eq!(
+            info.get("CreationDate"),
+            Some(&Object::String(b"D:20000229010101Z".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_pdf_date() {
+        assert_eq!(pdf_date(UNIX_EPOCH), "D:19700101000000Z");
+        assert_eq!(
+            pdf_date(UNIX_EPOCH + Duration::from_secs(1_704_067_199)),
+            "D:20231231235959Z"
+        );
+    }
+
+    #[test]
+    fn test_append_pages_to_existing_document() {
+        let original = write_document(
+            PdfOptions {
+                metadata: PdfMetadata {
+                    title: Some("Original".to_owned()),
+                    ..PdfMetadata::default()
+                },
+                ..uncompressed()
+            },
+            1,
+        );
+        let options = PdfOptions {
+            metadata: PdfMetadata {
+                author: Some("Scanner".to_owned()),
+                ..PdfMetadata::default()
+            },
+            ..PdfOptions::default()
+        };
+        let mut writer = PdfWriter::append(&original, Vec::new(), options).unwrap();
+        writer.add_page(&sample_image(), &sample_page()).unwrap();
+        writer.add_page(&sample_image(), &sample_page()).unwrap();
+        let updated = writer.finish().unwrap();
+
+        // The original bytes are kept as they are.
+        assert!(updated.starts_with(&original));
+        let pages = page_dictionaries(&updated);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[1].get("Parent"), pages[0].get("Parent"));
+
+        let pdf = ExistingPdf::parse(&updated).unwrap();
+        let previous = ExistingPdf::parse(&original).unwrap();
+        assert_eq!(
+            pdf.trailer.get("Prev"),
+            Some(&Object::Integer(previous.startxref as i64))
+        );
+        let info = pdf.resolve(pdf.trailer.get("Info").unwrap()).unwrap();
+        let info = info.as_dictionary().unwrap();
+        assert_eq!(
+            info.get("Title"),
+            Some(&Object::String(b"Original".to_vec()))
+        );
+        assert_eq!(
+            info.get("Author"),
+            Some(&Object::String(b"Scanner".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_append_twice_keeps_xref_stream_style() {
+        let original = write_document(uncompressed(), 1);
+        let pdf = ExistingPdf::parse(&original).unwrap();
+        let root = pdf.trailer.get("Root").unwrap().clone();
+        let size = pdf.size();
+
+        // Rewrite the original trailer as a cross-reference stream.
+        let table = original
+            .windows(4)
+            .rposition(|window| window == b"xref")
+            .unwrap();
+        let mut converted = original[..table].to_vec();
+        let mut entries = vec![0u8, 0, 0, 0, 0];
+        for number in 1..size {
+            let Some(offset) = (1..table)
+                .find(|&at| original[at..].starts_with(format!("\n{} 0 obj", number).as_bytes()))
+            else {
+                panic!("object {} not found", number);
+            };
+            entries.push(1);
+            entries.extend_from_slice(&(offset as u32 + 1).to_be_bytes());
+        }
+        entries.extend_from_slice(&[1]);
+        entries.extend_from_slice(&(table as u32).to_be_bytes());
+        let mut dictionary = Dictionary::new()
+            .with("Type", Object::name("XRef"))
+            .with("Size", Object::Integer(i64::from(size) + 1))
+            .with("Root", root)
+            .with(
+                "W",
+                Object::Array(vec![
+                    Object::Integer(1),
+                    Object::Integer(4),
+                    Object::Integer(0),
+                ]),
+            );
+        dictionary.set("Length", Object::Integer(entries.len() as i64));
+        converted.extend_from_slice(format!("{} 0 obj\n", size).as_bytes());
+        write_object(&mut converted, &Object::Dictionary(dictionary));
+        converted.extend_from_slice(b"\nstream\n");
+        converted.extend_from_slice(&entries);
+        converted.extend_from_slice(
+            format!("\nendstream\nendobj\nstartxref\n{}\n%%EOF\n", table).as_bytes(),
+        );
+        assert_eq!(page_dictionaries(&converted).len(), 1);
+
+        let mut writer = PdfWriter::append(&converted, Vec::new(), uncompressed()).unwrap();
+        writer.add_page(&sample_image(), &sample_page()).unwrap();
+        let updated = writer.finish().unwrap();
+        assert!(ExistingPdf::parse(&updated).unwrap().xref_stream);
+        assert_eq!(page_dictionaries(&updated).len(), 2);
+
+        let mut writer = PdfWriter::append(&updated, Vec::new(), uncompressed()).unwrap();
+        writer.add_page(&sample_image(), &sample_page()).unwrap();
+        assert_eq!(page_dictionaries(&writer.finish().unwrap()).len(), 3);
+    }
+
+    #[test]
+    fn test_append_rejects_encrypted_documents() {
+        let original = write_document(uncompressed(), 1);
+        let trailer = original
+            .windows(10)
+            .rposition(|window| window == b"trailer\n<<")
+            .unwrap();
+        let mut encrypted = original[..trailer + 10].to_vec();
+        encrypted.extend_from_slice(b"/Encrypt 99 0 R ");
+        encrypted.extend_from_slice(&original[trailer + 10..]);
+        assert!(matches!(
+            PdfWriter::append(&encrypted, Vec::new(), uncompressed()),
+            Err(TesseractError::ParseError { format: "PDF", .. })
+        ));
+    }
+}
@@ -0,0 +1,192 @@
+/// Units per em of the font, which PDF glyph widths are also given in.
+const UNITS_PER_EM: u16 = 1000;
+
+/// Advance width of the glyph, in font units.
+pub(crate) const GLYPH_WIDTH: u16 = 500;
+
+/// Maps every two-byte character code to the UTF-16 code unit with the same value.
+pub(crate) const TO_UNICODE_CMAP: &str = "/CIDInit /ProcSet findresource begin
+12 dict begin
+begincmap
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def
+/CMapName /Adobe-Identity-UCS def
+/CMapType 2 def
+1 begincodespacerange
+<0000> <FFFF>
+endcodespacerange
+1 beginbfrange
+<0000> <FFFF> <0000>
+endbfrange
+endcmap
+CMapName currentdict /CMap defineresource pop
+end
+end
+";
+
+/// Builds the `CIDToGIDMap` that maps every character code to glyph 1.
+pub(crate) fn cid_to_gid_map() -> Vec<u8> {
+    [0u8, 1].repeat(0x10000)
+}
+
+/// Builds the TrueType font of the invisible text layer written by `PdfWriter`.
+///
+/// Like Tesseract's `pdf.ttf`, the font has a single empty glyph of half an em, which every
+/// character code is mapped to. The text is never drawn, so only the glyph width matters:
+/// it lets the text be stretched over each word with horizontal scaling, while the
+/// `ToUnicode` map turns the character codes back into the recognized text.
+pub(crate) fn glyphless_font() -> Vec<u8> {
+    let glyphs = 2u16;
+
+    let mut head = Vec::new();
+    push32(&mut head, 0x0001_0000); // version
+    push32(&mut head, 0x0001_0000); // fontRevision
+    push32(&mut head, 0); // checkSumAdjustment, set once the font is assembled
+    push32(&mut head, 0x5F0F_3CF5); // magicNumber
+    push16(&mut head, 0b1011); // flags: baseline and left side bearing at 0, integer scaling
+    push16(&mut head, UNITS_PER_EM);
+    head.extend_from_slice(&[0; 16]); // created and modified
+    for bound in [0, 0, GLYPH_WIDTH, UNITS_PER_EM] {
+        push16(&mut head, bound); // xMin, yMin, xMax, yMax
+    }
+    push16(&mut head, 0); // macStyle
+    push16(&mut head, 3); // lowestRecPPEM
+    push16(&mut head, 2); // fontDirectionHint
+    push16(&mut head, 0); // indexToLocFormat: short offsets
+    push16(&mut head, 0); // glyphDataFormat
+
+    let mut hhea = Vec::new();
+    push32(&mut hhea, 0x0001_0000); // version
+    push16(&mut hhea, UNITS_PER_EM); // ascender
+    push16(&mut hhea, 0); // descender
+    push16(&mut hhea, 0); // lineGap
+    push16(&mut hhea, GLYPH_WIDTH); // advanceWidthMax
+    push16(&mut hhea, 0); // minLeftSideBearing
+    push16(&mut hhea, 0); // minRightSideBearing
+    push16(&mut hhea, 0); // xMaxExtent
+    push16(&mut hhea, 1); // caretSlopeRise
+    push16(&mut hhea, 0); // caretSlopeRun
+    hhea.extend_from_slice(&[0; 10]); // caretOffset and reserved
+    push16(&mut hhea, 0); // metricDataFormat
+    push16(&mut hhea, glyphs); // numberOfHMetrics
+
+    let mut maxp = Vec::new();
+    push32(&mut maxp, 0x0001_0000); // version
+    push16(&mut maxp, glyphs);
+    for value in [0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0] {
+        push16(&mut maxp, value); // outline, hinting and composite limits; maxZones is 2
+    }
+
+    let mut hmtx = Vec::new();
+    for _ in 0..glyphs {
+        push16(&mut hmtx, GLYPH_WIDTH);
+        push16(&mut hmtx, 0);
+    }
+
+    // Every glyph is empty, so all offsets into the glyph data are zero.
+    let loca = vec![0u8; 2 * (glyphs as usize + 1)];
+    let glyf = Vec::new();
+
+    // Table records must be sorted by tag.
+    let mut tables: [(&[u8; 4], Vec<u8>); 6] = [
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+
+    let count = tables.len() as u16;
+    let entry_selector = 15 - count.leading_zeros() as u16;
+    let search_range = 16 << entry_selector;
+    let mut font = Vec::new();
+    push32(&mut font, 0x0001_0000);
+    push16(&mut font, count);
+    push16(&mut font, search_range);
+    push16(&mut font, entry_selector);
+    push16(&mut font, count * 16 - search_range);
+
+    let mut offset = 12 + 16 * tables.len();
+    let mut body = Vec::new();
+    let mut head_offset = 0;
+    for (tag, data) in &mut tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        font.extend_from_slice(*tag);
+        push32(&mut font, checksum(data));
+        push32(&mut font, offset as u32);
+        push32(&mut font, data.len() as u32);
+        let padded = data.len().next_multiple_of(4);
+        data.resize(padded, 0);
+        body.extend_from_slice(data);
+        offset += padded;
+    }
+    font.extend_from_slice(&body);
+
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+    font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    font
+}
+
+fn push16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Sums the data as big-endian 32-bit words, padding the last one with zeros.
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read16(data: &[u8], at: usize) -> u16 {
+        u16::from_be_bytes([data[at], data[at + 1]])
+    }
+
+    fn read32(data: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+    }
+
+    #[test]
+    fn test_glyphless_font_tables() {
+        let font = glyphless_font();
+        assert_eq!(read32(&font, 0), 0x0001_0000);
+        assert_eq!(read16(&font, 4), 6);
+        assert_eq!(checksum(&font), 0xB1B0_AFBA);
+
+        let mut tags = Vec::new();
+        for record in font[12..12 + 6 * 16].chunks(16) {
+            let (offset, length) = (read32(record, 8) as usize, read32(record, 12) as usize);
+            assert_eq!(offset % 4, 0);
+            assert!(offset + length <= font.len());
+            if &record[..4] != b"head" {
+                assert_eq!(read32(record, 4), checksum(&font[offset..offset + length]));
+            }
+            if &record[..4] == b"hmtx" {
+                assert_eq!(read16(&font, offset), GLYPH_WIDTH);
+            }
+            tags.push(record[..4].to_vec());
+        }
+        let mut sorted = tags.clone();
+        sorted.sort();
+        assert_eq!(tags, sorted);
+    }
+
+    #[test]
+    fn test_cid_to_gid_map_targets_glyph_one() {
+        let map = cid_to_gid_map();
+        assert_eq!(map.len(), 2 * 0x10000);
+        assert!(map.chunks(2).all(|entry| entry == [0, 1]));
+    }
+}
@@ -0,0 +1,1078 @@
+use crate::error::{Result, TesseractError};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+
+/// A PDF object, as written by `PdfWriter` and as read from a document it appends to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Object {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Real(f64),
+    /// A name without its leading slash, kept in its written form (with `#xx` escapes).
+    Name(Vec<u8>),
+    /// The decoded bytes of a literal or hexadecimal string.
+    String(Vec<u8>),
+    Array(Vec<Object>),
+    Dictionary(Dictionary),
+    Reference(u32, u16),
+}
+
+impl Object {
+    pub(crate) fn name(name: &str) -> Self {
+        Object::Name(name.as_bytes().to_vec())
+    }
+
+    pub(crate) fn reference(number: u32) -> Self {
+        Object::Reference(number, 0)
+    }
+
+    pub(crate) fn as_integer(&self) -> Option<i64> {
+        match self {
+            Object::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_dictionary(&self) -> Option<&Dictionary> {
+        match self {
+            Object::Dictionary(dictionary) => Some(dictionary),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_name(&self, name: &str) -> bool {
+        matches!(self, Object::Name(value) if value == name.as_bytes())
+    }
+}
+
+/// A dictionary that keeps its entries in the order they were read or inserted.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Dictionary {
+    entries: Vec<(Vec<u8>, Object)>,
+}
+
+impl Dictionary {
+    pub(crate) fn new() -> Self {
+        Dictionary::default()
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&Object> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == key.as_bytes())
+            .map(|(_, value)| value)
+    }
+
+    /// Sets an entry, replacing an existing one with the same key in place.
+    pub(crate) fn set(&mut self, key: &str, value: Object) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|(name, _)| name == key.as_bytes())
+        {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key.as_bytes().to_vec(), value)),
+        }
+    }
+
+    pub(crate) fn with(mut self, key: &str, value: Object) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        self.entries.retain(|(name, _)| name != key.as_bytes());
+    }
+
+    fn push_raw(&mut self, key: Vec<u8>, value: Object) {
+        self.entries.retain(|(name, _)| *name != key);
+        self.entries.push((key, value));
+    }
+}
+
+/// Appends the written form of an object to `out`.
+pub(crate) fn write_object(out: &mut Vec<u8>, object: &Object) {
+    match object {
+        Object::Null => out.extend_from_slice(b"null"),
+        Object::Bool(value) => out.extend_from_slice(if *value { b"true" } else { b"false" }),
+        Object::Integer(value) => out.extend_from_slice(value.to_string().as_bytes()),
+        Object::Real(value) => out.extend_from_slice(format_number(*value).as_bytes()),
+        Object::Name(name) => {
+            out.push(b'/');
+            out.extend_from_slice(name);
+        }
+        Object::String(bytes) => write_string(out, bytes),
+        Object::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b' ');
+                }
+                write_object(out, item);
+            }
+            out.push(b']');
+        }
+        Object::Dictionary(dictionary) => {
+            out.extend_from_slice(b"<<");
+            for (i, (key, value)) in dictionary.entries.iter().enumerate() {
+                if i > 0 {
+                    out.push(b' ');
+                }
+                out.push(b'/');
+                out.extend_from_slice(key);
+                out.push(b' ');
+                write_object(out, value);
+            }
+            out.extend_from_slice(b">>");
+        }
+        Object::Reference(number, generation) => {
+            out.extend_from_slice(format!("{} {} R", number, generation).as_bytes());
+        }
+    }
+}
+
+/// Writes a literal string, escaping delimiters and bytes outside printable ASCII.
+fn write_string(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.push(b'(');
+    for &byte in bytes {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push(b'\\');
+                out.push(byte);
+            }
+            0x20..=0x7e => out.push(byte),
+            _ => out.extend_from_slice(format!("\\{:03o}", byte).as_bytes()),
+        }
+    }
+    out.push(b')');
+}
+
+/// Formats a number with at most three decimals and no trailing zeros.
+pub(crate) fn format_number(value: f64) -> String {
+    if !value.is_finite() {
+        return "0".to_owned();
+    }
+    let formatted = format!("{:.3}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" | "" => "0".to_owned(),
+        other => other.to_owned(),
+    }
+}
+
+/// Compresses stream data for the `FlateDecode` filter.
+pub(crate) fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // Writing to a `Vec` cannot fail.
+    let _ = encoder.write_all(data);
+    encoder.finish().unwrap_or_default()
+}
+
+/// Limit on the references followed to read one object of an existing document.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// Limit on how deeply arrays and dictionaries of an existing document may nest.
+const MAX_NESTING: usize = 256;
+
+fn parse_error(message: impl Into<String>) -> TesseractError {
+    TesseractError::ParseError {
+        format: "PDF",
+        message: message.into(),
+    }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b'\0' | b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+/// Reads objects from the bytes of a PDF file.
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Number of arrays and dictionaries the parser is inside.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a [u8], pos: usize) -> Self {
+        Parser {
+            data,
+            pos,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            if is_whitespace(byte) {
+                self.pos += 1;
+            } else if byte == b'%' {
+                while !matches!(self.peek(), None | Some(b'\r') | Some(b'\n')) {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Reads a run of regular characters, such as a number or a keyword.
+    fn token(&mut self) -> &'a [u8] {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(byte) = self.peek() {
+            if is_whitespace(byte) || is_delimiter(byte) {
+                break;
+            }
+            self.pos += 1;
+        }
+        &self.data[start..self.pos]
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<()> {
+        let token = self.token();
+        if token == keyword.as_bytes() {
+            Ok(())
+        } else {
+            Err(parse_error(format!(
+                "expected '{}' at offset {}, found '{}'",
+                keyword,
+                self.pos - token.len(),
+                String::from_utf8_lossy(token)
+            )))
+        }
+    }
+
+    fn unsigned(&mut self) -> Result<u64> {
+        let token = self.token();
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| parse_error(format!("expected a number at offset {}", self.pos)))
+    }
+
+    fn object(&mut self) -> Result<Object> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(parse_error("unexpected end of file")),
+            Some(b'/') => {
+                self.pos += 1;
+                let start = self.pos;
+                while let Some(byte) = self.peek() {
+                    if is_whitespace(byte) || is_delimiter(byte) {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Ok(Object::Name(self.data[start..self.pos].to_vec()))
+            }
+            Some(b'<') if self.data.get(self.pos + 1) == Some(&b'<') => self.nested(|parser| {
+                parser.pos += 2;
+                let mut dictionary = Dictionary::new();
+                loop {
+                    parser.skip_whitespace();
+                    if parser.data[parser.pos..].starts_with(b">>") {
+                        parser.pos += 2;
+                        return Ok(Object::Dictionary(dictionary));
+                    }
+                    match parser.object()? {
+                        Object::Name(key) => {
+                            let value = parser.object()?;
+                            dictionary.push_raw(key, value);
+                        }
+                        _ => return Err(parse_error("dictionary key is not a name")),
+                    }
+                }
+            }),
+            Some(b'<') => {
+                self.pos += 1;
+                let mut digits = Vec::new();
+                loop {
+                    match self.peek() {
+                        None => return Err(parse_error("unterminated hexadecimal string")),
+                        Some(b'>') => break,
+                        Some(byte) => {
+                            if let Some(digit) = (byte as char).to_digit(16) {
+                                digits.push(digit as u8);
+                            } else if !is_whitespace(byte) {
+                                return Err(parse_error("invalid hexadecimal string"));
+                            }
+                        }
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                // An odd final digit is followed by an implied zero.
+                Ok(Object::String(
+                    digits
+                        .chunks(2)
+                        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+                        .collect(),
+                ))
+            }
+            Some(b'(') => self.literal_string(),
+            Some(b'[') => self.nested(|parser| {
+                parser.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    parser.skip_whitespace();
+                    if parser.peek() == Some(b']') {
+                        parser.pos += 1;
+                        return Ok(Object::Array(items));
+                    }
+                    items.push(parser.object()?);
+                }
+            }),
+            Some(_) => self.number_or_keyword(),
+        }
+    }
+
+    /// Reads the contents of an array or dictionary, limiting how deeply they nest.
+    fn nested(&mut self, read: impl FnOnce(&mut Self) -> Result<Object>) -> Result<Object> {
+        if self.depth == MAX_NESTING {
+            return Err(parse_error("objects are nested too deeply"));
+        }
+        self.depth += 1;
+        let object = read(self);
+        self.depth -= 1;
+        object
+    }
+
+    fn literal_string(&mut self) -> Result<Object> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        let mut depth = 0;
+        loop {
+            let byte = self
+                .peek()
+                .ok_or_else(|| parse_error("unterminated string"))?;
+            self.pos += 1;
+            match byte {
+                b'(' => {
+                    depth += 1;
+                    bytes.push(byte);
+                }
+                b')' if depth == 0 => return Ok(Object::String(bytes)),
+                b')' => {
+                    depth -= 1;
+                    bytes.push(byte);
+                }
+                b'\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| parse_error("unterminated string"))?;
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => bytes.push(b'\n'),
+                        b'r' => bytes.push(b'\r'),
+                        b't' => bytes.push(b'\t'),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0c),
+                        b'0'..=b'7' => {
+                            let mut value = u32::from(escaped - b'0');
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(digit @ b'0'..=b'7') => {
+                                        value = value * 8 + u32::from(digit - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            bytes.push(value as u8);
+                        }
+                        // A backslash at the end of a line continues the string.
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => bytes.push(other),
+                    }
+                }
+                _ => bytes.push(byte),
+            }
+        }
+    }
+
+    fn number_or_keyword(&mut self) -> Result<Object> {
+        let start = self.pos;
+        let token = self.token();
+        match token {
+            b"true" => return Ok(Object::Bool(true)),
+            b"false" => return Ok(Object::Bool(false)),
+            b"null" => return Ok(Object::Null),
+            _ => {}
+        }
+        let text = std::str::from_utf8(token).unwrap_or("");
+        if let Ok(value) = text.parse::<i64>() {
+            // An integer may start an indirect reference `number generation R`.
+            if value >= 0 {
+                let after_number = self.pos;
+                if let Ok(generation) = std::str::from_utf8(self.token()).unwrap_or("").parse() {
+                    if self.token() == b"R" {
+                        return Ok(Object::Reference(value as u32, generation));
+                    }
+                }
+                self.pos = after_number;
+            }
+            return Ok(Object::Integer(value));
+        }
+        text.parse::<f64>().map(Object::Real).map_err(|_| {
+            parse_error(format!(
+                "unexpected '{}' at offset {}",
+                String::from_utf8_lossy(token),
+                start
+            ))
+        })
+    }
+}
+
+/// Where an object of an existing document is stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Location {
+    Offset(usize),
+    Compressed { stream: u32, index: usize },
+}
+
+/// The structure of an existing PDF file, read from its cross-reference sections.
+pub(crate) struct ExistingPdf<'a> {
+    data: &'a [u8],
+    locations: HashMap<u32, (Location, u16)>,
+    /// Trailer of the most recent update, or the dictionary of its cross-reference stream.
+    pub(crate) trailer: Dictionary,
+    /// Offset of the most recent cross-reference section.
+    pub(crate) startxref: usize,
+    /// Whether the most recent cross-reference section is a stream rather than a table.
+    pub(crate) xref_stream: bool,
+}
+
+impl<'a> ExistingPdf<'a> {
+    /// Reads the cross-reference sections of a file, from the last one through every
+    /// previous update.
+    pub(crate) fn parse(data: &'a [u8]) -> Result<Self> {
+        if !data.starts_with(b"%PDF-") {
+            return Err(parse_error("missing %PDF header"));
+        }
+        let marker = data
+            .windows(9)
+            .rposition(|window| window == b"startxref")
+            .ok_or_else(|| parse_error("missing startxref"))?;
+        let startxref = offset_from(Parser::new(data, marker + 9).unsigned()?)?;
+
+        let mut pdf = ExistingPdf {
+            data,
+            locations: HashMap::new(),
+            trailer: Dictionary::new(),
+            startxref,
+            xref_stream: false,
+        };
+        let mut visited = HashSet::new();
+        let mut next = Some(startxref);
+        while let Some(offset) = next {
+            if !visited.insert(offset) {
+                return Err(parse_error("cross-reference sections form a loop"));
+            }
+            let (trailer, is_stream) = pdf.read_section(offset)?;
+            if let Some(Object::Integer(stream)) = trailer.get("XRefStm") {
+                // A hybrid file lists its compressed objects in a separate stream.
+                pdf.read_section(offset_from(*stream)?)?;
+            }
+            next = match trailer.get("Prev") {
+                Some(Object::Integer(prev)) => Some(offset_from(*prev)?),
+                _ => None,
+            };
+            if visited.len() == 1 {
+                pdf.trailer = trailer;
+                pdf.xref_stream = is_stream;
+            }
+        }
+        if pdf.trailer.get("Encrypt").is_some() {
+            return Err(parse_error("encrypted documents are not supported"));
+        }
+        Ok(pdf)
+    }
+
+    /// Records the objects of one cross-reference section that newer sections did not
+    /// already list and returns its trailer.
+    fn read_section(&mut self, offset: usize) -> Result<(Dictionary, bool)> {
+        if offset >= self.data.len() {
+            return Err(parse_error(format!(
+                "cross-reference offset {} is past the end of the file",
+                offset
+            )));
+        }
+        let mut parser = Parser::new(self.data, offset);
+        parser.skip_whitespace();
+        if !self.data[parser.pos..].starts_with(b"xref") {
+            return self.read_xref_stream(offset).map(|trailer| (trailer, true));
+        }
+        parser.pos += 4;
+        loop {
+            parser.skip_whitespace();
+            if self.data[parser.pos..].starts_with(b"trailer") {
+                parser.pos += 7;
+                break;
+            }
+            let first = parser.unsigned()? as u32;
+            let count = parser.unsigned()? as u32;
+            for number in first..first.saturating_add(count) {
+                let position = offset_from(parser.unsigned()?)?;
+                let generation = parser.unsigned()? as u16;
+                let in_use = match parser.token() {
+                    b"n" => true,
+                    b"f" => false,
+                    _ => return Err(parse_error("invalid cross-reference entry")),
+                };
+                let location = if in_use {
+                    Some(Location::Offset(position))
+                } else {
+                    None
+                };
+                self.record(number, location, generation);
+            }
+        }
+        match parser.object()? {
+            Object::Dictionary(trailer) => Ok((trailer, false)),
+            _ => Err(parse_error("trailer is not a dictionary")),
+        }
+    }
+
+    fn read_xref_stream(&mut self, offset: usize) -> Result<Dictionary> {
+        let (dictionary, data) = match self.indirect_at(offset, MAX_REFERENCE_DEPTH)? {
+            (Object::Dictionary(dictionary), Some(data)) => (dictionary, data),
+            _ => return Err(parse_error("cross-reference section not found")),
+        };
+        if !dictionary.get("Type").is_some_and(|t| t.is_name("XRef")) {
+            return Err(parse_error("cross-reference section not found"));
+        }
+        let data = decode_stream(&dictionary, data)?;
+        let widths = integers(dictionary.get("W"))
+            .filter(|widths| widths.len() == 3 && widths.iter().all(|w| (0..=8).contains(w)))
+            .ok_or_else(|| parse_error("invalid cross-reference stream widths"))?;
+        let size = dictionary
+            .get("Size")
+            .and_then(Object::as_integer)
+            .ok_or_else(|| parse_error("cross-reference stream without /Size"))?;
+        let index = integers(dictionary.get("Index")).unwrap_or_else(|| vec![0, size]);
+
+        let entry_len: usize = widths.iter().map(|w| *w as usize).sum();
+        let mut entries = data.chunks_exact(entry_len.max(1));
+        for range in index.chunks_exact(2) {
+            let numbers = u32::try_from(range[0])
+                .ok()
+                .zip(u32::try_from(range[1]).ok())
+                .and_then(|(first, count)| Some(first..first.checked_add(count)?))
+                .ok_or_else(|| parse_error("invalid cross-reference stream index"))?;
+            for number in numbers {
+                let Some(entry) = entries.next() else {
+                    return Ok(dictionary);
+                };
+                let (kind, rest) = entry.split_at(widths[0] as usize);
+                let (second, third) = rest.split_at(widths[1] as usize);
+                // A missing type field defaults to an uncompressed object.
+                let kind = if kind.is_empty() { 1 } else { be_number(kind) };
+                let (second, third) = (be_number(second), be_number(third));
+                let (location, generation) = match kind {
+                    1 => (Some(Location::Offset(offset_from(second)?)), third as u16),
+                    2 => (
+                        Some(Location::Compressed {
+                            stream: u32::try_from(second)
+                                .map_err(|_| parse_error("invalid object stream number"))?,
+                            index: offset_from(third)?,
+                        }),
+                        0,
+                    ),
+                    _ => (None, 0),
+                };
+                self.record(number, location, generation);
+            }
+        }
+        Ok(dictionary)
+    }
+
+    fn record(&mut self, number: u32, location: Option<Location>, generation: u16) {
+        if self.locations.contains_key(&number) {
+            return;
+        }
+        // Free entries still hide older entries for the same number.
+        let location = location.unwrap_or(Location::Offset(0));
+        self.locations.insert(number, (location, generation));
+    }
+
+    /// Gets the object number one past the highest used, from the trailer.
+    pub(crate) fn size(&self) -> u32 {
+        let listed = self.locations.keys().max().map_or(0, |max| max + 1);
+        let size = self
+            .trailer
+            .get("Size")
+            .and_then(Object::as_integer)
+            .unwrap_or(0)
+            .max(0) as u32;
+        size.max(listed)
+    }
+
+    /// Gets the generation number of an object, as needed to replace it.
+    pub(crate) fn generation(&self, number: u32) -> u16 {
+        self.locations
+            .get(&number)
+            .map_or(0, |(_, generation)| *generation)
+    }
+
+    /// Reads an object, following references until a direct object is found.
+    pub(crate) fn resolve(&self, object: &Object) -> Result<Object> {
+        self.resolve_within(object, MAX_REFERENCE_DEPTH)
+    }
+
+    /// Reads the object with the given number, or `Null` if it is free or missing.
+    pub(crate) fn object(&self, number: u32) -> Result<Object> {
+        self.object_within(number, MAX_REFERENCE_DEPTH)
+    }
+
+    /// Follows references, counting each one read, including those read to find the length
+    /// of a stream, against `depth`.
+    fn resolve_within(&self, object: &Object, mut depth: usize) -> Result<Object> {
+        let mut object = object.clone();
+        while let Object::Reference(number, _) = object {
+            depth = depth
+                .checked_sub(1)
+                .ok_or_else(|| parse_error("references are nested too deeply"))?;
+            object = self.object_within(number, depth)?;
+        }
+        Ok(object)
+    }
+
+    fn object_within(&self, number: u32, depth: usize) -> Result<Object> {
+        match self.locations.get(&number) {
+            Some((Location::Offset(offset), _)) if *offset > 0 => {
+                Ok(self.indirect_at(*offset, depth)?.0)
+            }
+            Some((Location::Compressed { stream, index }, _)) => {
+                self.compressed_object(*stream, *index, depth)
+            }
+            _ => Ok(Object::Null),
+        }
+    }
+
+    /// Reads the indirect object at an offset and the raw data of its stream, if any.
+    fn indirect_at(&self, offset: usize, depth: usize) -> Result<(Object, Option<&'a [u8]>)> {
+        let mut parser = Parser::new(self.data, offset);
+        parser.unsigned()?;
+        parser.unsigned()?;
+        parser.keyword("obj")?;
+        let object = parser.object()?;
+        let Object::Dictionary(dictionary) = &object else {
+            return Ok((object, None));
+        };
+        if parser.token() != b"stream" {
+            return Ok((object, None));
+        }
+        // The keyword is followed by CRLF or LF; a lone CR is tolerated.
+        let mut start = parser.pos;
+        if self.data[start..].starts_with(b"\r\n") {
+            start += 2;
+        } else if matches!(self.data.get(start), Some(b'\n') | Some(b'\r')) {
+            start += 1;
+        }
+        let length = match dictionary.get("Length") {
+            Some(Object::Integer(length)) => Some(*length),
+            // The length of a cross-reference stream must be direct, so references can be
+            // followed here without recursing into the section being read.
+            Some(reference @ Object::Reference(..)) => {
+                self.resolve_within(reference, depth)?.as_integer()
+            }
+            _ => None,
+        };
+        let end = match length.map(offset_from).transpose()? {
+            Some(length)
+                if start
+                    .checked_add(length)
+                    .is_some_and(|end| end <= self.data.len())
+                    && Parser::new(self.data, start + length).token() == b"endstream" =>
+            {
+                start + length
+            }
+            // Recover from a missing or wrong length by searching for the end of the stream.
+            _ => {
+                let found = self.data[start..]
+                    .windows(9)
+                    .position(|window| window == b"endstream")
+                    .ok_or_else(|| parse_error("unterminated stream"))?;
+                let mut end = start + found;
+                while end > start && matches!(self.data[end - 1], b'\r' | b'\n') {
+                    end -= 1;
+                }
+                end
+            }
+        };
+        Ok((object, Some(&self.data[start..end])))
+    }
+
+    fn compressed_object(&self, stream: u32, index: usize, depth: usize) -> Result<Object> {
+        let offset = match self.locations.get(&stream) {
+            Some((Location::Offset(offset), _)) if *offset > 0 => *offset,
+            _ => return Err(parse_error(format!("object stream {} not found", stream))),
+        };
+        let (dictionary, data) = match self.indirect_at(offset, depth)? {
+            (Object::Dictionary(dictionary), Some(data)) => (dictionary, data),
+            _ => return Err(parse_error(format!("object {} is not a stream", stream))),
+        };
+        let data = decode_stream(&dictionary, data)?;
+        let count = dictionary
+            .get("N")
+            .and_then(Object::as_integer)
+            .unwrap_or(0);
+        let first = offset_from(
+            dictionary
+                .get("First")
+                .and_then(Object::as_integer)
+                .unwrap_or(0),
+        )?;
+        if index as i64 >= count {
+            return Err(parse_error(format!(
+                "object stream {} has no object {}",
+                stream, index
+            )));
+        }
+        let mut header = Parser::new(&data, 0);
+        let mut offset = 0;
+        for _ in 0..=index {
+            header.unsigned()?;
+            offset = offset_from(header.unsigned()?)?;
+        }
+        let position = first
+            .checked_add(offset)
+            .filter(|position| *position < data.len())
+            .ok_or_else(|| parse_error(format!("object stream {} is truncated", stream)))?;
+        Parser::new(&data, position).object()
+    }
+}
+
+/// Converts an offset, length or index read from the file, rejecting values that cannot be
+/// one.
+fn offset_from<T: TryInto<usize>>(value: T) -> Result<usize> {
+    value
+        .try_into()
+        .map_err(|_| parse_error("offset or length out of range"))
+}
+
+fn integers(object: Option<&Object>) -> Option<Vec<i64>> {
+    match object? {
+        Object::Array(items) => items.iter().map(Object::as_integer).collect(),
+        _ => None,
+    }
+}
+
+fn be_number(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |value, byte| value << 8 | u64::from(*byte))
+}
+
+/// Decodes the data of a stream that is uncompressed or uses `FlateDecode`.
+fn decode_stream(dictionary: &Dictionary, data: &[u8]) -> Result<Vec<u8>> {
+    let (filter, params) = match (dictionary.get("Filter"), dictionary.get("DecodeParms")) {
+        (None, _) => return Ok(data.to_vec()),
+        (Some(Object::Array(filters)), params) if filters.len() == 1 => {
+            let params = match params {
+                Some(Object::Array(params)) => params.first(),
+                params => params,
+            };
+            (&filters[0], params)
+        }
+        (Some(filter), params) => (filter, params),
+    };
+    if !filter.is_name("FlateDecode") {
+        return Err(parse_error("unsupported stream filter"));
+    }
+    let mut decoded = Vec::new();
+    ZlibDecoder::new(data)
+        .read_to_end(&mut decoded)
+        .map_err(|e| parse_error(format!("invalid compressed stream: {}", e)))?;
+    let params = params.and_then(Object::as_dictionary);
+    let param = |key: &str, default: i64| {
+        params
+            .and_then(|params| params.get(key))
+            .and_then(Object::as_integer)
+            .unwrap_or(default)
+    };
+    match param("Predictor", 1) {
+        1 => Ok(decoded),
+        predictor if predictor >= 10 => {
+            let colors = offset_from(param("Colors", 1).max(1))?;
+            let bits = offset_from(param("BitsPerComponent", 8).max(1))?;
+            let columns = offset_from(param("Columns", 1).max(1))?;
+            let invalid = || parse_error("invalid stream predictor parameters");
+            let pixel_bits = colors.checked_mul(bits).ok_or_else(invalid)?;
+            // A row longer than the whole stream cannot be valid.
+            let row_len = pixel_bits
+                .checked_mul(columns)
+                .map(|row_bits| row_bits.div_ceil(8))
+                .filter(|row_len| *row_len <= decoded.len())
+                .ok_or_else(invalid)?;
+            unpredict_png(&decoded, pixel_bits.div_ceil(8), row_len)
+        }
+        _ => Err(parse_error("unsupported stream predictor")),
+    }
+}
+
+/// Reverses the PNG row filters, each row of `row_len` bytes being preceded by its filter type.
+fn unpredict_png(data: &[u8], bytes_per_pixel: usize, row_len: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len());
+    let mut previous = vec![0u8; row_len];
+    for row in data.chunks(row_len + 1) {
+        let (filter, row) = row.split_first().unwrap_or((&0, &[]));
+        let mut current = row.to_vec();
+        current.resize(row_len, 0);
+        for i in 0..row_len {
+            let left = if i >= bytes_per_pixel {
+                current[i - bytes_per_pixel]
+            } else {
+                0
+            };
+            let up = previous[i];
+            let up_left = if i >= bytes_per_pixel {
+                previous[i - bytes_per_pixel]
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(parse_error("invalid PNG predictor row")),
+            };
+            current[i] = current[i].wrapping_add(predicted);
+        }
+        output.extend_from_slice(&current[..row.len().min(row_len)]);
+        previous = current;
+    }
+    Ok(output)
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let (a, b, c) = (
+        (estimate - i16::from(left)).abs(),
+        (estimate - i16::from(up)).abs(),
+        (estimate - i16::from(up_left)).abs(),
+    );
+    if a <= b && a <= c {
+        left
+    } else if b <= c {
+        up
+    } else {
+        up_left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &[u8]) -> Object {
+        Parser::new(text, 0).object().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_write_round_trip() {
+        let object = parse(b"<< /Type /Page /Kids [3 0 R 4 0 R] /Count 2 /Scale -1.5 /T (a\\(b\\)\\101) /H <48 65 6> /N null /B true >>");
+        let dictionary = object.as_dictionary().unwrap();
+        assert_eq!(
+            dictionary.get("Kids"),
+            Some(&Object::Array(vec![
+                Object::Reference(3, 0),
+                Object::Reference(4, 0)
+            ]))
+        );
+        assert_eq!(dictionary.get("Scale"), Some(&Object::Real(-1.5)));
+        assert_eq!(
+            dictionary.get("T"),
+            Some(&Object::String(b"a(b)A".to_vec()))
+        );
+        assert_eq!(dictionary.get("H"), Some(&Object::String(b"He`".to_vec())));
+
+        let mut written = Vec::new();
+        write_object(&mut written, &object);
+        assert_eq!(parse(&written), object);
+    }
+
+    #[test]
+    fn test_write_string_escapes() {
+        let mut written = Vec::new();
+        write_object(&mut written, &Object::String(b"(\\)\n\xfe".to_vec()));
+        assert_eq!(written, b"(\\(\\\\\\)\\012\\376)");
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(0.25), "0.25");
+        assert_eq!(format_number(-0.0001), "0");
+        assert_eq!(format_number(612.0004), "612");
+        assert_eq!(format_number(f64::NAN), "0");
+    }
+
+    #[test]
+    fn test_unpredict_png_rows() {
+        // One "Up" row below one "Sub" row, three bytes each.
+        let data = [1, 1, 1, 1, 2, 1, 1, 1];
+        assert_eq!(unpredict_png(&data, 1, 3).unwrap(), vec![1, 2, 3, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_parse_xref_stream_with_object_stream() {
+        let mut file = b"%PDF-1.5\n".to_vec();
+        let objects_offset = file.len();
+        let header = b"1 0 2 34 ";
+        let body = b"<< /Type /Catalog /Pages 2 0 R >> << /Type /Pages /Kids [] /Count 0 >>";
+        file.extend_from_slice(
+            format!(
+                "3 0 obj\n<< /Type /ObjStm /N 2 /First {} /Length {} >>\nstream\n",
+                header.len(),
+                header.len() + body.len()
+            )
+            .as_bytes(),
+        );
+        file.extend_from_slice(header);
+        file.extend_from_slice(body);
+        file.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let xref_offset = file.len();
+        let mut entries = vec![0, 0, 0, 0];
+        entries.extend_from_slice(&[2, 0, 3, 0]);
+        entries.extend_from_slice(&[2, 0, 3, 1]);
+        entries.extend_from_slice(&[1, 0, objects_offset as u8, 0]);
+        entries.extend_from_slice(&[1, 0, xref_offset as u8, 0]);
+        let compressed = deflate(&entries);
+        file.extend_from_slice(
+            format!(
+                "4 0 obj\n<< /Type /XRef /Size 5 /W [1 2 1] /Root 1 0 R /Filter /FlateDecode /Length {} >>\nstream\n",
+                compressed.len()
+            )
+            .as_bytes(),
+        );
+        file.extend_from_slice(&compressed);
+        file.extend_from_slice(
+            format!("\nendstream\nendobj\nstartxref\n{}\n%%EOF\n", xref_offset).as_bytes(),
+        );
+
+        let pdf = ExistingPdf::parse(&file).unwrap();
+        assert!(pdf.xref_stream);
+        assert_eq!(pdf.size(), 5);
+        let catalog = pdf.resolve(pdf.trailer.get("Root").unwrap()).unwrap();
+        let pages = pdf
+            .resolve(catalog.as_dictionary().unwrap().get("Pages").unwrap())
+            .unwrap();
+        assert_eq!(
+            pages.as_dictionary().unwrap().get("Count"),
+            Some(&Object::Integer(0))
+        );
+    }
+
+    /// Builds a file with a classic cross-reference table listing the objects as 1, 2, ...
+    fn classic_pdf(objects: &[&[u8]]) -> Vec<u8> {
+        let mut file = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (number, body) in objects.iter().enumerate() {
+            offsets.push(file.len());
+            file.extend_from_slice(format!("{} 0 obj\n", number + 1).as_bytes());
+            file.extend_from_slice(body);
+            file.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = file.len();
+        file.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            file.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        file.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        file
+    }
+
+    fn assert_parse_error<T>(result: Result<T>) {
+        match result {
+            Err(TesseractError::ParseError { format: "PDF", .. }) => {}
+            Err(e) => panic!("Expected a PDF ParseError, got {}", e),
+            Ok(_) => panic!("Expected a PDF ParseError"),
+        }
+    }
+
+    #[test]
+    fn test_stream_length_referring_to_itself() {
+        let file = classic_pdf(&[b"<< /Length 1 0 R >>\nstream\nabc\nendstream"]);
+        let pdf = ExistingPdf::parse(&file).unwrap();
+        assert_parse_error(pdf.object(1));
+    }
+
+    #[test]
+    fn test_negative_stream_length() {
+        let file = classic_pdf(&[b"<< /Length -5 >>\nstream\nabc\nendstream"]);
+        let pdf = ExistingPdf::parse(&file).unwrap();
+        assert_parse_error(pdf.object(1));
+    }
+
+    #[test]
+    fn test_overflowing_xref_stream_index() {
+        let mut file = b"%PDF-1.5\n".to_vec();
+        file.extend_from_slice(
+            b"1 0 obj\n<< /Type /XRef /Size 2 /W [1 1 1] /Index [9223372036854775807 1] /Length 3 >>\nstream\n\x01\x00\x00\nendstream\nendobj\nstartxref\n9\n%%EOF\n",
+        );
+        assert_parse_error(ExistingPdf::parse(&file));
+    }
+
+    #[test]
+    fn test_oversized_predictor_row() {
+        let dictionary = Dictionary::new()
+            .with("Filter", Object::name("FlateDecode"))
+            .with(
+                "DecodeParms",
+                Object::Dictionary(
+                    Dictionary::new()
+                        .with("Predictor", Object::Integer(12))
+                        .with("Columns", Object::Integer(i64::MAX)),
+                ),
+            );
+        assert_parse_error(decode_stream(&dictionary, &deflate(&[2, 1, 2, 3])));
+    }
+
+    #[test]
+    fn test_deeply_nested_arrays() {
+        let text = "[".repeat(100_000);
+        assert_parse_error(Parser::new(text.as_bytes(), 0).object());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_pdf() {
+        assert!(matches!(
+            ExistingPdf::parse(b"GIF89a"),
+            Err(TesseractError::ParseError { format: "PDF", .. })
+        ));
+    }
+}
@@ -0,0 +1,585 @@
+use crate::error::{Result, TesseractError};
+use std::os::raw::{c_int, c_void};
+#[cfg(feature = "image")]
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Leptonica `REMOVE_CMAP_BASED_ON_SRC`: picks gray or color output depending on the colormap.
+const REMOVE_CMAP_BASED_ON_SRC: c_int = 4;
+
+/// Owned Leptonica image.
+///
+/// The underlying `PIX` is reference counted by Leptonica; dropping a `Pix` releases this
+/// instance's reference with `pixDestroy`.
+pub struct Pix {
+    pub(crate) handle: Arc<Mutex<*mut c_void>>,
+}
+
+unsafe impl Send for Pix {}
+unsafe impl Sync for Pix {}
+
+impl Pix {
+    /// Creates a new, zero-filled image.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    /// * `depth` - Bits per pixel (1, 2, 4, 8, 16 or 32).
+    ///
+    /// # Returns
+    ///
+    /// Returns the new `Pix` if successful, otherwise returns an error.
+    pub fn new(width: i32, height: i32, depth: i32) -> Result<Self> {
+        if width <= 0 || height <= 0 {
+            return Err(TesseractError::InvalidDimensions);
+        }
+        if ![1, 2, 4, 8, 16, 32].contains(&depth) {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        unsafe { Self::from_raw(pixCreate(width, height, depth)) }
+    }
+
+    /// Takes ownership of a raw Leptonica `PIX` pointer.
+    ///
+    /// # Safety
+    ///
+    /// `pix` must be null or point to a valid Leptonica `PIX` holding a reference that the
+    /// caller owns and gives up. The returned `Pix` reads the image through it and releases
+    /// the reference with `pixDestroy` when dropped, so the pointer must not be dangling,
+    /// borrowed or destroyed by anyone else.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - Pointer to the image. The caller gives up its reference.
+    ///
+    /// # Returns
+    ///
+    /// Returns the wrapped `Pix`, or an error if the pointer is null.
+    pub unsafe fn from_raw(pix: *mut c_void) -> Result<Self> {
+        if pix.is_null() {
+            return Err(TesseractError::NullPointerError);
+        }
+        Ok(Pix {
+            handle: Arc::new(Mutex::new(pix)),
+        })
+    }
+
+    /// Creates a new reference to a `PIX` owned by someone else.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - Pointer to the image. The caller keeps its own reference.
+    pub(crate) fn from_borrowed(pix: *mut c_void) -> Result<Self> {
+        if pix.is_null() {
+            return Err(TesseractError::NullPointerError);
+        }
+        unsafe { Self::from_raw(pixClone(pix)) }
+    }
+
+    /// Creates an image from a raw pixel buffer.
+    ///
+    /// Grayscale input produces an 8 bpp image; RGB and RGBA input produce a 32 bpp image.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Raw image data.
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    /// * `bytes_per_pixel` - Number of bytes per pixel (1 for grayscale, 3 for RGB, 4 for RGBA).
+    /// * `bytes_per_line` - Number of bytes per line (usually width * bytes_per_pixel, but might be padded).
+    ///
+    /// # Returns
+    ///
+    /// Returns the new `Pix` if successful, otherwise returns an error.
+    pub fn from_bytes(
+        data: &[u8],
+        width: i32,
+        height: i32,
+        bytes_per_pixel: i32,
+        bytes_per_line: i32,
+    ) -> Result<Self> {
+        if width <= 0 || height <= 0 {
+            return Err(TesseractError::InvalidDimensions);
+        }
+        let depth = match bytes_per_pixel {
+            1 => 8,
+            3 | 4 => 32,
+            _ => return Err(TesseractError::InvalidBytesPerPixel),
+        };
+        let row_bytes = width
+            .checked_mul(bytes_per_pixel)
+            .ok_or(TesseractError::InvalidDimensions)?;
+        if bytes_per_line < row_bytes {
+            return Err(TesseractError::InvalidBytesPerLine);
+        }
+        let len = (height as usize)
+            .checked_mul(bytes_per_line as usize)
+            .ok_or(TesseractError::InvalidImageData)?;
+        if data.len() < len {
+            return Err(TesseractError::InvalidImageData);
+        }
+
+        let pix = Self::new(width, height, depth)?;
+        {
+            let handle = pix
+                .handle
+                .lock()
+                .map_err(|_| TesseractError::MutexLockError)?;
+            let wpl = unsafe { pixGetWpl(*handle) } as usize;
+            let words = unsafe {
+                std::slice::from_raw_parts_mut(pixGetData(*handle), wpl * height as usize)
+            };
+            pack_pixels(
+                data,
+                width as usize,
+                height as usize,
+                bytes_per_pixel as usize,
+                bytes_per_line as usize,
+                words,
+                wpl,
+            );
+            if bytes_per_pixel != 1 {
+                unsafe { pixSetSpp(*handle, bytes_per_pixel) };
+            }
+        }
+        Ok(pix)
+    }
+
+    /// Gets the width of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the width in pixels if successful, otherwise returns an error.
+    pub fn width(&self) -> Result<i32> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { pixGetWidth(*handle) })
+    }
+
+    /// Gets the height of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the height in pixels if successful, otherwise returns an error.
+    pub fn height(&self) -> Result<i32> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { pixGetHeight(*handle) })
+    }
+
+    /// Gets the depth of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the depth in bits per pixel if successful, otherwise returns an error.
+    pub fn depth(&self) -> Result<i32> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { pixGetDepth(*handle) })
+    }
+
+    /// Gets the resolution of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the horizontal and vertical resolution in pixels per inch (0 if unknown) if
+    /// successful, otherwise returns an error.
+    pub fn resolution(&self) -> Result<(i32, i32)> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { (pixGetXRes(*handle), pixGetYRes(*handle)) })
+    }
+
+    /// Sets the resolution of the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_res` - Horizontal resolution in pixels per inch.
+    /// * `y_res` - Vertical resolution in pixels per inch.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if successful, otherwise returns an error.
+    pub fn set_resolution(&self, x_res: i32, y_res: i32) -> Result<()> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        unsafe { pixSetResolution(*handle, x_res, y_res) };
+        Ok(())
+    }
+
+    /// Gets the number of 32-bit words per image line.
+    ///
+    /// # Returns
+    ///
+    /// Returns the words per line if successful, otherwise returns an error.
+    pub fn words_per_line(&self) -> Result<i32> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok(unsafe { pixGetWpl(*handle) })
+    }
+
+    /// Gets a copy of the raw pixel data in Leptonica's native layout.
+    ///
+    /// Each line occupies `words_per_line()` 32-bit words, with the leftmost pixel in the
+    /// most significant bits.
+    ///
+    /// # Returns
+    ///
+    /// Returns the raw pixel words if successful, otherwise returns an error.
+    pub fn data(&self) -> Result<Vec<u32>> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        let len = unsafe { pixGetWpl(*handle) * pixGetHeight(*handle) } as usize;
+        Ok(unsafe { std::slice::from_raw_parts(pixGetData(*handle), len) }.to_vec())
+    }
+
+    /// Converts the image to a packed pixel buffer.
+    ///
+    /// Images of 16 bpp or less are converted to 8-bit grayscale, 32 bpp images are returned
+    /// as RGB or RGBA depending on their samples per pixel. Colormapped images are resolved first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of (data, bytes_per_pixel) where lines are `width * bytes_per_pixel` bytes long.
+    pub fn to_bytes(&self) -> Result<(Vec<u8>, i32)> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+
+        // Normalize to a depth we can unpack, keeping our own reference either way.
+        let mut converted = unsafe { pixClone(*handle) };
+        if !unsafe { pixGetColormap(converted) }.is_null() {
+            let without_cmap = unsafe { pixRemoveColormap(converted, REMOVE_CMAP_BASED_ON_SRC) };
+            unsafe { pixDestroy(&mut converted) };
+            converted = without_cmap;
+        }
+        if !converted.is_null() && !matches!(unsafe { pixGetDepth(converted) }, 8 | 32) {
+            let gray = unsafe { pixConvertTo8(converted, 0) };
+            unsafe { pixDestroy(&mut converted) };
+            converted = gray;
+        }
+        let converted = unsafe { Pix::from_raw(converted) }?;
+        let converted_handle = converted
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+
+        let pix = *converted_handle;
+        let (width, height) = unsafe { (pixGetWidth(pix), pixGetHeight(pix)) };
+        let bytes_per_pixel = match unsafe { (pixGetDepth(pix), pixGetSpp(pix)) } {
+            (8, _) => 1,
+            (32, 4) => 4,
+            (32, _) => 3,
+            _ => return Err(TesseractError::InvalidImageData),
+        };
+        let wpl = unsafe { pixGetWpl(pix) } as usize;
+        let words = unsafe { std::slice::from_raw_parts(pixGetData(pix), wpl * height as usize) };
+        let data = unpack_pixels(words, wpl, width as usize, height as usize, bytes_per_pixel);
+        Ok((data, bytes_per_pixel as i32))
+    }
+}
+
+#[cfg(feature = "image")]
+impl Pix {
+    /// Reads an image file (PNG, JPEG, TIFF, BMP or PNM).
+    ///
+    /// The format is detected from the file contents rather than its extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the image file.
+    ///
+    /// # Returns
+    ///
+    /// Returns the decoded `Pix` if successful, otherwise returns an error.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file_error = |e: std::io::Error| TesseractError::FileError {
+            path: path.display().to_string(),
+            output: e.to_string(),
+        };
+        let image = image::ImageReader::open(path)
+            .map_err(file_error)?
+            .with_guessed_format()
+            .map_err(file_error)?
+            .decode()
+            .map_err(|e| TesseractError::ImageDecodeError(e.to_string()))?;
+        Self::from_dynamic_image(&image)
+    }
+
+    /// Decodes an encoded image (PNG, JPEG, TIFF, BMP or PNM) held in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Encoded image data.
+    ///
+    /// # Returns
+    ///
+    /// Returns the decoded `Pix` if successful, otherwise returns an error.
+    pub fn read_from_memory(bytes: &[u8]) -> Result<Self> {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| TesseractError::ImageDecodeError(e.to_string()))?;
+        Self::from_dynamic_image(&image)
+    }
+
+    /// Converts a decoded `image` crate image.
+    ///
+    /// Grayscale images become 8 bpp, everything else becomes 32 bpp RGB.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The decoded image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted `Pix` if successful, otherwise returns an error.
+    pub fn from_dynamic_image(image: &image::DynamicImage) -> Result<Self> {
+        let (data, width, height, bytes_per_pixel) = dynamic_image_to_bytes(image);
+        Self::from_bytes(
+            &data,
+            width as i32,
+            height as i32,
+            bytes_per_pixel as i32,
+            (width * bytes_per_pixel) as i32,
+        )
+    }
+}
+
+impl Drop for Pix {
+    fn drop(&mut self) {
+        if let Ok(mut handle) = self.handle.lock() {
+            unsafe { pixDestroy(&mut *handle) };
+        }
+    }
+}
+
+/// Flattens a decoded image into (data, width, height, bytes_per_pixel).
+///
+/// Transparent pixels are composited onto white, so text on a transparent background stays
+/// readable instead of turning black.
+#[cfg(feature = "image")]
+pub(crate) fn dynamic_image_to_bytes(image: &image::DynamicImage) -> (Vec<u8>, u32, u32, u32) {
+    let (width, height) = (image.width(), image.height());
+    let color = image.color();
+    match (color.has_color(), color.has_alpha()) {
+        (true, true) => {
+            let data = image
+                .to_rgba8()
+                .pixels()
+                .flat_map(|pixel| {
+                    let [r, g, b, alpha] = pixel.0;
+                    [r, g, b].map(|value| over_white(value, alpha))
+                })
+                .collect();
+            (data, width, height, 3)
+        }
+        (true, false) => (image.to_rgb8().into_raw(), width, height, 3),
+        (false, true) => {
+            let data = image
+                .to_luma_alpha8()
+                .pixels()
+                .map(|pixel| over_white(pixel.0[0], pixel.0[1]))
+                .collect();
+            (data, width, height, 1)
+        }
+        (false, false) => (image.to_luma8().into_raw(), width, height, 1),
+    }
+}
+
+/// Composites a channel value with the given alpha onto a white background.
+#[cfg(feature = "image")]
+fn over_white(value: u8, alpha: u8) -> u8 {
+    let (value, alpha) = (u32::from(value), u32::from(alpha));
+    ((value * alpha + 255 * (255 - alpha) + 127) / 255) as u8
+}
+
+/// Packs a byte buffer into Leptonica's word layout (8 bpp gray or 32 bpp RGBA).
+fn pack_pixels(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+    bytes_per_line: usize,
+    words: &mut [u32],
+    wpl: usize,
+) {
+    for y in 0..height {
+        let src = &data[y * bytes_per_line..];
+        let dst = &mut words[y * wpl..(y + 1) * wpl];
+        for x in 0..width {
+            let px = &src[x * bytes_per_pixel..(x + 1) * bytes_per_pixel];
+            match bytes_per_pixel {
+                1 => {
+                    let shift = 24 - 8 * (x % 4);
+                    dst[x / 4] |= (px[0] as u32) << shift;
+                }
+                _ => {
+                    let alpha = if bytes_per_pixel == 4 { px[3] } else { 255 };
+                    dst[x] = u32::from_be_bytes([px[0], px[1], px[2], alpha]);
+                }
+            }
+        }
+    }
+}
+
+/// Unpacks Leptonica's word layout (8 bpp gray or 32 bpp RGBA) into a byte buffer.
+fn unpack_pixels(
+    words: &[u32],
+    wpl: usize,
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(width * height * bytes_per_pixel);
+    for y in 0..height {
+        let line = &words[y * wpl..(y + 1) * wpl];
+        for x in 0..width {
+            match bytes_per_pixel {
+                1 => data.push((line[x / 4] >> (24 - 8 * (x % 4))) as u8),
+                _ => data.extend_from_slice(&line[x].to_be_bytes()[..bytes_per_pixel]),
+            }
+        }
+    }
+    data
+}
+
+extern "C" {
+    pub fn pixCreate(width: c_int, height: c_int, depth: c_int) -> *mut c_void;
+    pub fn pixClone(pixs: *mut c_void) -> *mut c_void;
+    pub fn pixDestroy(ppix: *mut *mut c_void);
+    pub fn pixGetWidth(pix: *const c_void) -> c_int;
+    pub fn pixGetHeight(pix: *const c_void) -> c_int;
+    pub fn pixGetDepth(pix: *const c_void) -> c_int;
+    pub fn pixGetSpp(pix: *const c_void) -> c_int;
+    pub fn pixSetSpp(pix: *mut c_void, spp: c_int) -> c_int;
+    pub fn pixGetWpl(pix: *const c_void) -> c_int;
+    pub fn pixGetXRes(pix: *const c_void) -> c_int;
+    pub fn pixGetYRes(pix: *const c_void) -> c_int;
+    pub fn pixSetResolution(pix: *mut c_void, xres: c_int, yres: c_int) -> c_int;
+    pub fn pixGetData(pix: *const c_void) -> *mut u32;
+    pub fn pixGetColormap(pix: *const c_void) -> *mut c_void;
+    pub fn pixRemoveColormap(pixs: *mut c_void, type_: c_int) -> *mut c_void;
+    pub fn pixConvertTo8(pixs: *mut c_void, cmapflag: c_int) -> *mut c_void;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_rejects_overflowing_dimensions() {
+        // 0x4000_0001 * 4 wraps around to 4 in 32 bits.
+        assert!(matches!(
+            Pix::from_bytes(&[0; 16], 0x4000_0001, 1, 4, 4),
+            Err(TesseractError::InvalidDimensions)
+        ));
+        assert!(matches!(
+            Pix::from_bytes(&[0; 16], 4, i32::MAX, 1, i32::MAX),
+            Err(TesseractError::InvalidImageData)
+        ));
+    }
+
+    #[test]
+    fn test_pack_unpack_gray_roundtrip() {
+        // 5 pixels wide with 2 bytes of row padding; needs 2 words per line.
+        let data = [
+            0u8, 10, 20, 30, 40, 0xAA, 0xAA, //
+            50, 60, 70, 80, 90, 0xAA, 0xAA,
+        ];
+        let mut words = vec![0u32; 4];
+        pack_pixels(&data, 5, 2, 1, 7, &mut words, 2);
+        assert_eq!(words[0], 0x000A141E);
+        assert_eq!(words[1], 0x28000000);
+
+        let unpacked = unpack_pixels(&words, 2, 5, 2, 1);
+        assert_eq!(unpacked, vec![0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+    }
+
+    #[test]
+    fn test_pack_unpack_rgb_roundtrip() {
+        let data = [1u8, 2, 3, 4, 5, 6];
+        let mut words = vec![0u32; 2];
+        pack_pixels(&data, 2, 1, 3, 6, &mut words, 2);
+        assert_eq!(words, vec![0x010203FF, 0x040506FF]);
+        assert_eq!(unpack_pixels(&words, 2, 2, 1, 3), data.to_vec());
+    }
+
+    #[test]
+    fn test_pack_unpack_rgba_roundtrip() {
+        let data = [9u8, 8, 7, 6];
+        let mut words = vec![0u32; 1];
+        pack_pixels(&data, 1, 1, 4, 4, &mut words, 1);
+        assert_eq!(words[0], 0x09080706);
+        assert_eq!(unpack_pixels(&words, 1, 1, 1, 4), data.to_vec());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_dynamic_image_to_bytes_keeps_gray_single_channel() {
+        let gray = image::DynamicImage::ImageLuma16(image::ImageBuffer::from_pixel(
+            3,
+            2,
+            image::Luma([0xFFFFu16]),
+        ));
+        let (data, width, height, bytes_per_pixel) = dynamic_image_to_bytes(&gray);
+        assert_eq!((width, height, bytes_per_pixel), (3, 2, 1));
+        assert_eq!(data, vec![255; 6]);
+
+        let rgba = image::DynamicImage::ImageRgba8(image::ImageBuffer::from_pixel(
+            2,
+            1,
+            image::Rgba([1u8, 2, 3, 255]),
+        ));
+        let (data, _, _, bytes_per_pixel) = dynamic_image_to_bytes(&rgba);
+        assert_eq!(bytes_per_pixel, 3);
+        assert_eq!(data, vec![1, 2, 3, 1, 2, 3]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_dynamic_image_to_bytes_composites_transparent_png_onto_white() {
+        // Black text pixels on a fully transparent black background, plus one half-covered
+        // pixel, as anti-aliased text exported with transparency usually is.
+        let mut rgba = image::RgbaImage::from_pixel(3, 1, image::Rgba([0, 0, 0, 0]));
+        rgba.put_pixel(1, 0, image::Rgba([0, 0, 0, 255]));
+        rgba.put_pixel(2, 0, image::Rgba([0, 0, 0, 128]));
+        let mut png = std::io::Cursor::new(Vec::new());
+        rgba.write_to(&mut png, image::ImageFormat::Png)
+            .expect("Failed to encode PNG");
+        let decoded =
+            image::load_from_memory(png.get_ref()).expect("Failed to decode transparent PNG");
+
+        let (data, width, height, bytes_per_pixel) = dynamic_image_to_bytes(&decoded);
+        assert_eq!((width, height, bytes_per_pixel), (3, 1, 3));
+        assert_eq!(data, vec![255, 255, 255, 0, 0, 0, 127, 127, 127]);
+
+        let gray_alpha = image::DynamicImage::ImageLumaA8(image::ImageBuffer::from_pixel(
+            1,
+            1,
+            image::LumaA([0u8, 0]),
+        ));
+        assert_eq!(dynamic_image_to_bytes(&gray_alpha), (vec![255], 1, 1, 1));
+    }
+
+    #[test]
+    fn test_pix_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Pix>();
+    }
+}
@@ -0,0 +1,281 @@
+use crate::api::TesseractConfiguration;
+use crate::error::{Result, TesseractError};
+use crate::page::Page;
+use crate::pix::Pix;
+use crate::{TesseractAPI, TesseractBuilder};
+use std::borrow::Borrow;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+
+/// A fixed-size pool of engines sharing one configuration.
+///
+/// Engines are created lazily, the first time a checkout finds no idle engine and the pool is
+/// below its size. When every engine is checked out, `get` waits for one to be returned.
+pub struct TesseractPool {
+    config: TesseractConfiguration,
+    size: usize,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+struct PoolState {
+    idle: Vec<TesseractAPI>,
+    created: usize,
+}
+
+impl TesseractPool {
+    /// Creates a pool from an initialized engine.
+    ///
+    /// The engine becomes the first member of the pool; the others are initialized with the
+    /// same datapath, language and variables when they are first needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - An initialized engine used as the template.
+    /// * `size` - Maximum number of engines in the pool.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new pool if successful, otherwise returns an error.
+    pub fn new(api: TesseractAPI, size: usize) -> Result<Self> {
+        if size == 0 {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        let config = api.configuration()?;
+        if !config.is_initialized() {
+            return Err(TesseractError::UninitializedError);
+        }
+        Ok(TesseractPool {
+            config,
+            size,
+            state: Mutex::new(PoolState {
+                idle: vec![api],
+                created: 1,
+            }),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Creates a pool whose engines are initialized by a builder.
+    ///
+    /// The first engine is initialized immediately so that configuration errors surface here.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The initialization settings shared by all engines.
+    /// * `size` - Maximum number of engines in the pool.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new pool if successful, otherwise returns an error.
+    pub fn from_builder(builder: &TesseractBuilder, size: usize) -> Result<Self> {
+        if size == 0 {
+            return Err(TesseractError::InvalidParameterError);
+        }
+        Self::new(builder.build()?, size)
+    }
+
+    /// Creates a pool with one engine per available CPU core.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - An initialized engine used as the template.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new pool if successful, otherwise returns an error.
+    pub fn with_available_parallelism(api: TesseractAPI) -> Result<Self> {
+        let size = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::new(api, size)
+    }
+
+    /// Gets the maximum number of engines in the pool.
+    ///
+    /// # Returns
+    ///
+    /// Returns the pool size.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Checks an engine out of the pool, waiting if all engines are in use.
+    ///
+    /// The engine is returned to the pool when the guard is dropped.
+    ///
+    /// # Returns
+    ///
+    /// Returns a guard for the engine, or an error if a new engine failed to initialize.
+    pub fn get(&self) -> Result<PooledEngine<'_>> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        loop {
+            if let Some(api) = state.idle.pop() {
+                return Ok(self.guard(api));
+            }
+            if state.created < self.size {
+                break;
+            }
+            state = self
+                .available
+                .wait(state)
+                .map_err(|_| TesseractError::MutexLockError)?;
+        }
+        state.created += 1;
+        drop(state);
+        self.create()
+    }
+
+    /// Checks an engine out of the pool without waiting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` if all engines are in use, otherwise the same as `get`.
+    pub fn try_get(&self) -> Result<Option<PooledEngine<'_>>> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        if let Some(api) = state.idle.pop() {
+            return Ok(Some(self.guard(api)));
+        }
+        if state.created >= self.size {
+            return Ok(None);
+        }
+        state.created += 1;
+        drop(state);
+        self.create().map(Some)
+    }
+
+    /// Recognizes a set of page images in parallel.
+    ///
+    /// One thread per pool engine takes pages in turn until all pages are recognized.
+    ///
+    /// # Arguments
+    ///
+    /// * `images` - The page images.
+    ///
+    /// # Returns
+    ///
+    /// Returns the result for each page, in the order of `images`.
+    pub fn map_pages<I>(&self, images: I) -> Vec<Result<Page>>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Pix> + Sync,
+    {
+        let images: Vec<I::Item> = images.into_iter().collect();
+        let results: Vec<Mutex<Option<Result<Page>>>> =
+            images.iter().map(|_| Mutex::new(None)).collect();
+        let next = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.size.min(images.len()) {
+                scope.spawn(|| {
+                    let mut engine: Option<PooledEngine<'_>> = None;
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= images.len() {
+                            break;
+                        }
+                        let image = images[index].borrow();
+                        let result = match engine.as_ref() {
+                            Some(engine) => recognize(engine, image),
+                            None => self.get().and_then(|checked_out| {
+                                let result = recognize(&checked_out, image);
+                                engine = Some(checked_out);
+                                result
+                            }),
+                        };
+                        if let Ok(mut slot) = results[index].lock() {
+                            *slot = Some(result);
+                        }
+                    }
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|slot| {
+                slot.into_inner()
+                    .ok()
+                    .flatten()
+                    .unwrap_or(Err(TesseractError::MutexLockError))
+            })
+            .collect()
+    }
+
+    fn create(&self) -> Result<PooledEngine<'_>> {
+        match TesseractAPI::from_configuration(&self.config) {
+            Ok(api) => Ok(self.guard(api)),
+            Err(e) => {
+                // Give the slot back so a later checkout can retry.
+                if let Ok(mut state) = self.state.lock() {
+                    state.created -= 1;
+                }
+                self.available.notify_one();
+                Err(e)
+            }
+        }
+    }
+
+    fn guard(&self, api: TesseractAPI) -> PooledEngine<'_> {
+        PooledEngine {
+            pool: self,
+            api: Some(api),
+        }
+    }
+
+    fn release(&self, api: TesseractAPI) {
+        let _ = api.clear();
+        let reusable = api
+            .configuration()
+            .is_ok_and(|config| config.same_init(&self.config))
+            && api.restore_configuration(&self.config).is_ok();
+        if let Ok(mut state) = self.state.lock() {
+            if reusable {
+                state.idle.push(api);
+            } else {
+                // Drop an engine that was re-initialized or whose variables could not be
+                // reset; a later checkout creates a new one.
+                state.created -= 1;
+            }
+        }
+        self.available.notify_one();
+    }
+}
+
+fn recognize(api: &TesseractAPI, pix: &Pix) -> Result<Page> {
+    api.set_image_2(pix)?;
+    api.recognize_page()
+}
+
+/// An engine checked out of a `TesseractPool`.
+///
+/// Dereferences to `TesseractAPI`. When the engine is returned, its image and results are
+/// cleared and variables set through the guard go back to the pool's values, or to their
+/// defaults if the pool does not set them. Config files read through the guard and debug
+/// variables are not undone. An engine that was re-initialized or ended through the guard
+/// is dropped instead, and a later checkout creates a new one.
+pub struct PooledEngine<'pool> {
+    pool: &'pool TesseractPool,
+    api: Option<TesseractAPI>,
+}
+
+impl Deref for PooledEngine<'_> {
+    type Target = TesseractAPI;
+
+    fn deref(&self) -> &TesseractAPI {
+        self.api.as_ref().expect("engine is present until drop")
+    }
+}
+
+impl Drop for PooledEngine<'_> {
+    fn drop(&mut self) {
+        if let Some(api) = self.api.take() {
+            self.pool.release(api);
+        }
+    }
+}
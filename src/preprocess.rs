@@ -0,0 +1,1072 @@
+use crate::error::{Result, TesseractError};
+use crate::pix::Pix;
+use crate::TesseractAPI;
+
+/// Gray level below which a pixel counts as dark for despeckling and border removal.
+const DARK: u8 = 128;
+
+/// Dynamic range used by the Sauvola threshold for the standard deviation.
+const SAUVOLA_RANGE: f64 = 128.0;
+
+/// An 8-bit grayscale image, the format `Pipeline` works on.
+///
+/// The pixels are packed without padding, so the image can be passed to
+/// `TesseractAPI::set_image` with one byte per pixel and `width` bytes per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrayImage {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    dpi: Option<u32>,
+}
+
+impl GrayImage {
+    /// Creates an image from grayscale pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Pixels, `width` bytes per line.
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image if the data matches the dimensions, otherwise returns an error.
+    pub fn new(data: Vec<u8>, width: u32, height: u32) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(TesseractError::InvalidDimensions);
+        }
+        if (width as usize).checked_mul(height as usize) != Some(data.len()) {
+            return Err(TesseractError::InvalidImageData);
+        }
+        Ok(GrayImage {
+            data,
+            width,
+            height,
+            dpi: None,
+        })
+    }
+
+    /// Converts pixels in the layout accepted by `TesseractAPI::set_image` to grayscale.
+    ///
+    /// Color pixels are converted with the ITU-R BT.601 luma weights; an alpha channel is
+    /// ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Raw image data.
+    /// * `width` - Width of the image.
+    /// * `height` - Height of the image.
+    /// * `bytes_per_pixel` - 1 for grayscale, 3 for RGB or 4 for RGBA.
+    /// * `bytes_per_line` - Number of bytes per line, including any padding.
+    ///
+    /// # Returns
+    ///
+    /// Returns the grayscale image if the arguments describe a valid image, otherwise
+    /// returns an error.
+    pub fn from_pixels(
+        data: &[u8],
+        width: u32,
+        height: u32,
+        bytes_per_pixel: u32,
+        bytes_per_line: u32,
+    ) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(TesseractError::InvalidDimensions);
+        }
+        if ![1, 3, 4].contains(&bytes_per_pixel) {
+            return Err(TesseractError::InvalidBytesPerPixel);
+        }
+        let (width_px, height_px, bpp, stride) = (
+            width as usize,
+            height as usize,
+            bytes_per_pixel as usize,
+            bytes_per_line as usize,
+        );
+        if stride < width_px * bpp {
+            return Err(TesseractError::InvalidBytesPerLine);
+        }
+        if data.len() < (height_px - 1) * stride + width_px * bpp {
+            return Err(TesseractError::InvalidImageData);
+        }
+        let mut gray = Vec::with_capacity(width_px * height_px);
+        for row in 0..height_px {
+            let line = &data[row * stride..row * stride + width_px * bpp];
+            if bpp == 1 {
+                gray.extend_from_slice(line);
+            } else {
+                gray.extend(
+                    line.chunks_exact(bpp)
+                        .map(|pixel| luma(pixel[0], pixel[1], pixel[2])),
+                );
+            }
+        }
+        Self::new(gray, width, height)
+    }
+
+    /// Converts a Leptonica image to grayscale, keeping its resolution.
+    ///
+    /// # Arguments
+    ///
+    /// * `pix` - The image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the grayscale image if successful, otherwise returns an error.
+    pub fn from_pix(pix: &Pix) -> Result<Self> {
+        let (data, bytes_per_pixel) = pix.to_bytes()?;
+        let (width, height) = (pix.width()? as u32, pix.height()? as u32);
+        let image = Self::from_pixels(
+            &data,
+            width,
+            height,
+            bytes_per_pixel as u32,
+            width * bytes_per_pixel as u32,
+        )?;
+        Ok(match pix.resolution()?.0 {
+            dpi if dpi > 0 => image.with_dpi(dpi as u32),
+            _ => image,
+        })
+    }
+
+    /// Converts a decoded `image` crate image to grayscale.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The decoded image.
+    ///
+    /// # Returns
+    ///
+    /// Returns the grayscale image.
+    #[cfg(feature = "image")]
+    pub fn from_dynamic_image(image: &image::DynamicImage) -> Self {
+        let gray = image.to_luma8();
+        let (width, height) = gray.dimensions();
+        GrayImage {
+            data: gray.into_raw(),
+            width,
+            height,
+            dpi: None,
+        }
+    }
+
+    /// Records the resolution of the image, which `Pipeline::rescale` scales from.
+    ///
+    /// # Arguments
+    ///
+    /// * `dpi` - Resolution in pixels per inch.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image with its resolution set.
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = Some(dpi).filter(|dpi| *dpi > 0);
+        self
+    }
+
+    /// Gets the width of the image.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height of the image.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the resolution of the image, if known.
+    pub fn dpi(&self) -> Option<u32> {
+        self.dpi
+    }
+
+    /// Gets the pixels, `width` bytes per line.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Gets the pixel at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the image.
+    pub fn pixel(&self, x: u32, y: u32) -> u8 {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        self.data[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Consumes the image, returning its pixels.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Creates an image of the same size and resolution with new pixels.
+    fn with_data(&self, data: Vec<u8>) -> Self {
+        GrayImage {
+            data,
+            width: self.width,
+            height: self.height,
+            dpi: self.dpi,
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width as usize + x
+    }
+}
+
+/// A preprocessing step of a `Pipeline`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    NormalizeContrast {
+        clip_percent: f32,
+    },
+    Otsu,
+    Sauvola {
+        window: u32,
+        k: f32,
+    },
+    /// Rotates by the given angle, or by the angle the engine detects when `None`.
+    Deskew(Option<f32>),
+    Despeckle {
+        max_size: usize,
+    },
+    RemoveBorder,
+    Rescale {
+        dpi: u32,
+    },
+}
+
+/// A chain of image preprocessing steps run before recognition.
+///
+/// Steps run in the order they were added, each on the output of the previous one. The
+/// result is an 8-bit `GrayImage` that `apply` sets on an engine, or that `run` returns for
+/// `TesseractAPI::set_image`.
+///
+/// ```no_run
+/// # fn example(api: &tesseract_rs::TesseractAPI, pix: &tesseract_rs::Pix) -> tesseract_rs::Result<()> {
+/// use tesseract_rs::{GrayImage, Pipeline};
+///
+/// let pipeline = Pipeline::new()
+///     .rescale(300)
+///     .normalize_contrast(1.0)
+///     .deskew()
+///     .sauvola(31, 0.34)
+///     .despeckle(4)
+///     .remove_border();
+/// pipeline.apply(api, GrayImage::from_pix(pix)?)?;
+/// println!("{}", api.get_utf8_text()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a linear contrast stretch that maps the darkest and brightest gray levels to
+    /// black and white.
+    ///
+    /// # Arguments
+    ///
+    /// * `clip_percent` - Percentage of pixels at each end that may be clipped, so that a
+    ///   few outliers do not limit the stretch.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn normalize_contrast(mut self, clip_percent: f32) -> Self {
+        self.steps.push(Step::NormalizeContrast {
+            clip_percent: clip_percent.clamp(0.0, 50.0),
+        });
+        self
+    }
+
+    /// Adds binarization with a global threshold chosen by Otsu's method.
+    ///
+    /// Suits evenly lit pages; use `sauvola` for uneven backgrounds.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn otsu(mut self) -> Self {
+        self.steps.push(Step::Otsu);
+        self
+    }
+
+    /// Adds binarization with Sauvola's adaptive threshold, computed from the mean and
+    /// standard deviation of the window around each pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Side of the window in pixels, rounded up to an odd number. A window of
+    ///   about twice the text height works well.
+    /// * `k` - Sensitivity; higher values turn fewer pixels black. Tesseract's own Sauvola
+    ///   thresholding defaults to 0.34.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn sauvola(mut self, window: u32, k: f32) -> Self {
+        self.steps.push(Step::Sauvola {
+            window: window.max(1) | 1,
+            k,
+        });
+        self
+    }
+
+    /// Adds deskewing by the angle the engine detects.
+    ///
+    /// The angle is the deskew angle of the first block reported by
+    /// `PageIterator::orientation` after layout analysis, or the slope of the first baseline
+    /// from `TesseractAPI::get_text_direction` when no block reports one. Pipelines with
+    /// this step must be run with `apply`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn deskew(mut self) -> Self {
+        self.steps.push(Step::Deskew(None));
+        self
+    }
+
+    /// Adds a rotation by a known angle about the image center, filling uncovered areas
+    /// with white.
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - Counter-clockwise rotation in radians, as returned by `detect_skew`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn deskew_by(mut self, angle: f32) -> Self {
+        self.steps.push(Step::Deskew(Some(angle)));
+        self
+    }
+
+    /// Adds removal of small dark specks, such as dust and scanner noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_size` - Largest number of pixels in a connected dark area that is removed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn despeckle(mut self, max_size: usize) -> Self {
+        self.steps.push(Step::Despeckle { max_size });
+        self
+    }
+
+    /// Adds removal of dark areas connected to the image edges, such as the black borders
+    /// left by scanners and photocopiers.
+    ///
+    /// Text touching the edge of the image is removed as well.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn remove_border(mut self) -> Self {
+        self.steps.push(Step::RemoveBorder);
+        self
+    }
+
+    /// Adds rescaling to a resolution, which Tesseract recognizes best at about 300 DPI.
+    ///
+    /// The image must have a resolution, see `GrayImage::with_dpi`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dpi` - Target resolution in pixels per inch.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated pipeline.
+    pub fn rescale(mut self, dpi: u32) -> Self {
+        self.steps.push(Step::Rescale { dpi });
+        self
+    }
+
+    /// Runs the steps on an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image to preprocess.
+    ///
+    /// # Returns
+    ///
+    /// Returns the preprocessed image, otherwise returns `InvalidParameterError` if the
+    /// pipeline detects the skew angle, which needs an engine, or rescales an image without a
+    /// resolution.
+    pub fn run(&self, image: GrayImage) -> Result<GrayImage> {
+        self.steps.iter().try_fold(image, |image, step| match step {
+            Step::Deskew(None) => Err(TesseractError::InvalidParameterError),
+            step => run_step(step, &image),
+        })
+    }
+
+    /// Runs the steps on an image and sets the result as the image to recognize.
+    ///
+    /// The resolution of the result, if known, is passed on with
+    /// `TesseractAPI::set_source_resolution`.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The engine that detects the skew angle and receives the result.
+    /// * `image` - The image to preprocess.
+    ///
+    /// # Returns
+    ///
+    /// Returns the preprocessed image that was set, otherwise returns an error.
+    pub fn apply(&self, api: &TesseractAPI, image: GrayImage) -> Result<GrayImage> {
+        let image = self
+            .steps
+            .iter()
+            .try_fold(image, |image, step| match step {
+                Step::Deskew(None) => Ok(rotate(&image, detect_skew(api, &image)?)),
+                step => run_step(step, &image),
+            })?;
+        set_image(api, &image)?;
+        Ok(image)
+    }
+}
+
+fn run_step(step: &Step, image: &GrayImage) -> Result<GrayImage> {
+    Ok(match *step {
+        Step::NormalizeContrast { clip_percent } => normalize_contrast(image, clip_percent),
+        Step::Otsu => threshold(image, otsu_level(image)),
+        Step::Sauvola { window, k } => sauvola(image, window, k),
+        Step::Deskew(angle) => rotate(image, angle.unwrap_or(0.0)),
+        Step::Despeckle { max_size } => despeckle(image, max_size),
+        Step::RemoveBorder => remove_border(image),
+        Step::Rescale { dpi } => rescale(image, dpi)?,
+    })
+}
+
+/// Detects how far the text of an image is skewed.
+///
+/// The image is set on the engine and its layout analyzed, replacing any image and results
+/// the engine had.
+///
+/// # Arguments
+///
+/// * `api` - The engine used for layout analysis.
+/// * `image` - The image to analyze.
+///
+/// # Returns
+///
+/// Returns the counter-clockwise rotation in radians that levels the text, or 0 if no text
+/// was found, otherwise returns an error.
+pub fn detect_skew(api: &TesseractAPI, image: &GrayImage) -> Result<f32> {
+    set_image(api, image)?;
+    let from_blocks = api
+        .analyse_layout()
+        .and_then(|iterator| iterator.orientation())
+        .map(|(_, _, _, deskew_angle)| deskew_angle);
+    match from_blocks {
+        Ok(angle) if angle != 0.0 => Ok(angle),
+        // The slope is in Tesseract's coordinates, where y points up, so a positive slope
+        // rises to the right and is leveled by a clockwise rotation.
+        _ => Ok(-api.get_text_direction()?.1.atan()),
+    }
+}
+
+fn set_image(api: &TesseractAPI, image: &GrayImage) -> Result<()> {
+    api.set_image(
+        &image.data,
+        image.width as i32,
+        image.height as i32,
+        1,
+        image.width as i32,
+    )?;
+    if let Some(dpi) = image.dpi {
+        api.set_source_resolution(dpi as i32)?;
+    }
+    Ok(())
+}
+
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    ((299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b) + 500) / 1000) as u8
+}
+
+fn histogram(image: &GrayImage) -> [u64; 256] {
+    let mut histogram = [0u64; 256];
+    for &value in &image.data {
+        histogram[value as usize] += 1;
+    }
+    histogram
+}
+
+fn normalize_contrast(image: &GrayImage, clip_percent: f32) -> GrayImage {
+    let histogram = histogram(image);
+    let clip = (image.data.len() as f64 * f64::from(clip_percent) / 100.0) as u64;
+    // The first level, counting from either end, past the clipped pixels.
+    let first_level = |levels: Vec<usize>| {
+        let mut seen = 0;
+        levels
+            .into_iter()
+            .find(|&level| {
+                seen += histogram[level];
+                seen > clip
+            })
+            .unwrap_or(0)
+    };
+    let low = first_level((0..256).collect());
+    let high = first_level((0..256).rev().collect());
+    if high <= low {
+        return image.clone();
+    }
+    let range = (high - low) as f64;
+    let table: Vec<u8> = (0..256)
+        .map(|level| {
+            let stretched = (level as f64 - low as f64) * 255.0 / range;
+            stretched.round().clamp(0.0, 255.0) as u8
+        })
+        .collect();
+    image.with_data(
+        image
+            .data
+            .iter()
+            .map(|&value| table[value as usize])
+            .collect(),
+    )
+}
+
+/// Chooses the level that maximizes the variance between the pixels at or below it and
+/// those above it.
+fn otsu_level(image: &GrayImage) -> u8 {
+    let histogram = histogram(image);
+    let total = image.data.len() as f64;
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(level, &count)| level as f64 * count as f64)
+        .sum();
+    let (mut background, mut background_sum) = (0.0, 0.0);
+    let (mut best_level, mut best_variance) = (0, -1.0);
+    for (level, &count) in histogram.iter().enumerate() {
+        background += count as f64;
+        if background == 0.0 {
+            continue;
+        }
+        let foreground = total - background;
+        if foreground == 0.0 {
+            break;
+        }
+        background_sum += level as f64 * count as f64;
+        let mean_difference = background_sum / background - (sum - background_sum) / foreground;
+        let variance = background * foreground * mean_difference * mean_difference;
+        if variance > best_variance {
+            best_variance = variance;
+            best_level = level;
+        }
+    }
+    best_level as u8
+}
+
+/// Turns pixels at or below `level` black and the others white.
+fn threshold(image: &GrayImage, level: u8) -> GrayImage {
+    image.with_data(
+        image
+            .data
+            .iter()
+            .map(|&value| if value <= level { 0 } else { 255 })
+            .collect(),
+    )
+}
+
+fn sauvola(image: &GrayImage, window: u32, k: f32) -> GrayImage {
+    let (width, height) = (image.width as usize, image.height as usize);
+    // Summed-area tables of the values and their squares, with a zero row and column.
+    let stride = width + 1;
+    let mut sums = vec![0u64; stride * (height + 1)];
+    let mut squares = vec![0u64; stride * (height + 1)];
+    for y in 0..height {
+        let (mut row_sum, mut row_squares) = (0u64, 0u64);
+        for x in 0..width {
+            let value = u64::from(image.data[image.index(x, y)]);
+            row_sum += value;
+            row_squares += value * value;
+            let at = (y + 1) * stride + x + 1;
+            sums[at] = sums[at - stride] + row_sum;
+            squares[at] = squares[at - stride] + row_squares;
+        }
+    }
+    let area_sum = |table: &[u64], (x0, y0, x1, y1): (usize, usize, usize, usize)| {
+        table[y1 * stride + x1] + table[y0 * stride + x0]
+            - table[y0 * stride + x1]
+            - table[y1 * stride + x0]
+    };
+
+    let radius = (window / 2) as usize;
+    let k = f64::from(k);
+    let mut data = Vec::with_capacity(image.data.len());
+    for y in 0..height {
+        for x in 0..width {
+            let area = (
+                x.saturating_sub(radius),
+                y.saturating_sub(radius),
+                (x + radius + 1).min(width),
+                (y + radius + 1).min(height),
+            );
+            let count = ((area.2 - area.0) * (area.3 - area.1)) as f64;
+            let mean = area_sum(&sums, area) as f64 / count;
+            let variance = (area_sum(&squares, area) as f64 / count - mean * mean).max(0.0);
+            let level = mean * (1.0 + k * (variance.sqrt() / SAUVOLA_RANGE - 1.0));
+            let value = f64::from(image.data[image.index(x, y)]);
+            data.push(if value <= level { 0 } else { 255 });
+        }
+    }
+    image.with_data(data)
+}
+
+/// Rotates counter-clockwise about the center with bilinear interpolation, keeping the size.
+fn rotate(image: &GrayImage, angle: f32) -> GrayImage {
+    if angle == 0.0 {
+        return image.clone();
+    }
+    let (width, height) = (image.width as usize, image.height as usize);
+    let (sin, cos) = f64::from(angle).sin_cos();
+    let (center_x, center_y) = ((width - 1) as f64 / 2.0, (height - 1) as f64 / 2.0);
+    let sample = |x: isize, y: isize| -> f64 {
+        if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+            255.0
+        } else {
+            f64::from(image.data[image.index(x as usize, y as usize)])
+        }
+    };
+    let mut data = Vec::with_capacity(image.data.len());
+    for y in 0..height {
+        for x in 0..width {
+            // Each output pixel is read from the source position rotated the other way.
+            let (dx, dy) = (x as f64 - center_x, y as f64 - center_y);
+            let source_x = center_x + dx * cos - dy * sin;
+            let source_y = center_y + dx * sin + dy * cos;
+            let (x0, y0) = (source_x.floor(), source_y.floor());
+            let (fx, fy) = (source_x - x0, source_y - y0);
+            let (x0, y0) = (x0 as isize, y0 as isize);
+            let top = sample(x0, y0) * (1.0 - fx) + sample(x0 + 1, y0) * fx;
+            let bottom = sample(x0, y0 + 1) * (1.0 - fx) + sample(x0 + 1, y0 + 1) * fx;
+            data.push((top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8);
+        }
+    }
+    image.with_data(data)
+}
+
+/// Visits the 8-connected dark area containing `start`, returning its pixel indices.
+fn dark_area(image: &GrayImage, start: usize, visited: &mut [bool]) -> Vec<usize> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut area = Vec::new();
+    let mut pending = vec![start];
+    visited[start] = true;
+    while let Some(index) = pending.pop() {
+        area.push(index);
+        let (x, y) = (index % width, index / width);
+        for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                let neighbor = image.index(nx, ny);
+                if !visited[neighbor] && image.data[neighbor] < DARK {
+                    visited[neighbor] = true;
+                    pending.push(neighbor);
+                }
+            }
+        }
+    }
+    area
+}
+
+fn despeckle(image: &GrayImage, max_size: usize) -> GrayImage {
+    let mut data = image.data.clone();
+    let mut visited = vec![false; data.len()];
+    for start in 0..data.len() {
+        if visited[start] || image.data[start] >= DARK {
+            continue;
+        }
+        let area = dark_area(image, start, &mut visited);
+        if area.len() <= max_size {
+            for index in area {
+                data[index] = 255;
+            }
+        }
+    }
+    image.with_data(data)
+}
+
+fn remove_border(image: &GrayImage) -> GrayImage {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut data = image.data.clone();
+    let mut visited = vec![false; data.len()];
+    let edges = (0..width)
+        .flat_map(|x| [image.index(x, 0), image.index(x, height - 1)])
+        .chain((0..height).flat_map(|y| [image.index(0, y), image.index(width - 1, y)]));
+    for start in edges {
+        if visited[start] || image.data[start] >= DARK {
+            continue;
+        }
+        for index in dark_area(image, start, &mut visited) {
+            data[index] = 255;
+        }
+    }
+    image.with_data(data)
+}
+
+fn rescale(image: &GrayImage, dpi: u32) -> Result<GrayImage> {
+    let source_dpi = image.dpi.ok_or(TesseractError::InvalidParameterError)?;
+    if dpi == 0 {
+        return Err(TesseractError::InvalidParameterError);
+    }
+    let factor = f64::from(dpi) / f64::from(source_dpi);
+    let scaled = |length: u32| ((f64::from(length) * factor).round() as u32).max(1);
+    let (width, height) = (scaled(image.width), scaled(image.height));
+    if (width, height) == (image.width, image.height) {
+        return Ok(image.clone().with_dpi(dpi));
+    }
+
+    let columns = resample_weights(image.width as usize, width as usize);
+    let rows = resample_weights(image.height as usize, height as usize);
+    // Resamples each row, then each column of the result.
+    let mut horizontal = vec![0.0f64; width as usize * image.height as usize];
+    for y in 0..image.height as usize {
+        let line = &image.data[y * image.width as usize..(y + 1) * image.width as usize];
+        for (x, weights) in columns.iter().enumerate() {
+            horizontal[y * width as usize + x] = weights
+                .iter()
+                .map(|&(source, weight)| f64::from(line[source]) * weight)
+                .sum();
+        }
+    }
+    let mut data = vec![0u8; width as usize * height as usize];
+    for (y, weights) in rows.iter().enumerate() {
+        for x in 0..width as usize {
+            let value: f64 = weights
+                .iter()
+                .map(|&(source, weight)| horizontal[source * width as usize + x] * weight)
+                .sum();
+            data[y * width as usize + x] = value.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    Ok(GrayImage {
+        data,
+        width,
+        height,
+        dpi: Some(dpi),
+    })
+}
+
+/// Gets the source pixels and weights of each output pixel when resampling a line of
+/// `source` pixels to `target` pixels: the average of the covered pixels when shrinking,
+/// and linear interpolation between pixel centers when enlarging.
+fn resample_weights(source: usize, target: usize) -> Vec<Vec<(usize, f64)>> {
+    let scale = source as f64 / target as f64;
+    (0..target)
+        .map(|i| {
+            if target < source {
+                let (start, end) = (i as f64 * scale, (i + 1) as f64 * scale);
+                let mut weights = Vec::new();
+                let mut pixel = start.floor() as usize;
+                while (pixel as f64) < end && pixel < source {
+                    let covered = (end.min(pixel as f64 + 1.0) - start.max(pixel as f64)) / scale;
+                    if covered > 0.0 {
+                        weights.push((pixel, covered));
+                    }
+                    pixel += 1;
+                }
+                weights
+            } else {
+                let position = ((i as f64 + 0.5) * scale - 0.5).clamp(0.0, (source - 1) as f64);
+                let left = position.floor() as usize;
+                let fraction = position - left as f64;
+                if fraction > 0.0 && left + 1 < source {
+                    vec![(left, 1.0 - fraction), (left + 1, fraction)]
+                } else {
+                    vec![(left, 1.0)]
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a white image and lets `draw` return the value of each pixel instead.
+    fn image(width: u32, height: u32, draw: impl Fn(u32, u32) -> Option<u8>) -> GrayImage {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                data.push(draw(x, y).unwrap_or(255));
+            }
+        }
+        GrayImage::new(data, width, height).unwrap()
+    }
+
+    fn dark_pixels(image: &GrayImage) -> Vec<(u32, u32)> {
+        let mut pixels = Vec::new();
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                if image.pixel(x, y) < DARK {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn test_grayscale_conversion() {
+        // One row of red, green, blue and white RGBA pixels, padded to 20 bytes per line.
+        let mut data = vec![255, 0, 0, 9, 0, 255, 0, 9, 0, 0, 255, 9, 255, 255, 255, 9];
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&data.clone());
+        let gray = GrayImage::from_pixels(&data, 4, 2, 4, 20).unwrap();
+        assert_eq!(gray.data(), &[76, 150, 29, 255, 76, 150, 29, 255]);
+
+        assert!(matches!(
+            GrayImage::from_pixels(&data, 4, 2, 4, 8),
+            Err(TesseractError::InvalidBytesPerLine)
+        ));
+        assert!(matches!(
+            GrayImage::from_pixels(&data[..30], 4, 2, 4, 20),
+            Err(TesseractError::InvalidImageData)
+        ));
+        assert!(matches!(
+            GrayImage::from_pixels(&data, 4, 2, 2, 20),
+            Err(TesseractError::InvalidBytesPerPixel)
+        ));
+    }
+
+    #[test]
+    fn test_otsu_separates_two_levels() {
+        let input = image(8, 8, |x, y| Some(if (x + y) % 3 == 0 { 60 } else { 190 }));
+        let level = otsu_level(&input);
+        assert!((60..190).contains(&level));
+
+        let output = Pipeline::new().otsu().run(input.clone()).unwrap();
+        for (before, after) in input.data().iter().zip(output.data()) {
+            assert_eq!(*after, if *before == 60 { 0 } else { 255 });
+        }
+    }
+
+    #[test]
+    fn test_sauvola_handles_uneven_background() {
+        // Dark strokes on a background brightening from left to right, where the strokes on
+        // the right are brighter than the background on the left.
+        let background = |x: u32| (120 + x * 100 / 63) as u8;
+        let is_stroke = |x: u32| [10, 11, 31, 32, 52, 53].contains(&x);
+        let input = image(64, 24, |x, _| {
+            Some(background(x) - if is_stroke(x) { 100 } else { 0 })
+        });
+
+        let output = Pipeline::new()
+            .sauvola(15, 0.34)
+            .run(input.clone())
+            .unwrap();
+        for y in 0..24 {
+            for x in 0..64 {
+                let expected = if is_stroke(x) { 0 } else { 255 };
+                assert_eq!(output.pixel(x, y), expected, "pixel ({}, {})", x, y);
+            }
+        }
+
+        // A single global threshold cannot separate them.
+        let global = Pipeline::new().otsu().run(input).unwrap();
+        assert!((0..64).any(|x| (global.pixel(x, 0) == 0) != is_stroke(x)));
+    }
+
+    #[test]
+    fn test_deskew_levels_rotated_line() {
+        // A 3 pixel thick line through the center, rising to the right by 0.1 radians.
+        let slope = 0.1f32.tan();
+        let input = image(101, 61, |x, y| {
+            let line_y = 30.0 - (x as f32 - 50.0) * slope;
+            ((y as f32 - line_y).abs() <= 1.0).then_some(0)
+        });
+        let rows = |image: &GrayImage| {
+            let pixels = dark_pixels(image);
+            let min = pixels.iter().map(|p| p.1).min().unwrap();
+            let max = pixels.iter().map(|p| p.1).max().unwrap();
+            (min, max, pixels.len())
+        };
+        assert!(rows(&input).1 - rows(&input).0 > 8);
+
+        let output = Pipeline::new().deskew_by(-0.1).run(input).unwrap();
+        let (min, max, count) = rows(&output);
+        assert!(min >= 28 && max <= 32, "rows {}..={}", min, max);
+        assert!(count > 200);
+    }
+
+    #[test]
+    fn test_deskew_by_zero_keeps_image() {
+        let input = image(9, 7, |x, y| Some((x * 20 + y) as u8));
+        assert_eq!(
+            Pipeline::new().deskew_by(0.0).run(input.clone()).unwrap(),
+            input
+        );
+        assert_eq!(rotate(&input, 1e-9), input);
+    }
+
+    #[test]
+    fn test_deskew_detection_needs_engine() {
+        let input = image(4, 4, |_, _| None);
+        assert!(matches!(
+            Pipeline::new().deskew().run(input),
+            Err(TesseractError::InvalidParameterError)
+        ));
+    }
+
+    #[test]
+    fn test_despeckle_removes_small_areas() {
+        let block = |x: u32, y: u32| (10..15).contains(&x) && (10..15).contains(&y);
+        let speck = |x: u32, y: u32| (2..4).contains(&x) && (2..4).contains(&y);
+        let input = image(20, 20, |x, y| {
+            (block(x, y) || speck(x, y) || (x, y) == (17, 3) || (x, y) == (5, 17)).then_some(0)
+        });
+
+        let output = Pipeline::new().despeckle(4).run(input).unwrap();
+        let expected: Vec<_> = (0..20)
+            .flat_map(|y| (0..20).map(move |x| (x, y)))
+            .filter(|&(x, y)| block(x, y))
+            .collect();
+        assert_eq!(dark_pixels(&output), expected);
+    }
+
+    #[test]
+    fn test_despeckle_joins_diagonal_neighbors() {
+        let input = image(6, 6, |x, y| (x == y).then_some(0));
+        assert_eq!(
+            Pipeline::new().despeckle(5).run(input.clone()).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn test_remove_border() {
+        let frame = |x: u32, y: u32| x < 3 || y < 3 || x >= 27 || y >= 17;
+        // A stroke reaching into the frame is part of it; the letter in the middle is not.
+        let letter = |x: u32, y: u32| (10..20).contains(&x) && (8..12).contains(&y);
+        let input = image(30, 20, |x, y| {
+            if frame(x, y) || (x == 3 && y == 10) {
+                Some(20)
+            } else if letter(x, y) {
+                Some(40)
+            } else {
+                None
+            }
+        });
+
+        let output = Pipeline::new().remove_border().run(input).unwrap();
+        for y in 0..20 {
+            for x in 0..30 {
+                let expected = if letter(x, y) { 40 } else { 255 };
+                assert_eq!(output.pixel(x, y), expected, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rescale_to_dpi() {
+        let stripes = image(4, 2, |x, _| Some(if x < 2 { 0 } else { 200 })).with_dpi(600);
+        let half = Pipeline::new().rescale(300).run(stripes).unwrap();
+        assert_eq!((half.width(), half.height(), half.dpi()), (2, 1, Some(300)));
+        assert_eq!(half.data(), &[0, 200]);
+
+        let uniform = image(3, 3, |_, _| Some(100)).with_dpi(150);
+        let double = Pipeline::new().rescale(300).run(uniform).unwrap();
+        assert_eq!((double.width(), double.height()), (6, 6));
+        assert!(double.data().iter().all(|&value| value == 100));
+
+        // Enlarging interpolates between pixel centers.
+        let ramp = image(2, 1, |x, _| Some(if x == 0 { 0 } else { 100 })).with_dpi(100);
+        let ramp = Pipeline::new().rescale(200).run(ramp).unwrap();
+        assert_eq!(ramp.data(), &[0, 25, 75, 100, 0, 25, 75, 100]);
+    }
+
+    #[test]
+    fn test_rescale_needs_resolution() {
+        let input = image(4, 4, |_, _| None);
+        assert!(matches!(
+            Pipeline::new().rescale(300).run(input),
+            Err(TesseractError::InvalidParameterError)
+        ));
+    }
+
+    #[test]
+    fn test_normalize_contrast() {
+        let input = image(51, 2, |x, _| Some(100 + x as u8));
+        let output = Pipeline::new().normalize_contrast(0.0).run(input).unwrap();
+        assert_eq!(output.pixel(0, 0), 0);
+        assert_eq!(output.pixel(50, 1), 255);
+        assert_eq!(output.pixel(25, 0), 128);
+        assert!(output.data()[..51].windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Clipping ignores the outliers at either end.
+        let outliers = image(100, 1, |x, _| {
+            Some(match x {
+                0 => 0,
+                99 => 255,
+                x if x < 50 => 100,
+                _ => 150,
+            })
+        });
+        let output = Pipeline::new()
+            .normalize_contrast(1.0)
+            .run(outliers)
+            .unwrap();
+        assert_eq!((output.pixel(1, 0), output.pixel(98, 0)), (0, 255));
+
+        let flat = image(3, 3, |_, _| Some(90));
+        assert_eq!(
+            Pipeline::new()
+                .normalize_contrast(0.0)
+                .run(flat.clone())
+                .unwrap(),
+            flat
+        );
+    }
+
+    #[test]
+    fn test_pipeline_chains_steps_in_order() {
+        // Low-contrast text with a speck and a scanner border.
+        let input = image(40, 30, |x, y| {
+            if x < 2 || y < 2 || x >= 38 || y >= 28 {
+                Some(110)
+            } else if (10..30).contains(&x) && (12..16).contains(&y) {
+                Some(120)
+            } else if (x, y) == (6, 6) {
+                Some(125)
+            } else {
+                Some(140)
+            }
+        })
+        .with_dpi(150);
+
+        let output = Pipeline::new()
+            .normalize_contrast(0.0)
+            .otsu()
+            .remove_border()
+            .despeckle(2)
+            .rescale(300)
+            .run(input)
+            .unwrap();
+        assert_eq!(
+            (output.width(), output.height(), output.dpi()),
+            (80, 60, Some(300))
+        );
+        let pixels = dark_pixels(&output);
+        assert!(pixels
+            .iter()
+            .all(|&(x, y)| (20..60).contains(&x) && (24..32).contains(&y)));
+        assert_eq!(pixels.len(), 40 * 8);
+    }
+}
@@ -1,20 +1,45 @@
 use crate::api::TessDeleteText;
-use crate::enums::TessPageIteratorLevel;
+use crate::choice_iterator::{ChoiceIterator, Choices};
+use crate::enums::{TessPageIteratorLevel, TessParagraphJustification, TessPolyBlockType};
 use crate::error::{Result, TesseractError};
+use crate::page_iterator::{
+    TessPageIteratorBaseline, TessPageIteratorBegin, TessPageIteratorBlockType,
+    TessPageIteratorIsAtBeginningOf, TessPageIteratorIsAtFinalElement,
+    TessPageIteratorParagraphInfo,
+};
+use crate::results::ResultsGuard;
+use crate::views::{BlockView, Elements, LineView, SymbolView, WordView};
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_float, c_int, c_void};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 
-pub struct ResultIterator {
+/// Iterator over the recognition results of a `TesseractAPI`.
+///
+/// The iterator borrows the `TesseractAPI` it was created from, so it cannot outlive the
+/// engine. Calls that clear or replace the results, such as `set_image`, `recognize`,
+/// `clear` or `end`, invalidate it; its methods then return
+/// `TesseractError::IteratorInvalidated`.
+pub struct ResultIterator<'api> {
     pub handle: Arc<Mutex<*mut c_void>>,
+    pub(crate) results: ResultsGuard<'api>,
 }
 
-unsafe impl Send for ResultIterator {}
-unsafe impl Sync for ResultIterator {}
+unsafe impl Send for ResultIterator<'_> {}
+unsafe impl Sync for ResultIterator<'_> {}
 
-impl ResultIterator {
+impl<'api> ResultIterator<'api> {
     /// Creates a new instance of the ResultIterator.
     ///
+    /// The iterator is not tied to a `TesseractAPI`, so it cannot detect that the results it
+    /// reads were freed. Use `TesseractAPI::get_iterator` instead where possible.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid ResultIterator handle that nothing else deletes, as the returned
+    /// iterator deletes it when dropped. The results it reads must stay alive and unchanged
+    /// while the iterator is in use: the engine must not be dropped, and `set_image`,
+    /// `recognize`, `clear`, `end` and the other calls replacing the results must not be made.
+    ///
     /// # Arguments
     ///
     /// * `handle` - Pointer to the ResultIterator.
@@ -22,12 +47,183 @@ impl ResultIterator {
     /// # Returns
     ///
     /// Returns the new instance of the ResultIterator.
-    pub fn new(handle: *mut c_void) -> Self {
+    pub unsafe fn new(handle: *mut c_void) -> Self {
+        Self::with_results(handle, ResultsGuard::unchecked())
+    }
+
+    pub(crate) fn with_results(handle: *mut c_void, results: ResultsGuard<'api>) -> Self {
         ResultIterator {
             handle: Arc::new(Mutex::new(handle)),
+            results,
         }
     }
 
+    /// Locks the engine and the iterator after checking that the results are still alive.
+    pub(crate) fn lock(
+        &self,
+    ) -> Result<(
+        Option<MutexGuard<'api, *mut c_void>>,
+        MutexGuard<'_, *mut c_void>,
+    )> {
+        let results = self.results.lock()?;
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        Ok((results, handle))
+    }
+
+    /// Moves the iterator to the first element of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if successful, otherwise returns an error.
+    pub fn begin(&self) -> Result<()> {
+        let (_results, handle) = self.lock()?;
+        unsafe { TessPageIteratorBegin(*handle) };
+        Ok(())
+    }
+
+    /// Iterates over the layout blocks of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding a `BlockView` for each block, starting from the first.
+    pub fn blocks(&self) -> Elements<'_, BlockView> {
+        Elements::new(self, TessPageIteratorLevel::RIL_BLOCK, BlockView::read)
+    }
+
+    /// Iterates over the text lines of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding a `LineView` for each line, starting from the first.
+    pub fn lines(&self) -> Elements<'_, LineView> {
+        Elements::new(self, TessPageIteratorLevel::RIL_TEXTLINE, LineView::read)
+    }
+
+    /// Iterates over the words of the page.
+    ///
+    /// ```no_run
+    /// # fn example(api: &tesseract_rs::TesseractAPI) -> tesseract_rs::Result<()> {
+    /// use tesseract_rs::WordView;
+    ///
+    /// api.recognize()?;
+    /// let iterator = api.get_iterator()?;
+    /// let confident: Vec<WordView> = iterator
+    ///     .words()
+    ///     .filter(|word| word.as_ref().map_or(true, |word| word.confidence >= 60.0))
+    ///     .collect::<tesseract_rs::Result<_>>()?;
+    /// for word in &confident {
+    ///     println!("{} {:?}", word.text, word.bbox);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding a `WordView` for each word, starting from the first.
+    pub fn words(&self) -> Elements<'_, WordView> {
+        Elements::new(self, TessPageIteratorLevel::RIL_WORD, WordView::read)
+    }
+
+    /// Iterates over the symbols of the page.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator yielding a `SymbolView` for each symbol, starting from the first.
+    pub fn symbols(&self) -> Elements<'_, SymbolView> {
+        Elements::new(self, TessPageIteratorLevel::RIL_SYMBOL, SymbolView::read)
+    }
+
+    /// Gets the recognition alternatives for the current symbol.
+    ///
+    /// The legacy engine reports alternatives by default. For LSTM recognition, set
+    /// `lstm_choice_mode` to `TessLstmChoiceMode::LSTM_CHOICE_SYMBOLS` with
+    /// `TesseractAPI::set_lstm_choice_mode` before recognizing; otherwise only the chosen
+    /// symbol is reported.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator over `(text, confidence)` pairs, best first, if successful,
+    /// otherwise returns an error.
+    pub fn choices(&self) -> Result<Choices<'_>> {
+        let (_results, handle) = self.lock()?;
+        let choice_handle = unsafe { TessResultIteratorGetChoiceIterator(*handle) };
+        if choice_handle.is_null() {
+            return Err(TesseractError::NullPointerError);
+        }
+        Ok(Choices::new(ChoiceIterator::new(choice_handle), self))
+    }
+
+    /// Gets the raw LSTM candidates of every timestep of the current word.
+    ///
+    /// Requires `lstm_choice_mode` to be `TessLstmChoiceMode::LSTM_CHOICE_TIMESTEPS`, set
+    /// with `TesseractAPI::set_lstm_choice_mode` before recognizing; otherwise the result is
+    /// empty. Timesteps of all symbols are concatenated in order; use
+    /// `symbol_timestep_choices` to keep them grouped by symbol.
+    ///
+    /// # Returns
+    ///
+    /// Returns one list of `(text, probability)` candidates per timestep if successful,
+    /// otherwise returns an error.
+    pub fn word_timestep_choices(&self) -> Result<Vec<Vec<(String, f32)>>> {
+        Ok(self
+            .symbol_timestep_choices()?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Gets the raw LSTM candidates of every timestep of the current word, grouped by the
+    /// symbol the timesteps were segmented into.
+    ///
+    /// Requires `lstm_choice_mode` to be `TessLstmChoiceMode::LSTM_CHOICE_TIMESTEPS`.
+    ///
+    /// # Returns
+    ///
+    /// Returns, for each symbol, one list of `(text, probability)` candidates per timestep
+    /// if successful, otherwise returns an error.
+    pub fn symbol_timestep_choices(&self) -> Result<Vec<Vec<Vec<(String, f32)>>>> {
+        let (_results, handle) = self.lock()?;
+        let symbol_count = unsafe { TessRsResultIteratorLSTMSymbolCount(*handle) };
+        if symbol_count < 0 {
+            return Err(TesseractError::NullPointerError);
+        }
+        let mut symbols = Vec::with_capacity(symbol_count as usize);
+        for symbol in 0..symbol_count {
+            let timestep_count = unsafe { TessRsResultIteratorLSTMTimestepCount(*handle, symbol) };
+            let mut timesteps = Vec::with_capacity(timestep_count as usize);
+            for timestep in 0..timestep_count {
+                let choice_count =
+                    unsafe { TessRsResultIteratorLSTMChoiceCount(*handle, symbol, timestep) };
+                let mut choices = Vec::with_capacity(choice_count as usize);
+                for choice in 0..choice_count {
+                    let mut probability = 0.0;
+                    let text_ptr = unsafe {
+                        TessRsResultIteratorLSTMChoice(
+                            *handle,
+                            symbol,
+                            timestep,
+                            choice,
+                            &mut probability,
+                        )
+                    };
+                    if text_ptr.is_null() {
+                        return Err(TesseractError::NullPointerError);
+                    }
+                    // The text belongs to the engine's character set and must not be freed.
+                    let text = unsafe { CStr::from_ptr(text_ptr) }.to_str()?.to_owned();
+                    choices.push((text, probability));
+                }
+                timesteps.push(choices);
+            }
+            symbols.push(timesteps);
+        }
+        Ok(symbols)
+    }
+
     /// Gets the UTF-8 text of the current iterator.
     ///
     /// # Arguments
@@ -38,10 +234,7 @@ impl ResultIterator {
     ///
     /// Returns the UTF-8 text as a `String` if successful, otherwise returns an error.
     pub fn get_utf8_text(&self, level: TessPageIteratorLevel) -> Result<String> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         let text_ptr = unsafe { TessResultIteratorGetUTF8Text(*handle, level as c_int) };
         if text_ptr.is_null() {
             return Err(TesseractError::NullPointerError);
@@ -62,10 +255,7 @@ impl ResultIterator {
     ///
     /// Returns the confidence as a `f32`.
     pub fn confidence(&self, level: TessPageIteratorLevel) -> Result<f32> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorConfidence(*handle, level as c_int) })
     }
 
@@ -75,10 +265,7 @@ impl ResultIterator {
     ///
     /// Returns the recognition language as a `String` if successful, otherwise returns an error.
     pub fn word_recognition_language(&self) -> Result<String> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         let lang_ptr = unsafe { TessResultIteratorWordRecognitionLanguage(*handle) };
         if lang_ptr.is_null() {
             return Err(TesseractError::NullPointerError);
@@ -93,10 +280,7 @@ impl ResultIterator {
     ///
     /// Returns the font attributes as a tuple if successful, otherwise returns an error.
     pub fn word_font_attributes(&self) -> Result<(bool, bool, bool, bool, bool, bool, i32, i32)> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         let mut is_bold = 0;
         let mut is_italic = 0;
         let mut is_underlined = 0;
@@ -142,10 +326,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the current iterator is from the dictionary, otherwise returns `false`.
     pub fn word_is_from_dictionary(&self) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorWordIsFromDictionary(*handle) != 0 })
     }
 
@@ -155,10 +336,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the current iterator is numeric, otherwise returns `false`.
     pub fn word_is_numeric(&self) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorWordIsNumeric(*handle) != 0 })
     }
 
@@ -168,10 +346,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the current iterator is superscript, otherwise returns `false`.
     pub fn symbol_is_superscript(&self) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorSymbolIsSuperscript(*handle) != 0 })
     }
 
@@ -181,10 +356,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the current iterator is subscript, otherwise returns `false`.
     pub fn symbol_is_subscript(&self) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorSymbolIsSubscript(*handle) != 0 })
     }
 
@@ -194,10 +366,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the current iterator is dropcap, otherwise returns `false`.
     pub fn symbol_is_dropcap(&self) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorSymbolIsDropcap(*handle) != 0 })
     }
 
@@ -211,10 +380,7 @@ impl ResultIterator {
     ///
     /// Returns `true` if the next iterator exists, otherwise returns `false`.
     pub fn next(&self, level: TessPageIteratorLevel) -> Result<bool> {
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
         Ok(unsafe { TessResultIteratorNext(*handle, level as c_int) != 0 })
     }
 
@@ -260,10 +426,7 @@ impl ResultIterator {
         let mut right = 0;
         let mut bottom = 0;
 
-        let handle = self
-            .handle
-            .lock()
-            .map_err(|_| TesseractError::MutexLockError)?;
+        let (_results, handle) = self.lock()?;
 
         let result = unsafe {
             TessPageIteratorBoundingBox(
@@ -282,9 +445,141 @@ impl ResultIterator {
             Ok((left, top, right, bottom))
         }
     }
+    /// Checks whether there is no element of the specified level at the current position.
+    ///
+    /// Image, line and other non-text blocks have no paragraphs, lines, words or symbols,
+    /// and past the end of the page there is not even a block.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Level to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if there is no element of the specified level, otherwise returns `false`.
+    pub fn is_empty(&self, level: TessPageIteratorLevel) -> Result<bool> {
+        let (mut left, mut top, mut right, mut bottom) = (0, 0, 0, 0);
+        let (_results, handle) = self.lock()?;
+        // Tesseract only fails to report a bounding box for an element that is not there.
+        let result = unsafe {
+            TessPageIteratorBoundingBox(
+                *handle,
+                level as c_int,
+                &mut left,
+                &mut top,
+                &mut right,
+                &mut bottom,
+            )
+        };
+        Ok(result == 0)
+    }
+
+    /// Checks if the iterator is at the beginning of the specified level.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Level to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the iterator is at the beginning of the specified level, otherwise returns `false`.
+    pub fn is_at_beginning_of(&self, level: TessPageIteratorLevel) -> Result<bool> {
+        let (_results, handle) = self.lock()?;
+        Ok(unsafe { TessPageIteratorIsAtBeginningOf(*handle, level as c_int) != 0 })
+    }
+
+    /// Checks if the iterator is at the final element of the specified level.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Level of the enclosing element.
+    /// * `element` - Level of the element.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the iterator is at the final `element` within `level`, otherwise returns `false`.
+    pub fn is_at_final_element(
+        &self,
+        level: TessPageIteratorLevel,
+        element: TessPageIteratorLevel,
+    ) -> Result<bool> {
+        let (_results, handle) = self.lock()?;
+        Ok(unsafe {
+            TessPageIteratorIsAtFinalElement(*handle, level as c_int, element as c_int) != 0
+        })
+    }
+
+    /// Gets the block type of the current block.
+    ///
+    /// # Returns
+    ///
+    /// Returns the block type as a `TessPolyBlockType`.
+    pub fn block_type(&self) -> Result<TessPolyBlockType> {
+        let (_results, handle) = self.lock()?;
+        Ok(TessPolyBlockType::from_int(unsafe {
+            TessPageIteratorBlockType(*handle)
+        }))
+    }
+
+    /// Gets the baseline of the current element.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Level of the baseline.
+    ///
+    /// # Returns
+    ///
+    /// Returns the baseline as a tuple of (x1, y1, x2, y2) if successful, otherwise returns an error.
+    pub fn baseline(&self, level: TessPageIteratorLevel) -> Result<(i32, i32, i32, i32)> {
+        let mut x1 = 0;
+        let mut y1 = 0;
+        let mut x2 = 0;
+        let mut y2 = 0;
+        let (_results, handle) = self.lock()?;
+        let result = unsafe {
+            TessPageIteratorBaseline(*handle, level as c_int, &mut x1, &mut y1, &mut x2, &mut y2)
+        };
+        if result == 0 {
+            Err(TesseractError::InvalidParameterError)
+        } else {
+            Ok((x1, y1, x2, y2))
+        }
+    }
+
+    /// Gets the paragraph information of the current paragraph.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of (justification, is_list_item, is_crown, first_line_indent) if successful, otherwise returns an error.
+    pub fn paragraph_info(&self) -> Result<(TessParagraphJustification, bool, bool, i32)> {
+        let mut justification = 0;
+        let mut is_list_item = false;
+        let mut is_crown = false;
+        let mut first_line_indent = 0;
+        let (_results, handle) = self.lock()?;
+        let result = unsafe {
+            TessPageIteratorParagraphInfo(
+                *handle,
+                &mut justification,
+                &mut is_list_item,
+                &mut is_crown,
+                &mut first_line_indent,
+            )
+        };
+        if result == 0 {
+            Err(TesseractError::InvalidParameterError)
+        } else {
+            Ok((
+                TessParagraphJustification::from_int(justification),
+                is_list_item,
+                is_crown,
+                first_line_indent,
+            ))
+        }
+    }
 }
 
-impl Drop for ResultIterator {
+impl Drop for ResultIterator<'_> {
     fn drop(&mut self) {
         if let Ok(handle) = self.handle.lock() {
             unsafe { TessResultIteratorDelete(*handle) };
@@ -316,6 +611,8 @@ extern "C" {
     pub fn TessResultIteratorSymbolIsSubscript(handle: *mut c_void) -> c_int;
     pub fn TessResultIteratorSymbolIsDropcap(handle: *mut c_void) -> c_int;
     pub fn TessResultIteratorNext(handle: *mut c_void, level: c_int) -> c_int;
+    pub fn TessResultIteratorGetChoiceIterator(handle: *mut c_void) -> *mut c_void;
+    pub fn TessResultIteratorGetPageIterator(handle: *mut c_void) -> *mut c_void;
     pub fn TessPageIteratorBoundingBox(
         handle: *mut c_void,
         level: c_int,
@@ -325,3 +622,22 @@ extern "C" {
         bottom: *mut c_int,
     ) -> c_int;
 }
+
+// Accessors compiled from `shim/result_iterator.cpp` by the build script.
+#[cfg(feature = "build-tesseract")]
+extern "C" {
+    fn TessRsResultIteratorLSTMSymbolCount(handle: *mut c_void) -> c_int;
+    fn TessRsResultIteratorLSTMTimestepCount(handle: *mut c_void, symbol: c_int) -> c_int;
+    fn TessRsResultIteratorLSTMChoiceCount(
+        handle: *mut c_void,
+        symbol: c_int,
+        timestep: c_int,
+    ) -> c_int;
+    fn TessRsResultIteratorLSTMChoice(
+        handle: *mut c_void,
+        symbol: c_int,
+        timestep: c_int,
+        choice: c_int,
+        probability: *mut c_float,
+    ) -> *const c_char;
+}
@@ -1,12 +1,92 @@
-use crate::error::{Result, TesseractError};
+use crate::error::{to_cstring, Result, TesseractError};
 use crate::TesseractAPI;
-use std::ffi::{CStr, CString};
+use std::ffi::CStr;
+use std::io::Write;
 use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::Arc;
 use std::sync::Mutex;
 
+/// Output base that makes a renderer write to stdout instead of opening a file, which the
+/// in-memory constructors then redirect to their writer.
+const STDOUT_OUTPUTBASE: &str = "-";
+
 pub struct TessResultRenderer {
     handle: Arc<Mutex<*mut c_void>>,
+    /// Writers receiving the output of this renderer and those inserted into it; they are
+    /// referenced by the renderers until they are deleted, so they are boxed to keep their
+    /// addresses.
+    #[allow(clippy::vec_box)]
+    outputs: Mutex<Vec<Box<OutputSink>>>,
+}
+
+/// The writer behind the output stream of an in-memory renderer.
+struct OutputSink {
+    writer: Box<dyn Write + Send>,
+}
+
+/// A shared in-memory buffer for the output of `TessResultRenderer::new_text_renderer_to_writer`
+/// and the other in-memory constructors.
+///
+/// Clones share the same contents, so one clone can be given to the renderer while another
+/// reads the output.
+///
+/// ```no_run
+/// # fn example(api: &tesseract_rs::TesseractAPI) -> tesseract_rs::Result<()> {
+/// use tesseract_rs::{OutputBuffer, TessResultRenderer};
+///
+/// let pdf = OutputBuffer::new();
+/// let renderer = TessResultRenderer::new_pdf_renderer_to_writer("tessdata", false, pdf.clone())?;
+/// renderer.begin_document("scan");
+/// renderer.add_image(api);
+/// renderer.end_document();
+/// let bytes: Vec<u8> = pdf.take();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OutputBuffer {
+    bytes: Arc<Mutex<Vec<u8>>>,
+}
+
+impl OutputBuffer {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets a copy of the output written so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes written to the buffer.
+    pub fn contents(&self) -> Vec<u8> {
+        self.lock().clone()
+    }
+
+    /// Takes the output written so far, leaving the buffer empty.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bytes written to the buffer.
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+        self.bytes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Write for OutputBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 unsafe impl Send for TessResultRenderer {}
@@ -23,15 +103,7 @@ impl TessResultRenderer {
     ///
     /// Returns the new instance of the TessResultRenderer.
     pub fn new_text_renderer(outputbase: &str) -> Result<Self> {
-        let outputbase = CString::new(outputbase).unwrap();
-        let handle = unsafe { TessTextRendererCreate(outputbase.as_ptr()) };
-        if handle.is_null() {
-            Err(TesseractError::NullPointerError)
-        } else {
-            Ok(TessResultRenderer {
-                handle: Arc::new(Mutex::new(handle)),
-            })
-        }
+        Self::create(outputbase, TessTextRendererCreate)
     }
 
     /// Creates a new instance of the TessResultRenderer for HOCR.
@@ -44,15 +116,7 @@ impl TessResultRenderer {
     ///
     /// Returns the new instance of the TessResultRenderer.
     pub fn new_hocr_renderer(outputbase: &str) -> Result<Self> {
-        let outputbase = CString::new(outputbase).unwrap();
-        let handle = unsafe { TessHOcrRendererCreate(outputbase.as_ptr()) };
-        if handle.is_null() {
-            Err(TesseractError::NullPointerError)
-        } else {
-            Ok(TessResultRenderer {
-                handle: Arc::new(Mutex::new(handle)),
-            })
-        }
+        Self::create(outputbase, TessHOcrRendererCreate)
     }
 
     /// Creates a new instance of the TessResultRenderer for PDF.
@@ -67,22 +131,275 @@ impl TessResultRenderer {
     ///
     /// Returns the new instance of the TessResultRenderer.
     pub fn new_pdf_renderer(outputbase: &str, datadir: &str, textonly: bool) -> Result<Self> {
-        let outputbase = CString::new(outputbase).unwrap();
-        let datadir = CString::new(datadir).unwrap();
+        let outputbase = to_cstring(outputbase)?;
+        let datadir = to_cstring(datadir)?;
         let handle = unsafe {
             TessPDFRendererCreate(outputbase.as_ptr(), datadir.as_ptr(), textonly as c_int)
         };
+        Self::from_handle(handle)
+    }
+
+    /// Creates a new instance of the TessResultRenderer writing text to a writer instead of
+    /// a file.
+    ///
+    /// The writer receives the output as the renderer produces it; use an `OutputBuffer` to
+    /// collect it in memory. Write errors make the renderer calls return `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the rendered document.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_text_renderer_to_writer<W: Write + Send + 'static>(writer: W) -> Result<Self> {
+        Self::new_text_renderer(STDOUT_OUTPUTBASE)?.with_writer(writer)
+    }
+
+    /// Creates a new instance of the TessResultRenderer writing HOCR to a writer instead of
+    /// a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the rendered document.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_hocr_renderer_to_writer<W: Write + Send + 'static>(writer: W) -> Result<Self> {
+        Self::new_hocr_renderer(STDOUT_OUTPUTBASE)?.with_writer(writer)
+    }
+
+    /// Creates a new instance of the TessResultRenderer writing PDF to a writer instead of
+    /// a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `datadir` - Data directory path.
+    /// * `textonly` - Whether to include text only.
+    /// * `writer` - Destination of the rendered document.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_pdf_renderer_to_writer<W: Write + Send + 'static>(
+        datadir: &str,
+        textonly: bool,
+        writer: W,
+    ) -> Result<Self> {
+        Self::new_pdf_renderer(STDOUT_OUTPUTBASE, datadir, textonly)?.with_writer(writer)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for ALTO XML.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.xml`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_alto_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessAltoRendererCreate)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for TSV.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.tsv`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_tsv_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessTsvRendererCreate)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for UNLV zone text.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.unlv`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_unlv_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessUnlvRendererCreate)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for character box files.
+    ///
+    /// The box renderers all write to `outputbase.box`, so at most one of them can be part
+    /// of a composite renderer for a given output base.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.box`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_box_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessBoxTextRendererCreate)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for LSTM training box files.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.box`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_lstm_box_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessLSTMBoxRendererCreate)
+    }
+
+    /// Creates a new instance of the TessResultRenderer for `WordStr` box files.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputbase` - Output base path; the document is written to `outputbase.box`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new instance of the TessResultRenderer.
+    pub fn new_word_str_box_renderer(outputbase: &str) -> Result<Self> {
+        Self::create(outputbase, TessWordStrBoxRendererCreate)
+    }
+
+    /// Creates a renderer that passes every call on to each of the given renderers, so one
+    /// pass over a document writes all of their formats.
+    ///
+    /// ```no_run
+    /// use tesseract_rs::TessResultRenderer;
+    ///
+    /// let renderer = TessResultRenderer::composite([
+    ///     TessResultRenderer::new_pdf_renderer("archive/scan", "tessdata", false)?,
+    ///     TessResultRenderer::new_hocr_renderer("archive/scan")?,
+    ///     TessResultRenderer::new_alto_renderer("archive/scan")?,
+    ///     TessResultRenderer::new_tsv_renderer("archive/scan")?,
+    /// ])?;
+    /// # Ok::<(), tesseract_rs::TesseractError>(())
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `renderers` - The renderers to combine; the first one is returned with the others
+    ///   inserted after it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the combined renderer, or `InvalidParameterError` if `renderers` is empty.
+    pub fn composite<I>(renderers: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = TessResultRenderer>,
+    {
+        let mut renderers = renderers.into_iter();
+        let first = renderers
+            .next()
+            .ok_or(TesseractError::InvalidParameterError)?;
+        for renderer in renderers {
+            first.insert(renderer)?;
+        }
+        Ok(first)
+    }
+
+    /// Appends a renderer that receives every call made to this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `next` - The renderer to append, which this renderer takes ownership of.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the renderer was appended, otherwise returns an error.
+    pub fn insert(&self, next: TessResultRenderer) -> Result<()> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        let mut next_handle = next
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        unsafe { TessResultRendererInsert(*handle, *next_handle) };
+        // Deleting this renderer now deletes the inserted one, so it keeps its writers too.
+        *next_handle = std::ptr::null_mut();
+        let next_outputs = std::mem::take(
+            &mut *next
+                .outputs
+                .lock()
+                .map_err(|_| TesseractError::MutexLockError)?,
+        );
+        self.outputs
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?
+            .extend(next_outputs);
+        Ok(())
+    }
+
+    /// Runs `f` with the renderer handle, then passes the output it produced on to the
+    /// writers of in-memory renderers.
+    pub(crate) fn render<T>(&self, f: impl FnOnce(*mut c_void) -> T) -> Result<T> {
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        let result = f(*handle);
+        unsafe { TessRsResultRendererFlushOutput(*handle) };
+        Ok(result)
+    }
+
+    /// Redirects the output of a renderer created for stdout to `writer`.
+    fn with_writer<W: Write + Send + 'static>(self, writer: W) -> Result<Self> {
+        let mut sink = Box::new(OutputSink {
+            writer: Box::new(writer),
+        });
+        {
+            let handle = self
+                .handle
+                .lock()
+                .map_err(|_| TesseractError::MutexLockError)?;
+            let context = &mut *sink as *mut OutputSink as *mut c_void;
+            if unsafe { TessRsResultRendererSetOutput(*handle, write_output, context) } == 0 {
+                return Err(TesseractError::RenderError);
+            }
+        }
+        self.outputs
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?
+            .push(sink);
+        Ok(self)
+    }
+
+    fn create(
+        outputbase: &str,
+        create: unsafe extern "C" fn(*const c_char) -> *mut c_void,
+    ) -> Result<Self> {
+        let outputbase = to_cstring(outputbase)?;
+        Self::from_handle(unsafe { create(outputbase.as_ptr()) })
+    }
+
+    fn from_handle(handle: *mut c_void) -> Result<Self> {
         if handle.is_null() {
             Err(TesseractError::NullPointerError)
         } else {
             Ok(TessResultRenderer {
                 handle: Arc::new(Mutex::new(handle)),
+                outputs: Mutex::new(Vec::new()),
             })
         }
     }
 
     /// Begins a new document.
     ///
+    /// `TesseractAPI::begin_document` wraps the begin, add and end calls in a `Document`
+    /// that makes them in order and reports failures as errors.
+    ///
     /// # Arguments
     ///
     /// * `title` - Title of the document.
@@ -91,13 +408,20 @@ impl TessResultRenderer {
     ///
     /// Returns `true` if the document was created successfully, otherwise returns `false`.
     pub fn begin_document(&self, title: &str) -> bool {
-        let title = CString::new(title).unwrap();
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessResultRendererBeginDocument(*handle, title.as_ptr()) != 0 }
+        let Ok(title) = to_cstring(title) else {
+            return false;
+        };
+        self.render(|handle| unsafe {
+            TessResultRendererBeginDocument(handle, title.as_ptr()) != 0
+        })
+        .unwrap_or(false)
     }
 
     /// Adds an image to the document.
     ///
+    /// The image currently set on `api` is recognized first if it has not been recognized
+    /// yet, so calling `recognize` beforehand is optional. The document must have been begun.
+    ///
     /// # Arguments
     ///
     /// * `api` - The TesseractAPI instance.
@@ -106,9 +430,15 @@ impl TessResultRenderer {
     ///
     /// Returns `true` if the image was added successfully, otherwise returns `false`.
     pub fn add_image(&self, api: &TesseractAPI) -> bool {
-        let api_handle = api.handle.lock().unwrap();
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessResultRendererAddImage(*handle, *api_handle) != 0 }
+        // The engine is locked before the renderer, in the same order as
+        // `TesseractAPI::process_pages_with`.
+        let Ok(api_handle) = api.handle.lock() else {
+            return false;
+        };
+        // The renderer reads the results through the text getters.
+        api.results.recognize_if_needed();
+        self.render(|handle| unsafe { TessResultRendererAddImage(handle, *api_handle) != 0 })
+            .unwrap_or(false)
     }
 
     /// Ends the document.
@@ -117,8 +447,8 @@ impl TessResultRenderer {
     ///
     /// Returns `true` if the document was ended successfully, otherwise returns `false`.
     pub fn end_document(&self) -> bool {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessResultRendererEndDocument(*handle) != 0 }
+        self.render(|handle| unsafe { TessResultRendererEndDocument(handle) != 0 })
+            .unwrap_or(false)
     }
 
     /// Gets the extension of the document.
@@ -127,7 +457,10 @@ impl TessResultRenderer {
     ///
     /// Returns the extension as a `String` if successful, otherwise returns an error.
     pub fn get_extension(&self) -> Result<String> {
-        let handle = self.handle.lock().unwrap();
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         let ext_ptr = unsafe { TessResultRendererExtention(*handle) };
         if ext_ptr.is_null() {
             Err(TesseractError::NullPointerError)
@@ -143,7 +476,10 @@ impl TessResultRenderer {
     ///
     /// Returns the title as a `String` if successful, otherwise returns an error.
     pub fn get_title(&self) -> Result<String> {
-        let handle = self.handle.lock().unwrap();
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
         let title_ptr = unsafe { TessResultRendererTitle(*handle) };
         if title_ptr.is_null() {
             Err(TesseractError::NullPointerError)
@@ -157,29 +493,52 @@ impl TessResultRenderer {
     ///
     /// # Returns
     ///
-    /// Returns the number of images as an `i32`.
+    /// Returns the number of images as an `i32`, or 0 if the renderer's lock is poisoned.
     pub fn get_image_num(&self) -> i32 {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessResultRendererImageNum(*handle) }
+        self.handle
+            .lock()
+            .map_or(0, |handle| unsafe { TessResultRendererImageNum(*handle) })
     }
 }
 
 impl Drop for TessResultRenderer {
     fn drop(&mut self) {
-        let handle = self.handle.lock().unwrap();
-        unsafe { TessDeleteResultRenderer(*handle) };
+        if let Ok(handle) = self.handle.lock() {
+            if !handle.is_null() {
+                unsafe {
+                    TessRsResultRendererReleaseOutput(*handle);
+                    TessDeleteResultRenderer(*handle);
+                };
+            }
+        }
     }
 }
 
+/// Passes output from the shim's stream to the writer of an in-memory renderer.
+extern "C" fn write_output(context: *mut c_void, data: *const c_char, size: usize) -> c_int {
+    let sink = unsafe { &mut *(context as *mut OutputSink) };
+    let bytes = unsafe { std::slice::from_raw_parts(data as *const u8, size) };
+    // Unwinding into C++ is undefined behavior, so a panicking writer counts as a failure.
+    catch_unwind(AssertUnwindSafe(|| sink.writer.write_all(bytes)))
+        .map_or(0, |written| written.is_ok() as c_int)
+}
+
 extern "C" {
     pub fn TessTextRendererCreate(outputbase: *const c_char) -> *mut c_void;
     pub fn TessHOcrRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessAltoRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessTsvRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessUnlvRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessBoxTextRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessLSTMBoxRendererCreate(outputbase: *const c_char) -> *mut c_void;
+    pub fn TessWordStrBoxRendererCreate(outputbase: *const c_char) -> *mut c_void;
     pub fn TessPDFRendererCreate(
         outputbase: *const c_char,
         datadir: *const c_char,
         textonly: c_int,
     ) -> *mut c_void;
     pub fn TessDeleteResultRenderer(renderer: *mut c_void);
+    pub fn TessResultRendererInsert(renderer: *mut c_void, next: *mut c_void);
     pub fn TessResultRendererBeginDocument(renderer: *mut c_void, title: *const c_char) -> c_int;
     pub fn TessResultRendererAddImage(renderer: *mut c_void, api: *mut c_void) -> c_int;
     pub fn TessResultRendererEndDocument(renderer: *mut c_void) -> c_int;
@@ -187,3 +546,31 @@ extern "C" {
     pub fn TessResultRendererTitle(renderer: *mut c_void) -> *const c_char;
     pub fn TessResultRendererImageNum(renderer: *mut c_void) -> c_int;
 }
+
+// Output streams compiled from `shim/result_renderer.cpp` by the build script.
+#[cfg(feature = "build-tesseract")]
+extern "C" {
+    fn TessRsResultRendererSetOutput(
+        renderer: *mut c_void,
+        write: extern "C" fn(*mut c_void, *const c_char, usize) -> c_int,
+        context: *mut c_void,
+    ) -> c_int;
+    fn TessRsResultRendererFlushOutput(renderer: *mut c_void);
+    fn TessRsResultRendererReleaseOutput(renderer: *mut c_void);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_buffer_clones_share_contents() {
+        let buffer = OutputBuffer::new();
+        let mut writer = buffer.clone();
+        writer.write_all(b"%PDF-1.5\n").unwrap();
+        writer.write_all(b"%%EOF\n").unwrap();
+        assert_eq!(buffer.contents(), b"%PDF-1.5\n%%EOF\n");
+        assert_eq!(buffer.take(), b"%PDF-1.5\n%%EOF\n");
+        assert!(buffer.contents().is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::api::TesseractAPI;
+use crate::error::{Result, TesseractError};
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::MutexGuard;
+
+/// Tracks the recognition results held by an engine.
+///
+/// Clearing the engine, setting a new image or recognizing again frees the results that
+/// iterators read from. Each of these calls advances the generation while the engine is
+/// locked, and iterators compare it with the generation they were created at under the
+/// same lock.
+#[derive(Debug, Default)]
+pub(crate) struct Results {
+    generation: AtomicU64,
+    recognized: AtomicBool,
+}
+
+impl Results {
+    /// Records that the results were cleared or replaced without recognizing.
+    pub(crate) fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.recognized.store(false, Ordering::SeqCst);
+    }
+
+    /// Records that recognition ran, replacing any earlier results.
+    pub(crate) fn recognize(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.recognized.store(true, Ordering::SeqCst);
+    }
+
+    /// Records a call that recognizes the image only if that has not happened yet, as the
+    /// text getters do.
+    pub(crate) fn recognize_if_needed(&self) {
+        if !self.recognized.swap(true, Ordering::SeqCst) {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Checks whether the current image has been recognized.
+    pub(crate) fn is_recognized(&self) -> bool {
+        self.recognized.load(Ordering::SeqCst)
+    }
+
+    /// Records that the results were edited in place, returning the new generation.
+    pub(crate) fn edit(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+}
+
+/// The results an iterator was created from.
+pub(crate) struct ResultsGuard<'api> {
+    api: Option<&'api TesseractAPI>,
+    generation: AtomicU64,
+}
+
+impl<'api> ResultsGuard<'api> {
+    /// Creates a guard for the current results of `api`, which must be locked by the caller.
+    pub(crate) fn new(api: &'api TesseractAPI) -> Self {
+        ResultsGuard {
+            api: Some(api),
+            generation: AtomicU64::new(api.results.generation()),
+        }
+    }
+
+    /// Creates a guard for an iterator that was not created by a `TesseractAPI`, which
+    /// cannot be checked.
+    pub(crate) fn unchecked() -> Self {
+        ResultsGuard {
+            api: None,
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Locks the engine and checks that the results are still the ones the iterator reads.
+    ///
+    /// # Returns
+    ///
+    /// Returns the engine lock, to be held while the iterator is used, if the results are
+    /// unchanged, otherwise returns `TesseractError::IteratorInvalidated`.
+    pub(crate) fn lock(&self) -> Result<Option<MutexGuard<'api, *mut c_void>>> {
+        let Some(api) = self.api else {
+            return Ok(None);
+        };
+        let handle = api
+            .handle
+            .lock()
+            .map_err(|_| TesseractError::MutexLockError)?;
+        if api.results.generation() != self.generation.load(Ordering::SeqCst) {
+            return Err(TesseractError::IteratorInvalidated);
+        }
+        Ok(Some(handle))
+    }
+
+    /// Records an edit of the results made through the iterator holding this guard, which
+    /// stays valid while every other iterator is invalidated. The engine must be locked by
+    /// the caller.
+    pub(crate) fn edit(&self) {
+        if let Some(api) = self.api {
+            self.generation.store(api.results.edit(), Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_changes() {
+        let results = Results::default();
+        let start = results.generation();
+        results.recognize_if_needed();
+        assert_eq!(results.generation(), start + 1);
+        // The text getters reuse results that already exist.
+        results.recognize_if_needed();
+        assert_eq!(results.generation(), start + 1);
+        results.recognize();
+        assert_eq!(results.generation(), start + 2);
+        results.invalidate();
+        results.recognize_if_needed();
+        assert_eq!(results.generation(), start + 4);
+        // Edits keep the results, so the text getters do not recognize again.
+        assert_eq!(results.edit(), start + 5);
+        results.recognize_if_needed();
+        assert_eq!(results.generation(), start + 5);
+    }
+}
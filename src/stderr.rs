@@ -0,0 +1,98 @@
+use std::io::Write;
+
+/// Runs `f` while capturing everything written to stderr.
+///
+/// Tesseract reports why a call failed (a missing traineddata file, an unreadable config file)
+/// only through `tprintf`, which writes to stderr. File descriptor 2 is pointed at a temporary
+/// file for the duration of `f` so the messages can be attached to the returned error.
+///
+/// # Returns
+///
+/// Returns the result of `f` and the captured output.
+#[cfg(unix)]
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    use std::io::{Read, Seek, SeekFrom};
+    use std::os::unix::io::AsRawFd;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    // File descriptor 2 is process-wide, so only one capture may be active at a time.
+    static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let _lock = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = std::env::temp_dir().join(format!(
+        "tesseract-rs-stderr-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut file = match std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&path)
+    {
+        Ok(file) => file,
+        Err(_) => return (f(), String::new()),
+    };
+    let _ = std::fs::remove_file(&path);
+
+    let saved = unsafe {
+        libc::fflush(std::ptr::null_mut());
+        libc::dup(libc::STDERR_FILENO)
+    };
+    if saved < 0 || unsafe { libc::dup2(file.as_raw_fd(), libc::STDERR_FILENO) } < 0 {
+        if saved >= 0 {
+            unsafe { libc::close(saved) };
+        }
+        return (f(), String::new());
+    }
+
+    let result = f();
+
+    unsafe {
+        libc::fflush(std::ptr::null_mut());
+        libc::dup2(saved, libc::STDERR_FILENO);
+        libc::close(saved);
+    }
+    let mut bytes = Vec::new();
+    if file.seek(SeekFrom::Start(0)).is_ok() {
+        let _ = file.read_to_end(&mut bytes);
+    }
+    (result, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Runs `f` without capturing; stderr redirection is only supported on Unix.
+#[cfg(not(unix))]
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    (f(), String::new())
+}
+
+/// Writes captured output back to stderr after a successful call.
+pub(crate) fn replay(output: &str) {
+    if !output.is_empty() {
+        let _ = std::io::stderr().write_all(output.as_bytes());
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_collects_stderr() {
+        let (value, output) = capture(|| {
+            let message = b"captured message\n";
+            unsafe {
+                libc::write(
+                    libc::STDERR_FILENO,
+                    message.as_ptr() as *const libc::c_void,
+                    message.len(),
+                )
+            };
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(output, "captured message\n");
+    }
+}
@@ -0,0 +1,236 @@
+use crate::builder::TesseractBuilder;
+use crate::enums::TessOcrEngineMode;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Index of the unicharset in the traineddata offset table.
+const TESSDATA_UNICHARSET: usize = 1;
+/// Index of the legacy engine's shape templates.
+const TESSDATA_INTTEMP: usize = 3;
+/// Index of the LSTM network.
+const TESSDATA_LSTM: usize = 17;
+
+/// Components present in a `.traineddata` file, read from its offset table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TraineddataComponents {
+    pub(crate) legacy: bool,
+    pub(crate) lstm: bool,
+}
+
+impl TraineddataComponents {
+    /// Reads the component table of a traineddata file in memory.
+    ///
+    /// The file starts with the number of entries as a 32 bit integer followed by one 64 bit
+    /// offset per entry, where a negative offset marks a missing entry. Both byte orders are
+    /// accepted, as Tesseract does.
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        Self::from_header(data, data.len() as u64)
+    }
+
+    /// Reads the component table of a traineddata file on disk.
+    ///
+    /// Only the entry count and the offset table are read, not the components themselves.
+    pub(crate) fn read(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+        let mut header = vec![0; 4];
+        file.read_exact(&mut header).ok()?;
+        let (count, _) = entry_count(&header)?;
+        header.resize(4 + count * 8, 0);
+        file.read_exact(&mut header[4..]).ok()?;
+        Self::from_header(&header, len)
+    }
+
+    /// Reads the component table from the start of a traineddata file of `len` bytes.
+    fn from_header(data: &[u8], len: u64) -> Option<Self> {
+        let (count, swap) = entry_count(data)?;
+        let offsets: Vec<i64> = (0..count)
+            .map(|i| {
+                let bytes: [u8; 8] = data.get(4 + i * 8..12 + i * 8)?.try_into().ok()?;
+                Some(if swap {
+                    i64::from_be_bytes(bytes)
+                } else {
+                    i64::from_le_bytes(bytes)
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        let present = |index: usize| -> bool {
+            let Some(&start) = offsets.get(index) else {
+                return false;
+            };
+            if start < 0 {
+                return false;
+            }
+            let end = offsets[index + 1..]
+                .iter()
+                .copied()
+                .find(|&offset| offset >= 0)
+                .unwrap_or(len as i64);
+            end > start
+        };
+        Some(TraineddataComponents {
+            legacy: present(TESSDATA_UNICHARSET) && present(TESSDATA_INTTEMP),
+            lstm: present(TESSDATA_LSTM),
+        })
+    }
+
+    /// Checks whether the components can run an engine mode.
+    pub(crate) fn supports(&self, oem: TessOcrEngineMode) -> bool {
+        match oem {
+            TessOcrEngineMode::OEM_TESSERACT_ONLY => self.legacy,
+            TessOcrEngineMode::OEM_LSTM_ONLY => self.lstm,
+            TessOcrEngineMode::OEM_TESSERACT_LSTM_COMBINED => self.legacy && self.lstm,
+            TessOcrEngineMode::OEM_DEFAULT => self.legacy || self.lstm,
+        }
+    }
+}
+
+/// Reads the number of entries in the offset table and whether it is big-endian.
+fn entry_count(data: &[u8]) -> Option<(usize, bool)> {
+    let raw_count: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let little = i32::from_le_bytes(raw_count);
+    if (1..=1000).contains(&little) {
+        return Some((little as usize, false));
+    }
+    let big = i32::from_be_bytes(raw_count);
+    (1..=1000).contains(&big).then_some((big as usize, true))
+}
+
+/// Finds a requested language whose traineddata cannot run the builder's legacy engine mode.
+///
+/// Languages whose traineddata cannot be located or read are skipped.
+pub(crate) fn language_missing_legacy(builder: &TesseractBuilder) -> Option<String> {
+    if !matches!(
+        builder.oem,
+        TessOcrEngineMode::OEM_TESSERACT_ONLY | TessOcrEngineMode::OEM_TESSERACT_LSTM_COMBINED
+    ) {
+        return None;
+    }
+    if let Some(data) = &builder.traineddata {
+        let components = TraineddataComponents::parse(data)?;
+        return (!components.supports(builder.oem)).then(|| builder.language_string());
+    }
+    let datapath = datapath(builder)?;
+    builder.language_string().split('+').find_map(|language| {
+        let components = TraineddataComponents::read(&traineddata_path(&datapath, language))?;
+        (!components.supports(builder.oem)).then(|| language.to_owned())
+    })
+}
+
+/// Gets the directory Tesseract loads the builder's traineddata from.
+fn datapath(builder: &TesseractBuilder) -> Option<String> {
+    builder
+        .datapath
+        .clone()
+        .or_else(|| std::env::var("TESSDATA_PREFIX").ok())
+}
+
+fn traineddata_path(datapath: &str, language: &str) -> PathBuf {
+    PathBuf::from(datapath).join(format!("{}.traineddata", language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn traineddata(entries: &[Option<usize>], big_endian: bool) -> Vec<u8> {
+        let count = entries.len() as i32;
+        let header = 4 + entries.len() * 8;
+        let mut data = if big_endian {
+            count.to_be_bytes().to_vec()
+        } else {
+            count.to_le_bytes().to_vec()
+        };
+        let mut offset = header as i64;
+        let mut body = Vec::new();
+        for entry in entries {
+            let value = match entry {
+                Some(size) => {
+                    let current = offset;
+                    offset += *size as i64;
+                    body.resize(body.len() + size, 0);
+                    current
+                }
+                None => -1,
+            };
+            data.extend(if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            });
+        }
+        data.extend(body);
+        data
+    }
+
+    #[test]
+    fn test_lstm_only_traineddata() {
+        let mut entries = vec![None; 24];
+        entries[0] = Some(10);
+        entries[17] = Some(100);
+        entries[21] = Some(20);
+        let components = TraineddataComponents::parse(&traineddata(&entries, false)).unwrap();
+        assert_eq!(
+            components,
+            TraineddataComponents {
+                legacy: false,
+                lstm: true
+            }
+        );
+        assert!(components.supports(TessOcrEngineMode::OEM_LSTM_ONLY));
+        assert!(components.supports(TessOcrEngineMode::OEM_DEFAULT));
+        assert!(!components.supports(TessOcrEngineMode::OEM_TESSERACT_ONLY));
+        assert!(!components.supports(TessOcrEngineMode::OEM_TESSERACT_LSTM_COMBINED));
+    }
+
+    #[test]
+    fn test_full_traineddata_big_endian() {
+        let mut entries = vec![Some(4); 24];
+        entries[10] = None;
+        let components = TraineddataComponents::parse(&traineddata(&entries, true)).unwrap();
+        assert!(components.legacy && components.lstm);
+        assert!(components.supports(TessOcrEngineMode::OEM_TESSERACT_LSTM_COMBINED));
+    }
+
+    #[test]
+    fn test_empty_entry_is_missing() {
+        let mut entries = vec![None; 24];
+        entries[1] = Some(8);
+        entries[3] = Some(0);
+        let components = TraineddataComponents::parse(&traineddata(&entries, false)).unwrap();
+        assert!(!components.legacy);
+    }
+
+    #[test]
+    fn test_read_header_from_file() {
+        let mut entries = vec![None; 24];
+        entries[1] = Some(8);
+        entries[3] = Some(16);
+        entries[17] = Some(4096);
+        let data = traineddata(&entries, true);
+        let path = std::env::temp_dir().join("tesseract-rs-header.traineddata");
+        std::fs::write(&path, &data).unwrap();
+        assert_eq!(
+            TraineddataComponents::read(&path),
+            TraineddataComponents::parse(&data)
+        );
+        assert!(TraineddataComponents::read(&path).unwrap().legacy);
+
+        // The last component ends at the end of the file, which the header alone cannot tell.
+        std::fs::write(&path, &data[..4 + 24 * 8]).unwrap();
+        assert!(!TraineddataComponents::read(&path).unwrap().lstm);
+        std::fs::write(&path, &data[..100]).unwrap();
+        assert_eq!(TraineddataComponents::read(&path), None);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(TraineddataComponents::read(&path), None);
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert_eq!(TraineddataComponents::parse(&[]), None);
+        assert_eq!(TraineddataComponents::parse(&[0, 0, 0, 0]), None);
+        assert_eq!(TraineddataComponents::parse(&[2, 0, 0, 0, 1, 2]), None);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::error::{Result, TesseractError};
+use crate::page::BoundingBox;
+
+/// Column names of the header line written by the TSV renderer.
+const TSV_HEADER: &str =
+    "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext";
+
+/// Hierarchy level of a TSV row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TsvLevel {
+    Page = 1,
+    Block = 2,
+    Paragraph = 3,
+    Line = 4,
+    Word = 5,
+}
+
+impl TsvLevel {
+    /// Converts the value of the `level` column.
+    ///
+    /// # Returns
+    ///
+    /// Returns the level, or `None` for values outside 1-5.
+    pub fn from_int(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(TsvLevel::Page),
+            2 => Some(TsvLevel::Block),
+            3 => Some(TsvLevel::Paragraph),
+            4 => Some(TsvLevel::Line),
+            5 => Some(TsvLevel::Word),
+            _ => None,
+        }
+    }
+}
+
+/// A row of the TSV output of `TesseractAPI::get_tsv_text` or the TSV renderer.
+///
+/// ```
+/// use tesseract_rs::{TsvLevel, TsvRow};
+///
+/// let rows = TsvRow::parse("5\t1\t1\t1\t1\t1\t36\t92\t60\t24\t96.5\tHello\n")?;
+/// assert_eq!(rows[0].level, TsvLevel::Word);
+/// assert_eq!(rows[0].text, "Hello");
+/// assert_eq!(rows[0].bbox().right, 96);
+/// # Ok::<(), tesseract_rs::TesseractError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsvRow {
+    pub level: TsvLevel,
+    /// One-based page number.
+    pub page_num: u32,
+    pub block_num: u32,
+    pub par_num: u32,
+    pub line_num: u32,
+    pub word_num: u32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Word confidence (0-100); `None` for rows above the word level, written as `-1`.
+    pub confidence: Option<f32>,
+    pub text: String,
+}
+
+impl TsvRow {
+    /// Parses TSV output into rows.
+    ///
+    /// The header line written by the TSV renderer and blank lines are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - TSV text.
+    ///
+    /// # Returns
+    ///
+    /// Returns the rows in order if successful, otherwise returns a `ParseError` naming the
+    /// offending line.
+    pub fn parse(text: &str) -> Result<Vec<Self>> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with("level\t"))
+            .map(|(index, line)| {
+                Self::from_line(line).map_err(|e| match e {
+                    TesseractError::ParseError { format, message } => TesseractError::ParseError {
+                        format,
+                        message: format!("line {}: {}", index + 1, message),
+                    },
+                    other => other,
+                })
+            })
+            .collect()
+    }
+
+    /// Parses a single TSV line.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - A line with the twelve tab-separated columns.
+    ///
+    /// # Returns
+    ///
+    /// Returns the row if successful, otherwise returns a `ParseError`.
+    pub fn from_line(line: &str) -> Result<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let columns: Vec<&str> = line.splitn(12, '\t').collect();
+        if columns.len() < 11 {
+            return Err(parse_error(format!(
+                "expected 12 columns, found {}",
+                columns.len()
+            )));
+        }
+        let number = |index: usize| -> Result<i32> {
+            columns[index].trim().parse().map_err(|_| {
+                parse_error(format!(
+                    "invalid {} '{}'",
+                    column_name(index),
+                    columns[index]
+                ))
+            })
+        };
+        let count = |index: usize| -> Result<u32> {
+            u32::try_from(number(index)?)
+                .map_err(|_| parse_error(format!("negative {}", column_name(index))))
+        };
+        let level = TsvLevel::from_int(number(0)?)
+            .ok_or_else(|| parse_error(format!("invalid level '{}'", columns[0])))?;
+        let confidence: f32 = columns[10]
+            .trim()
+            .parse()
+            .map_err(|_| parse_error(format!("invalid conf '{}'", columns[10])))?;
+        Ok(TsvRow {
+            level,
+            page_num: count(1)?,
+            block_num: count(2)?,
+            par_num: count(3)?,
+            line_num: count(4)?,
+            word_num: count(5)?,
+            left: number(6)?,
+            top: number(7)?,
+            width: number(8)?,
+            height: number(9)?,
+            confidence: (confidence >= 0.0).then_some(confidence),
+            text: columns.get(11).copied().unwrap_or("").to_owned(),
+        })
+    }
+
+    /// Formats the row as a TSV line, without a line terminator.
+    ///
+    /// # Returns
+    ///
+    /// Returns the twelve tab-separated columns.
+    pub fn to_line(&self) -> String {
+        let confidence = match self.confidence {
+            Some(confidence) => confidence.to_string(),
+            None => "-1".to_owned(),
+        };
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.level as i32,
+            self.page_num,
+            self.block_num,
+            self.par_num,
+            self.line_num,
+            self.word_num,
+            self.left,
+            self.top,
+            self.width,
+            self.height,
+            confidence,
+            self.text
+        )
+    }
+
+    /// Gets the header line of the TSV format.
+    ///
+    /// # Returns
+    ///
+    /// Returns the tab-separated column names.
+    pub fn header() -> &'static str {
+        TSV_HEADER
+    }
+
+    /// Gets the bounding box of the row.
+    ///
+    /// # Returns
+    ///
+    /// Returns the box spanning `left`, `top`, `width` and `height`.
+    pub fn bbox(&self) -> BoundingBox {
+        BoundingBox::new(
+            self.left,
+            self.top,
+            self.left + self.width,
+            self.top + self.height,
+        )
+    }
+}
+
+fn column_name(index: usize) -> &'static str {
+    TSV_HEADER.split('\t').nth(index).unwrap_or("column")
+}
+
+fn parse_error(message: String) -> TesseractError {
+    TesseractError::ParseError {
+        format: "TSV",
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+                       1\t1\t0\t0\t0\t0\t0\t0\t400\t200\t-1\t\n\
+                       2\t1\t1\t0\t0\t0\t36\t92\t300\t30\t-1\t\n\
+                       4\t1\t1\t1\t1\t0\t36\t92\t300\t30\t-1\t\n\
+                       5\t1\t1\t1\t1\t1\t36\t92\t60\t24\t96.5\tHello\n\
+                       5\t1\t1\t1\t1\t2\t110\t92\t226\t30\t91.5\tw\u{f6}rld!\n";
+
+    #[test]
+    fn test_parse_rows() {
+        let rows = TsvRow::parse(TSV).unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].level, TsvLevel::Page);
+        assert_eq!(rows[0].confidence, None);
+        assert_eq!(rows[0].text, "");
+        assert_eq!(rows[2].level, TsvLevel::Line);
+        assert_eq!(
+            rows[3],
+            TsvRow {
+                level: TsvLevel::Word,
+                page_num: 1,
+                block_num: 1,
+                par_num: 1,
+                line_num: 1,
+                word_num: 1,
+                left: 36,
+                top: 92,
+                width: 60,
+                height: 24,
+                confidence: Some(96.5),
+                text: "Hello".to_owned(),
+            }
+        );
+        assert_eq!(rows[4].text, "w\u{f6}rld!");
+        assert_eq!(rows[4].bbox(), BoundingBox::new(110, 92, 336, 122));
+    }
+
+    #[test]
+    fn test_to_line_round_trip() {
+        let rows = TsvRow::parse(TSV).unwrap();
+        let written: String = std::iter::once(TsvRow::header().to_owned())
+            .chain(rows.iter().map(TsvRow::to_line))
+            .map(|line| line + "\n")
+            .collect();
+        assert_eq!(written, TSV);
+        assert_eq!(TsvRow::parse(&written).unwrap(), rows);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = TsvRow::parse(
+            "5\t1\t1\t1\t1\t1\t36\t92\t60\t24\t96\tok\n7\t1\t1\t1\t1\t1\t0\t0\t1\t1\t-1\t\n",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse TSV: line 2: invalid level '7'"
+        );
+        assert!(TsvRow::from_line("5\t1\t1").is_err());
+        assert!(TsvRow::from_line("5\t1\t1\t1\t1\t1\tx\t92\t60\t24\t96\tok").is_err());
+    }
+}
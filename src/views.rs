@@ -0,0 +1,163 @@
+use crate::enums::{TessPageIteratorLevel, TessPolyBlockType};
+use crate::error::Result;
+use crate::page::{text_at, Baseline, BoundingBox};
+use crate::result_iterator::ResultIterator;
+
+/// A layout block read by `ResultIterator::blocks`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockView {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: f32,
+    pub block_type: TessPolyBlockType,
+}
+
+/// A text line read by `ResultIterator::lines`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineView {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub baseline: Option<Baseline>,
+    pub confidence: f32,
+}
+
+/// A word read by `ResultIterator::words`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordView {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub baseline: Option<Baseline>,
+    pub confidence: f32,
+    pub is_from_dictionary: bool,
+    pub is_numeric: bool,
+}
+
+/// A symbol read by `ResultIterator::symbols`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolView {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub confidence: f32,
+}
+
+impl BlockView {
+    pub(crate) fn read(iterator: &ResultIterator) -> Result<Self> {
+        let level = TessPageIteratorLevel::RIL_BLOCK;
+        Ok(BlockView {
+            text: text_at(iterator, level)?,
+            bbox: iterator.get_bounding_box(level)?.into(),
+            confidence: iterator.confidence(level)?,
+            block_type: iterator.block_type()?,
+        })
+    }
+}
+
+impl LineView {
+    pub(crate) fn read(iterator: &ResultIterator) -> Result<Self> {
+        let level = TessPageIteratorLevel::RIL_TEXTLINE;
+        Ok(LineView {
+            text: text_at(iterator, level)?,
+            bbox: iterator.get_bounding_box(level)?.into(),
+            baseline: iterator.baseline(level).ok().map(Baseline::from),
+            confidence: iterator.confidence(level)?,
+        })
+    }
+}
+
+impl WordView {
+    pub(crate) fn read(iterator: &ResultIterator) -> Result<Self> {
+        let level = TessPageIteratorLevel::RIL_WORD;
+        Ok(WordView {
+            text: text_at(iterator, level)?,
+            bbox: iterator.get_bounding_box(level)?.into(),
+            baseline: iterator.baseline(level).ok().map(Baseline::from),
+            confidence: iterator.confidence(level)?,
+            is_from_dictionary: iterator.word_is_from_dictionary()?,
+            is_numeric: iterator.word_is_numeric()?,
+        })
+    }
+}
+
+impl SymbolView {
+    pub(crate) fn read(iterator: &ResultIterator) -> Result<Self> {
+        let level = TessPageIteratorLevel::RIL_SYMBOL;
+        Ok(SymbolView {
+            text: text_at(iterator, level)?,
+            bbox: iterator.get_bounding_box(level)?.into(),
+            confidence: iterator.confidence(level)?,
+        })
+    }
+}
+
+/// Iterator over the elements of one level of a page, created by `ResultIterator::blocks`,
+/// `lines`, `words` and `symbols`.
+///
+/// The first call to `next` moves the underlying `ResultIterator` to the start of the page,
+/// so the first element is always included. Below the block level, image and other
+/// non-text blocks are skipped. Iteration stops after the last element or after the first
+/// error.
+///
+/// The elements are read at the position of the underlying `ResultIterator`, so only one of
+/// its adapters should be advanced at a time; walk two levels in step with two iterators
+/// from `TesseractAPI::get_iterator`.
+pub struct Elements<'a, T> {
+    iterator: &'a ResultIterator<'a>,
+    level: TessPageIteratorLevel,
+    read: fn(&ResultIterator) -> Result<T>,
+    started: bool,
+    finished: bool,
+}
+
+impl<'a, T> Elements<'a, T> {
+    pub(crate) fn new(
+        iterator: &'a ResultIterator<'a>,
+        level: TessPageIteratorLevel,
+        read: fn(&ResultIterator) -> Result<T>,
+    ) -> Self {
+        Elements {
+            iterator,
+            level,
+            read,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Moves to the next element, returning `false` at the end of the page.
+    fn advance(&mut self) -> Result<bool> {
+        let mut found = if self.started {
+            self.iterator.next(self.level)?
+        } else {
+            self.started = true;
+            self.iterator.begin()?;
+            !self.iterator.is_empty(TessPageIteratorLevel::RIL_BLOCK)?
+        };
+        // Image and other non-text blocks have no lines, words or symbols to stop at.
+        while found && self.iterator.is_empty(self.level)? {
+            found = self.iterator.next(TessPageIteratorLevel::RIL_BLOCK)?;
+        }
+        Ok(found)
+    }
+}
+
+impl<T> Iterator for Elements<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let item = match self.advance() {
+            Ok(true) => (self.read)(self.iterator),
+            Ok(false) => {
+                self.finished = true;
+                return None;
+            }
+            Err(e) => Err(e),
+        };
+        self.finished = item.is_err();
+        Some(item)
+    }
+}
+
+impl<T> std::iter::FusedIterator for Elements<'_, T> {}
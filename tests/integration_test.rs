@@ -2,7 +2,7 @@ use image::{DynamicImage, ImageBuffer, Luma};
 use imageproc::contrast::adaptive_threshold;
 use imageproc::filter::filter3x3;
 use std::path::PathBuf;
-use tesseract_rs::TesseractAPI;
+use tesseract_rs::{Pix, TesseractAPI};
 
 fn get_default_tessdata_dir() -> PathBuf {
     if cfg!(target_os = "macos") {
@@ -573,3 +573,1622 @@ fn test_dynamic_image_setting() {
     assert!(!text.trim().is_empty(), "OCR result is empty");
     assert!(text.trim().contains("9"), "Expected digit '9' not found");
 }
+
+#[test]
+fn test_recognize_page_structure() {
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+
+    let page = api.recognize_page().expect("Failed to recognize page");
+    assert!(!page.blocks.is_empty());
+    assert!(page.text().contains("sample text"));
+
+    let words: Vec<_> = page.words().collect();
+    assert!(words.iter().any(|w| w.text == "sample"));
+    for word in &words {
+        assert!(word.bbox.left >= 0 && word.bbox.right <= width as i32);
+        assert!(word.bbox.top >= 0 && word.bbox.bottom <= height as i32);
+        assert!(word.confidence > 0.0);
+        let symbols: String = word.symbols.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(symbols, word.text);
+    }
+
+    // The page is owned and survives clearing the engine.
+    let snapshot = page.clone();
+    api.clear().expect("Failed to clear");
+    assert_eq!(snapshot, page);
+}
+
+/// Places `sample_text.png` above or below a noisy photograph-like figure, so automatic
+/// page segmentation finds an image block next to the text.
+fn load_test_image_with_figure(figure_above: bool) -> (Vec<u8>, u32, u32) {
+    let text = image::open(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("test_images")
+            .join("sample_text.png"),
+    )
+    .expect("Failed to open test image")
+    .to_rgb8();
+    let (width, text_height) = text.dimensions();
+    let width = width.max(600);
+    let height = text_height + 480;
+
+    let mut page = image::RgbImage::from_pixel(width, height, image::Rgb([255, 255, 255]));
+    let (text_top, figure_top) = if figure_above {
+        (480, 40)
+    } else {
+        (0, text_height + 80)
+    };
+    image::imageops::replace(&mut page, &text, 0, i64::from(text_top));
+    let mut seed = 0x2545_f491_u32;
+    for y in figure_top..figure_top + 360 {
+        for x in 40..width - 40 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let noise = (seed >> 16) % 96;
+            let shade = (x * 96 / width + noise) as u8;
+            page.put_pixel(x, y, image::Rgb([shade, shade, shade]));
+        }
+    }
+    (page.into_raw(), width, height)
+}
+
+#[test]
+fn test_recognize_page_with_image_region() {
+    use tesseract_rs::{TessPageSegMode, TessPolyBlockType};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_page_seg_mode(TessPageSegMode::PSM_AUTO)
+        .expect("Failed to set page segmentation mode");
+
+    let (image_data, width, height) = load_test_image_with_figure(false);
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+
+    let page = api.recognize_page().expect("Failed to recognize page");
+    assert!(page.text().contains("sample text"));
+    assert!(page.words().any(|w| w.text == "sample"));
+
+    let is_text = |block_type| {
+        !matches!(
+            block_type,
+            TessPolyBlockType::PT_FLOWING_IMAGE
+                | TessPolyBlockType::PT_HEADING_IMAGE
+                | TessPolyBlockType::PT_PULLOUT_IMAGE
+                | TessPolyBlockType::PT_HORZ_LINE
+                | TessPolyBlockType::PT_VERT_LINE
+                | TessPolyBlockType::PT_NOISE
+        )
+    };
+    let figures: Vec<_> = page
+        .blocks
+        .iter()
+        .filter(|block| !is_text(block.block_type))
+        .collect();
+    assert!(!figures.is_empty(), "The figure should be an image block");
+    for figure in figures {
+        assert!(figure.paragraphs.is_empty());
+        assert!(figure.bbox.top >= 0 && figure.bbox.bottom <= height as i32);
+    }
+}
+
+#[test]
+fn test_pix_roundtrip_and_thresholded_image() {
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let pix = Pix::from_bytes(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to create Pix");
+    assert_eq!(pix.width().unwrap(), width as i32);
+    assert_eq!(pix.height().unwrap(), height as i32);
+    assert_eq!(pix.depth().unwrap(), 32);
+
+    pix.set_resolution(300, 300).unwrap();
+    assert_eq!(pix.resolution().unwrap(), (300, 300));
+
+    let (bytes, bytes_per_pixel) = pix.to_bytes().expect("Failed to convert Pix");
+    assert_eq!(bytes_per_pixel, 3);
+    assert_eq!(bytes, image_data);
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    assert!(matches!(
+        api.set_input_image(&pix),
+        Err(tesseract_rs::TesseractError::UninitializedError)
+    ));
+    assert!(matches!(
+        api.get_input_image(),
+        Err(tesseract_rs::TesseractError::UninitializedError)
+    ));
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_image_2(&pix).expect("Failed to set image");
+    drop(pix);
+
+    let text = api.get_utf8_text().expect("Failed to perform OCR");
+    assert!(text.contains("sample text"));
+
+    let thresholded = api
+        .get_thresholded_image()
+        .expect("Failed to get thresholded image");
+    assert_eq!(thresholded.depth().unwrap(), 1);
+    assert_eq!(thresholded.width().unwrap(), width as i32);
+    let (gray, bytes_per_pixel) = thresholded.to_bytes().expect("Failed to convert Pix");
+    assert_eq!(bytes_per_pixel, 1);
+    assert!(gray.iter().all(|&p| p == 0 || p == 255));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_set_image_from_path_and_bytes() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("test_images");
+    path.push("sample_text.png");
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    api.set_image_from_path(&path)
+        .expect("Failed to set image from path");
+    let text = api.get_utf8_text().expect("Failed to perform OCR");
+    assert!(text.contains("sample text"));
+
+    let decoded = image::open(&path).expect("Failed to open image");
+    for format in [
+        image::ImageFormat::Png,
+        image::ImageFormat::Jpeg,
+        image::ImageFormat::Tiff,
+        image::ImageFormat::Bmp,
+        image::ImageFormat::Pnm,
+    ] {
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(decoded.to_rgb8())
+            .write_to(&mut encoded, format)
+            .expect("Failed to encode image");
+
+        let pix = Pix::read_from_memory(encoded.get_ref()).expect("Failed to decode image");
+        assert_eq!(pix.width().unwrap(), decoded.width() as i32);
+        assert_eq!(pix.height().unwrap(), decoded.height() as i32);
+
+        api.set_image_from_bytes(encoded.get_ref())
+            .expect("Failed to set image from bytes");
+        let text = api.get_utf8_text().expect("Failed to perform OCR");
+        assert!(text.contains("sample text"), "{:?}: {}", format, text);
+    }
+
+    assert!(api.set_image_from_bytes(b"not an image").is_err());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_recognize_multipage_tiff() {
+    use tesseract_rs::{TessResultRenderer, TiffPages};
+    use tiff::encoder::{colortype, Rational, TiffEncoder};
+    use tiff::tags::ResolutionUnit;
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("test_images");
+    path.push("sample_text.png");
+    let gray = image::open(&path).expect("Failed to open image").to_luma8();
+
+    let mut tiff_data = std::io::Cursor::new(Vec::new());
+    let mut encoder = TiffEncoder::new(&mut tiff_data).expect("Failed to create encoder");
+    for _ in 0..3 {
+        let mut page = encoder
+            .new_image::<colortype::Gray8>(gray.width(), gray.height())
+            .expect("Failed to add page");
+        page.resolution(ResolutionUnit::Inch, Rational { n: 300, d: 1 });
+        page.write_data(gray.as_raw())
+            .expect("Failed to write page");
+    }
+    let tiff_data = tiff_data.into_inner();
+
+    let pages: Vec<_> = TiffPages::from_bytes(&tiff_data)
+        .expect("Failed to read TIFF")
+        .collect::<Result<_, _>>()
+        .expect("Failed to decode page");
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0].width().unwrap(), gray.width() as i32);
+    assert_eq!(pages[0].depth().unwrap(), 8);
+    assert_eq!(pages[0].resolution().unwrap(), (300, 300));
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let outputbase = std::env::temp_dir().join("tesseract_rs_multipage");
+    let renderer = TessResultRenderer::new_text_renderer(outputbase.to_str().unwrap())
+        .expect("Failed to create renderer");
+    assert!(renderer.begin_document("multipage"));
+
+    let source = TiffPages::from_bytes(&tiff_data).expect("Failed to read TIFF");
+    let results: Vec<_> = api
+        .recognize_document(source)
+        .with_renderer(&renderer)
+        .collect::<Result<_, _>>()
+        .expect("Failed to recognize document");
+    assert!(renderer.end_document());
+    assert_eq!(renderer.get_image_num(), 3);
+
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.index, i);
+        assert!(result.page.text().contains("sample text"));
+    }
+
+    let rendered = std::fs::read_to_string(outputbase.with_extension("txt"))
+        .expect("Failed to read rendered text");
+    assert_eq!(rendered.matches("sample text").count(), 3);
+    let _ = std::fs::remove_file(outputbase.with_extension("txt"));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_recognize_bilevel_group4_tiff() {
+    use tesseract_rs::TiffPages;
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("test_images");
+    path.push("sample_text_g4.tif");
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let source = TiffPages::open(&path).expect("Failed to read TIFF");
+    let results: Vec<_> = api
+        .recognize_document(source)
+        .collect::<Result<_, _>>()
+        .expect("Failed to recognize document");
+    assert_eq!(results.len(), 2);
+    for result in &results {
+        assert!(result.page.text().contains("sample text"));
+    }
+}
+
+#[test]
+fn test_recognize_with_monitor_progress_and_cancel() {
+    use std::sync::{Arc, Mutex};
+    use tesseract_rs::{TessMonitor, TesseractError};
+
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    assert!(matches!(
+        api.recognize_with_monitor(&TessMonitor::new()),
+        Err(TesseractError::UninitializedError)
+    ));
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reports);
+    let monitor = TessMonitor::new();
+    monitor.set_progress_callback(move |progress| {
+        sink.lock().unwrap().push(*progress);
+        true
+    });
+    api.recognize_with_monitor(&monitor)
+        .expect("Failed to recognize with monitor");
+
+    let reports = reports.lock().unwrap();
+    assert!(!reports.is_empty());
+    assert!(reports.windows(2).all(|w| w[0].percent <= w[1].percent));
+    for report in reports.iter() {
+        let bbox = report.word_bbox;
+        assert!(bbox.left <= bbox.right && bbox.top <= bbox.bottom);
+        assert!(bbox.right <= width as i32 && bbox.bottom <= height as i32);
+    }
+    let text = api.get_utf8_text().expect("Failed to get text");
+    assert!(text.contains("sample text"));
+
+    let monitor = TessMonitor::new();
+    monitor.set_progress_callback(|_| false);
+    match api.recognize_with_monitor(&monitor) {
+        Err(TesseractError::Cancelled) => {}
+        other => panic!("Expected cancellation, got {:?}", other),
+    }
+    assert!(monitor.is_cancelled());
+
+    let monitor = TessMonitor::new();
+    monitor.cancel();
+    assert!(matches!(
+        api.recognize_with_monitor(&monitor),
+        Err(TesseractError::Cancelled)
+    ));
+
+    // A panicking callback stops recognition and the panic reaches the caller.
+    let monitor = TessMonitor::new();
+    monitor.set_progress_callback(|_| panic!("callback panicked"));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        api.recognize_with_monitor(&monitor)
+    }));
+    assert!(result.is_err());
+    assert!(monitor.is_cancelled());
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("The engine should stay usable after a callback panic");
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_tesseract() {
+    use tesseract_rs::{AsyncTesseract, TesseractError};
+
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let engine = AsyncTesseract::new(api).expect("Failed to start worker");
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("Failed to build runtime");
+    runtime.block_on(async move {
+        engine
+            .set_image(image_data, width as i32, height as i32, 3, 3 * width as i32)
+            .await
+            .expect("Failed to set image");
+
+        // A dropped job is skipped or cancelled without affecting later jobs.
+        drop(engine.get_utf8_text());
+
+        let text = engine.get_utf8_text().await.expect("Failed to perform OCR");
+        assert!(text.contains("sample text"));
+
+        let page = engine.recognize_page().await.expect("Failed to recognize");
+        assert!(page.text().contains("sample text"));
+
+        let cancelled = engine
+            .run_with_monitor(|api, monitor| {
+                monitor.cancel();
+                api.recognize_with_monitor(monitor)
+            })
+            .await;
+        assert!(matches!(cancelled, Err(TesseractError::Cancelled)));
+
+        let name = engine
+            .run(|_| Ok(std::thread::current().name().map(str::to_owned)))
+            .await
+            .expect("Failed to run job");
+        assert_eq!(name.as_deref(), Some("tesseract-worker"));
+
+        // Shutting down lets the queued jobs finish.
+        let pending = engine.get_utf8_text();
+        engine.shutdown().await.expect("Failed to shut down");
+        let text = pending.await.expect("Queued job did not run");
+        assert!(text.contains("sample text"));
+    });
+}
+
+#[test]
+fn test_tesseract_pool() {
+    use tesseract_rs::TesseractPool;
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_variable("tessedit_pageseg_mode", "3")
+        .expect("Failed to set variable");
+
+    let clone = api.try_clone().expect("Failed to clone engine");
+    assert_eq!(
+        clone
+            .get_string_variable("tessedit_pageseg_mode")
+            .expect("Failed to get variable"),
+        "3"
+    );
+
+    assert!(TesseractPool::new(TesseractAPI::new(), 2).is_err());
+    assert!(TesseractPool::new(clone, 0).is_err());
+
+    let pool = TesseractPool::new(api, 2).expect("Failed to create pool");
+    {
+        let first = pool.get().expect("Failed to check out engine");
+        let second = pool.try_get().expect("Failed to create engine");
+        assert!(second.is_some());
+        assert!(pool.try_get().expect("Failed to check pool").is_none());
+        assert!(first
+            .get_init_languages_as_string()
+            .unwrap()
+            .contains("eng"));
+    }
+    assert!(pool.try_get().expect("Failed to check pool").is_some());
+
+    let images: Vec<Pix> = ["sample_text.png", "digits.png", "sample_text.png"]
+        .iter()
+        .map(|name| {
+            let (data, width, height) = load_test_image(name).expect("Failed to load test image");
+            Pix::from_bytes(&data, width as i32, height as i32, 3, 3 * width as i32)
+                .expect("Failed to create Pix")
+        })
+        .collect();
+    let pages = pool.map_pages(&images);
+    assert_eq!(pages.len(), 3);
+    let texts: Vec<String> = pages
+        .into_iter()
+        .map(|page| page.expect("Failed to recognize page").text())
+        .collect();
+    assert!(texts[0].contains("sample text"));
+    assert!(texts[2].contains("sample text"));
+    assert!(texts[1].chars().any(|c| c.is_ascii_digit()));
+}
+
+#[test]
+fn test_pool_restores_variables() {
+    use tesseract_rs::{TesseractBuilder, TesseractPool};
+
+    let tessdata_dir = get_tessdata_dir();
+    let builder = TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .language("eng")
+        .variable("tessedit_char_whitelist", "0123456789");
+    let pool = TesseractPool::from_builder(&builder, 1).expect("Failed to create pool");
+    {
+        let engine = pool.get().expect("Failed to check out engine");
+        engine
+            .set_variable("tessedit_char_whitelist", "abc")
+            .expect("Failed to set variable");
+        engine.set_dpi(300).expect("Failed to set DPI");
+    }
+
+    let engine = pool.get().expect("Failed to check out engine");
+    assert_eq!(
+        engine
+            .get_string_variable("tessedit_char_whitelist")
+            .unwrap(),
+        "0123456789"
+    );
+    assert_eq!(engine.get_int_variable("user_defined_dpi").unwrap(), 0);
+
+    // An engine re-initialized through the guard is not handed to the next borrower.
+    engine
+        .init(tessdata_dir.to_str().unwrap(), "tur")
+        .expect("Failed to re-initialize engine");
+    drop(engine);
+    let engine = pool.get().expect("Failed to check out engine");
+    assert_eq!(engine.get_init_languages_as_string().unwrap(), "eng");
+
+    // Nor is an engine ended through the guard.
+    engine.end().expect("Failed to end engine");
+    assert!(engine.oem().is_err());
+    drop(engine);
+    let engine = pool.get().expect("Failed to check out engine");
+    assert_eq!(engine.get_init_languages_as_string().unwrap(), "eng");
+    assert!(engine.oem().is_ok());
+}
+
+#[test]
+fn test_tesseract_builder() {
+    use tesseract_rs::{TessOcrEngineMode, TesseractBuilder, TesseractPool};
+
+    let tessdata_dir = get_tessdata_dir();
+    let builder = TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .languages(["eng", "tur"])
+        .oem(TessOcrEngineMode::OEM_LSTM_ONLY)
+        .init_variable("load_system_dawg", "0")
+        .variable("tessedit_char_whitelist", "0123456789");
+    let api = builder.build().expect("Failed to build engine");
+    assert_eq!(api.get_init_languages_as_string().unwrap(), "eng+tur");
+    assert_eq!(
+        api.get_string_variable("tessedit_char_whitelist").unwrap(),
+        "0123456789"
+    );
+
+    let clone = api.try_clone().expect("Failed to clone engine");
+    assert_eq!(clone.get_init_languages_as_string().unwrap(), "eng+tur");
+    assert!(!clone.get_bool_variable("load_system_dawg").unwrap());
+    assert_eq!(
+        clone
+            .get_string_variable("tessedit_char_whitelist")
+            .unwrap(),
+        "0123456789"
+    );
+
+    let pool = TesseractPool::from_builder(&builder, 2).expect("Failed to create pool");
+    let _first = pool.get().expect("Failed to check out engine");
+    let second = pool.get().expect("Failed to create engine");
+    assert_eq!(second.get_init_languages_as_string().unwrap(), "eng+tur");
+
+    let traineddata =
+        std::fs::read(tessdata_dir.join("eng.traineddata")).expect("Failed to read traineddata");
+    let api = TesseractBuilder::new()
+        .language("eng")
+        .traineddata(traineddata)
+        .build()
+        .expect("Failed to build engine from memory");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    assert!(api.get_utf8_text().unwrap().contains("sample text"));
+    let clone = api.try_clone().expect("Failed to clone in-memory engine");
+    assert_eq!(clone.get_init_languages_as_string().unwrap(), "eng");
+
+    assert!(TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .language("no_such_language")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_ocr_engine_mode() {
+    use tesseract_rs::{TessOcrEngineMode, TesseractBuilder, TesseractError};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .language("eng")
+        .oem(TessOcrEngineMode::OEM_LSTM_ONLY)
+        .build()
+        .expect("Failed to build engine");
+    assert_eq!(api.oem().unwrap(), TessOcrEngineMode::OEM_LSTM_ONLY);
+
+    // The default mode resolves to the engine available in the traineddata.
+    let api = TesseractAPI::new();
+    assert!(matches!(api.oem(), Err(TesseractError::UninitializedError)));
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    assert_ne!(api.oem().unwrap(), TessOcrEngineMode::OEM_DEFAULT);
+
+    // The bundled tessdata_best models contain no legacy engine components.
+    let result = TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .language("eng")
+        .oem(TessOcrEngineMode::OEM_TESSERACT_ONLY)
+        .build();
+    match result {
+        Err(TesseractError::LegacyEngineUnavailable { language }) => assert_eq!(language, "eng"),
+        Err(e) => panic!("Expected LegacyEngineUnavailable, got {}", e),
+        Ok(_) => panic!("Expected legacy engine initialization to fail"),
+    }
+}
+
+#[test]
+fn test_error_context() {
+    use tesseract_rs::TesseractError;
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    match api.init(tessdata_dir.to_str().unwrap(), "xyz") {
+        Err(TesseractError::InitError {
+            datapath,
+            language,
+            output,
+            ..
+        }) => {
+            assert_eq!(datapath.as_deref(), tessdata_dir.to_str());
+            assert_eq!(language, "xyz");
+            assert!(
+                output.contains("xyz"),
+                "Captured output should mention the language: {}",
+                output
+            );
+        }
+        Err(e) => panic!("Expected InitError, got {}", e),
+        Ok(_) => panic!("Expected initialization with a missing language to fail"),
+    }
+
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    match api.set_variable("no_such_variable", "1") {
+        Err(TesseractError::SetVariableError { name, value }) => {
+            assert_eq!(name, "no_such_variable");
+            assert_eq!(value, "1");
+        }
+        other => panic!("Expected SetVariableError, got {:?}", other),
+    }
+    assert!(matches!(
+        api.set_variable("tessedit_char_whitelist", "a\0b"),
+        Err(TesseractError::InvalidString { .. })
+    ));
+    assert!(matches!(
+        api.read_config_file("/nonexistent/tesseract-rs.config"),
+        Err(TesseractError::FileError { .. })
+    ));
+}
+
+#[test]
+fn test_typed_parameters() {
+    use tesseract_rs::{ParameterType, ParameterValue, TesseractBuilder, TesseractError};
+
+    let tessdata_dir = get_tessdata_dir();
+    let words_file = std::env::temp_dir().join("tesseract-rs-user-words.txt");
+    std::fs::write(&words_file, "tesseract\n").expect("Failed to write user words");
+    let api = TesseractBuilder::new()
+        .datapath(&tessdata_dir)
+        .language("eng")
+        .user_words_file(&words_file)
+        .build()
+        .expect("Failed to build engine");
+    assert_eq!(
+        api.get_string_variable("user_words_file").unwrap(),
+        words_file.to_string_lossy()
+    );
+
+    let parameters = api.parameters().expect("Failed to list parameters");
+    let whitelist = parameters
+        .iter()
+        .find(|p| p.name == "tessedit_char_whitelist")
+        .expect("Whitelist parameter should be listed");
+    assert_eq!(whitelist.parameter_type(), ParameterType::String);
+    assert!(!whitelist.description.is_empty());
+    assert!(parameters
+        .iter()
+        .any(|p| p.name == "user_defined_dpi" && p.parameter_type() == ParameterType::Int));
+
+    api.set_char_whitelist("0123456789").unwrap();
+    api.set_char_blacklist("O").unwrap();
+    api.set_preserve_interword_spaces(true).unwrap();
+    api.set_dpi(300).unwrap();
+    let parameters = api.parameters().expect("Failed to list parameters");
+    let whitelist = parameters
+        .iter()
+        .find(|p| p.name == "tessedit_char_whitelist")
+        .expect("Whitelist parameter should be listed");
+    assert_eq!(whitelist.value, ParameterValue::from("0123456789"));
+    assert_eq!(whitelist.default, ParameterValue::from(""));
+    assert!(!whitelist.is_default());
+    let dpi = parameters
+        .iter()
+        .find(|p| p.name == "user_defined_dpi")
+        .expect("DPI parameter should be listed");
+    assert_eq!(dpi.value, ParameterValue::Int(300));
+    assert_eq!(dpi.default, ParameterValue::Int(0));
+    assert!(!dpi.is_default());
+    assert_eq!(
+        api.get_string_variable("tessedit_char_whitelist").unwrap(),
+        "0123456789"
+    );
+    assert_eq!(
+        api.get_string_variable("tessedit_char_blacklist").unwrap(),
+        "O"
+    );
+    assert!(api.get_bool_variable("preserve_interword_spaces").unwrap());
+    assert_eq!(api.get_int_variable("user_defined_dpi").unwrap(), 300);
+
+    api.set_parameter("textord_min_xheight", 12).unwrap();
+    assert_eq!(
+        api.get_parameter("textord_min_xheight").unwrap(),
+        ParameterValue::Int(12)
+    );
+    assert!(matches!(
+        api.set_parameter("tessedit_char_whitelsit", "0123456789"),
+        Err(TesseractError::UnknownParameter { .. })
+    ));
+    match api.set_parameter("user_defined_dpi", "300") {
+        Err(TesseractError::ParameterTypeMismatch {
+            expected, actual, ..
+        }) => {
+            assert_eq!(expected, ParameterType::String);
+            assert_eq!(actual, ParameterType::Int);
+        }
+        other => panic!("Expected ParameterTypeMismatch, got {:?}", other),
+    }
+    assert!(matches!(
+        api.get_int_variable("tessedit_char_whitelist"),
+        Err(TesseractError::ParameterTypeMismatch { .. })
+    ));
+    let _ = std::fs::remove_file(words_file);
+}
+
+#[test]
+fn test_parameters_before_init() {
+    use tesseract_rs::{ParameterValue, TesseractError};
+
+    // Parameters are readable and settable on an engine that was never initialized.
+    let api = TesseractAPI::new();
+    assert_eq!(api.get_int_variable("user_defined_dpi").unwrap(), 0);
+    api.set_parameter("user_defined_dpi", 300)
+        .expect("Failed to set parameter");
+    assert_eq!(
+        api.get_parameter("user_defined_dpi").unwrap(),
+        ParameterValue::Int(300)
+    );
+    assert!(matches!(
+        api.set_parameter("user_defined_dpi", "300"),
+        Err(TesseractError::ParameterTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        TesseractAPI::new().get_parameter("no_such_parameter"),
+        Err(TesseractError::UnknownParameter { .. })
+    ));
+}
+
+#[test]
+fn test_export_import_parameters() {
+    use tesseract_rs::ParameterSnapshot;
+
+    let tessdata_dir = get_tessdata_dir();
+    let config_file = std::env::temp_dir().join("tesseract-rs-export.config");
+    std::fs::write(
+        &config_file,
+        "tessedit_char_whitelist 0123456789\ntextord_min_xheight 12\n",
+    )
+    .expect("Failed to write config file");
+
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.read_config_file(config_file.to_str().unwrap())
+        .expect("Failed to read config file");
+    api.set_debug_variable("classify_debug_level", "1")
+        .expect("Failed to set debug variable");
+
+    let snapshot = api
+        .export_parameters()
+        .expect("Failed to export parameters");
+    assert_eq!(snapshot.get("tessedit_char_whitelist"), Some("0123456789"));
+    assert_eq!(snapshot.get("textord_min_xheight"), Some("12"));
+    assert_eq!(snapshot.get("classify_debug_level"), Some("1"));
+
+    let config = snapshot.to_config_string();
+    assert_eq!(ParameterSnapshot::from_config_str(&config), snapshot);
+
+    let restored = TesseractAPI::new();
+    restored
+        .init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    restored
+        .import_parameters(&ParameterSnapshot::from_config_str(&config))
+        .expect("Failed to import parameters");
+    assert_eq!(
+        restored
+            .get_string_variable("tessedit_char_whitelist")
+            .unwrap(),
+        "0123456789"
+    );
+    assert_eq!(
+        restored.get_int_variable("classify_debug_level").unwrap(),
+        1
+    );
+
+    // Imported parameters are part of the configuration replayed by clones.
+    let clone = restored.try_clone().expect("Failed to clone engine");
+    assert_eq!(clone.get_int_variable("textord_min_xheight").unwrap(), 12);
+    let _ = std::fs::remove_file(config_file);
+}
+
+#[test]
+fn test_hocr_reader_matches_recognition() {
+    use tesseract_rs::HocrDocument;
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+
+    let page = api.recognize_page().expect("Failed to recognize page");
+    let hocr = api.get_hocr_text(0).expect("Failed to get hOCR text");
+    let document = HocrDocument::parse(&hocr).expect("Failed to parse hOCR");
+    assert_eq!(document.pages.len(), 1);
+    let hocr_page = &document.pages[0];
+    assert_eq!(hocr_page.bbox.right, width as i32);
+    assert_eq!(hocr_page.bbox.bottom, height as i32);
+    assert!(hocr_page
+        .areas
+        .iter()
+        .flat_map(|area| &area.paragraphs)
+        .flat_map(|paragraph| &paragraph.lines)
+        .all(|line| line.x_size.is_some()));
+
+    let reloaded = hocr_page.to_page();
+    let expected: Vec<_> = page.words().map(|w| (w.text.clone(), w.bbox)).collect();
+    let actual: Vec<_> = reloaded.words().map(|w| (w.text.clone(), w.bbox)).collect();
+    assert_eq!(actual, expected);
+    for (reloaded_word, word) in reloaded.words().zip(page.words()) {
+        assert_eq!(reloaded_word.confidence, word.confidence.trunc());
+    }
+}
+
+#[test]
+fn test_tsv_and_alto_round_trip() {
+    use tesseract_rs::{AltoLayout, TsvLevel, TsvRow};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    for name in ["sample_text.png", "digits.png", "multilang_sample.png"] {
+        let (image_data, width, height) = load_test_image(name).expect("Failed to load image");
+        api.set_image(
+            &image_data,
+            width as i32,
+            height as i32,
+            3,
+            3 * width as i32,
+        )
+        .expect("Failed to set image");
+
+        let page = api.recognize_page().expect("Failed to recognize page");
+        let expected: Vec<_> = page
+            .words()
+            .filter(|w| !w.text.is_empty())
+            .map(|w| (w.text.clone(), w.bbox))
+            .collect();
+        assert!(!expected.is_empty(), "No words recognized in {}", name);
+
+        let tsv = api.get_tsv_text(0).expect("Failed to get TSV text");
+        let rows = TsvRow::parse(&tsv).expect("Failed to parse TSV");
+        assert_eq!(rows[0].level, TsvLevel::Page);
+        assert_eq!(rows[0].bbox().right, width as i32);
+        let words: Vec<_> = rows
+            .iter()
+            .filter(|row| row.level == TsvLevel::Word)
+            .collect();
+        assert_eq!(
+            words
+                .iter()
+                .map(|row| (row.text.clone(), row.bbox()))
+                .collect::<Vec<_>>(),
+            expected,
+            "TSV words differ for {}",
+            name
+        );
+        assert!(words.iter().all(|row| row.confidence.is_some()));
+        let written: String = rows.iter().map(|row| row.to_line() + "\n").collect();
+        assert_eq!(TsvRow::parse(&written).unwrap(), rows);
+
+        let alto = api.get_alto_text(0).expect("Failed to get ALTO text");
+        let layout = AltoLayout::parse(&alto).expect("Failed to parse ALTO");
+        assert_eq!(layout.pages.len(), 1);
+        assert_eq!(layout.pages[0].width, width as i32);
+        assert_eq!(
+            layout.pages[0]
+                .strings()
+                .map(|string| (string.content.clone(), string.bbox))
+                .collect::<Vec<_>>(),
+            expected,
+            "ALTO strings differ for {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_box_files() {
+    use tesseract_rs::{BoxFile, BoxFormat};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    let page = api.recognize_page().expect("Failed to recognize page");
+
+    let char_boxes = BoxFile::parse(&api.get_box_text(0).unwrap()).unwrap();
+    assert_eq!(char_boxes.format, BoxFormat::Char);
+    assert_eq!(char_boxes, BoxFile::from_page(&page, height as i32, 0));
+    let first_symbol = &page.words().next().unwrap().symbols[0];
+    assert_eq!(char_boxes.entries[0].bbox(height as i32), first_symbol.bbox);
+
+    let lstm_boxes = BoxFile::parse(&api.get_lstm_box_text(0).unwrap()).unwrap();
+    assert_eq!(lstm_boxes.format, BoxFormat::Lstm);
+    assert!(lstm_boxes.entries.last().unwrap().is_line_end());
+
+    let word_str_boxes = BoxFile::parse(&api.get_word_str_box_text(0).unwrap()).unwrap();
+    assert_eq!(word_str_boxes.format, BoxFormat::WordStr);
+    assert!(word_str_boxes.entries[0].text.contains("sample"));
+
+    let path = std::env::temp_dir().join("tesseract-rs-test.box");
+    lstm_boxes.write(&path).expect("Failed to write box file");
+    assert_eq!(BoxFile::read(&path).unwrap(), lstm_boxes);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_symbol_choices() {
+    use tesseract_rs::{TessLstmChoiceMode, TessPageIteratorLevel};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_lstm_choice_mode(TessLstmChoiceMode::LSTM_CHOICE_SYMBOLS)
+        .expect("Failed to set lstm_choice_mode");
+    assert_eq!(api.get_int_variable("lstm_choice_mode").unwrap(), 2);
+
+    let (image_data, width, height) =
+        load_test_image("digits.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    api.recognize().expect("Failed to recognize");
+
+    let iterator = api.get_iterator().expect("Failed to get iterator");
+    let mut symbols = 0;
+    loop {
+        let symbol = iterator
+            .get_utf8_text(TessPageIteratorLevel::RIL_SYMBOL)
+            .expect("Failed to get symbol text");
+        let choices: Vec<(String, f32)> =
+            iterator.choices().expect("Failed to get choices").collect();
+        assert!(!choices.is_empty(), "No choices for symbol {}", symbol);
+        assert!(choices.iter().any(|(text, _)| *text == symbol));
+        assert!(choices
+            .iter()
+            .all(|(_, confidence)| (0.0..=100.0).contains(confidence)));
+        symbols += 1;
+        if !iterator.next(TessPageIteratorLevel::RIL_SYMBOL).unwrap() {
+            break;
+        }
+    }
+    assert!(symbols > 0);
+}
+
+#[test]
+fn test_timestep_choices() {
+    use tesseract_rs::{TessLstmChoiceMode, TessPageIteratorLevel};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_lstm_choice_mode(TessLstmChoiceMode::LSTM_CHOICE_TIMESTEPS)
+        .expect("Failed to set lstm_choice_mode");
+
+    let (image_data, width, height) =
+        load_test_image("digits.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    api.recognize().expect("Failed to recognize");
+
+    let iterator = api.get_iterator().expect("Failed to get iterator");
+    loop {
+        let word = iterator
+            .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+            .expect("Failed to get word text");
+        let symbols = iterator
+            .symbol_timestep_choices()
+            .expect("Failed to get symbol timesteps");
+        let timesteps = iterator
+            .word_timestep_choices()
+            .expect("Failed to get word timesteps");
+        assert!(!timesteps.is_empty(), "No timesteps for word {}", word);
+        assert_eq!(timesteps.len(), symbols.iter().map(Vec::len).sum::<usize>());
+        assert!(timesteps
+            .iter()
+            .flatten()
+            .all(|(_, probability)| probability.is_finite() && *probability >= 0.0));
+        if !iterator.next(TessPageIteratorLevel::RIL_WORD).unwrap() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn test_level_iterators() {
+    use tesseract_rs::{TessPageIteratorLevel, WordView};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    let page = api.recognize_page().expect("Failed to recognize page");
+
+    let iterator = api.get_iterator().expect("Failed to get iterator");
+    // Moving the iterator first must not make the adapters skip the first word.
+    iterator.next(TessPageIteratorLevel::RIL_WORD).unwrap();
+    let words: Vec<WordView> = iterator
+        .words()
+        .collect::<tesseract_rs::Result<_>>()
+        .expect("Failed to read words");
+    assert_eq!(words.len(), page.words().count());
+    for (view, word) in words.iter().zip(page.words()) {
+        assert_eq!(view.text, word.text);
+        assert_eq!(view.bbox, word.bbox);
+        assert_eq!(view.confidence, word.confidence);
+    }
+
+    let lines = iterator.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), page.lines().count());
+    assert!(lines.iter().all(|line| line.is_ok()));
+    assert_eq!(iterator.blocks().count(), page.blocks.len());
+    let symbols = iterator.symbols().filter_map(|symbol| symbol.ok()).count();
+    assert_eq!(
+        symbols,
+        page.words().map(|word| word.symbols.len()).sum::<usize>()
+    );
+}
+
+#[test]
+fn test_level_iterators_skip_image_blocks() {
+    use tesseract_rs::{TessPageSegMode, WordView};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    api.set_page_seg_mode(TessPageSegMode::PSM_AUTO)
+        .expect("Failed to set page segmentation mode");
+
+    // With the figure first and last, the image block starts and ends the page.
+    for figure_above in [true, false] {
+        let (image_data, width, height) = load_test_image_with_figure(figure_above);
+        api.set_image(
+            &image_data,
+            width as i32,
+            height as i32,
+            3,
+            3 * width as i32,
+        )
+        .expect("Failed to set image");
+        let page = api.recognize_page().expect("Failed to recognize page");
+        assert!(page.blocks.iter().any(|block| block.paragraphs.is_empty()));
+
+        let iterator = api.get_iterator().expect("Failed to get iterator");
+        let words: Vec<WordView> = iterator
+            .words()
+            .collect::<tesseract_rs::Result<_>>()
+            .expect("Failed to read words");
+        assert!(words.iter().any(|word| word.text == "sample"));
+        assert_eq!(words.len(), page.words().count());
+        for (view, word) in words.iter().zip(page.words()) {
+            assert_eq!(view.text, word.text);
+            assert_eq!(view.bbox, word.bbox);
+        }
+        assert_eq!(iterator.blocks().count(), page.blocks.len());
+        assert_eq!(
+            iterator
+                .lines()
+                .collect::<tesseract_rs::Result<Vec<_>>>()
+                .unwrap()
+                .len(),
+            page.lines().count()
+        );
+    }
+}
+
+#[test]
+fn test_get_iterators_read_recognized_results() {
+    use tesseract_rs::TessPageIteratorLevel;
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+
+    let (page_iter, result_iter) = api.get_iterators().expect("Failed to get iterators");
+    let word = result_iter
+        .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+        .expect("Failed to read the first word");
+    assert!(!word.is_empty());
+    assert_eq!(
+        page_iter
+            .bounding_box(TessPageIteratorLevel::RIL_WORD)
+            .expect("Failed to read the word box"),
+        result_iter
+            .get_bounding_box(TessPageIteratorLevel::RIL_WORD)
+            .unwrap()
+    );
+    assert!(api
+        .get_utf8_text()
+        .expect("Failed to get text")
+        .contains(&word));
+}
+
+#[test]
+fn test_iterator_invalidation() {
+    use tesseract_rs::{TessPageIteratorLevel, TesseractError};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let set_image = || {
+        api.set_image(
+            &image_data,
+            width as i32,
+            height as i32,
+            3,
+            3 * width as i32,
+        )
+        .expect("Failed to set image")
+    };
+    set_image();
+    api.recognize().expect("Failed to recognize");
+
+    let iterator = api.get_iterator().expect("Failed to get iterator");
+    // Reading the text reuses the existing results.
+    let text = api.get_utf8_text().expect("Failed to get text");
+    let first_word = iterator
+        .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+        .expect("Iterator was invalidated by get_utf8_text");
+    assert!(text.contains(&first_word));
+
+    set_image();
+    assert!(matches!(
+        iterator.get_utf8_text(TessPageIteratorLevel::RIL_WORD),
+        Err(TesseractError::IteratorInvalidated)
+    ));
+    assert!(matches!(
+        iterator.next(TessPageIteratorLevel::RIL_WORD),
+        Err(TesseractError::IteratorInvalidated)
+    ));
+    assert!(matches!(
+        iterator.words().next(),
+        Some(Err(TesseractError::IteratorInvalidated))
+    ));
+
+    // Orientation detection clears the results whether or not it succeeds.
+    api.recognize().expect("Failed to recognize");
+    let iterator = api.get_iterator().expect("Failed to get iterator");
+    let _ = api.detect_os();
+    assert!(matches!(
+        iterator.get_utf8_text(TessPageIteratorLevel::RIL_WORD),
+        Err(TesseractError::IteratorInvalidated)
+    ));
+
+    api.recognize().expect("Failed to recognize");
+    let layout = api.analyse_layout().expect("Failed to analyse layout");
+    assert!(layout
+        .bounding_box(TessPageIteratorLevel::RIL_BLOCK)
+        .is_ok());
+    api.clear().expect("Failed to clear");
+    assert!(matches!(
+        layout.next(TessPageIteratorLevel::RIL_BLOCK),
+        Err(TesseractError::IteratorInvalidated)
+    ));
+}
+
+#[test]
+fn test_mutable_iterator_edits() {
+    use tesseract_rs::{TessPageIteratorLevel, TesseractError};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    api.recognize().expect("Failed to recognize");
+    let word_count = |api: &TesseractAPI| {
+        api.get_iterator()
+            .expect("Failed to get iterator")
+            .words()
+            .count()
+    };
+    let words_before = word_count(&api);
+    assert!(words_before >= 2, "Expected at least two words");
+
+    {
+        let reader = api.get_iterator().expect("Failed to get iterator");
+        let iterator = api
+            .get_mutable_iterator()
+            .expect("Failed to get mutable iterator");
+        assert!(matches!(
+            iterator.replace_word("two words"),
+            Err(TesseractError::InvalidParameterError)
+        ));
+        iterator
+            .replace_word("Replacement")
+            .expect("Failed to replace word");
+        assert_eq!(
+            iterator
+                .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+                .unwrap(),
+            "Replacement"
+        );
+        assert!(matches!(
+            reader.get_utf8_text(TessPageIteratorLevel::RIL_WORD),
+            Err(TesseractError::IteratorInvalidated)
+        ));
+
+        iterator.begin().unwrap();
+        assert!(iterator.next(TessPageIteratorLevel::RIL_WORD).unwrap());
+        let deleted = iterator
+            .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+            .unwrap();
+        let has_next = iterator.delete_word().expect("Failed to delete word");
+        // The word is removed right away and the iterator stays usable.
+        assert_eq!(word_count(&api), words_before - 1);
+        if has_next {
+            assert_ne!(
+                iterator
+                    .get_utf8_text(TessPageIteratorLevel::RIL_WORD)
+                    .unwrap(),
+                deleted
+            );
+        }
+        assert!(!api.get_utf8_text().unwrap().contains(&deleted));
+    }
+
+    assert_eq!(word_count(&api), words_before - 1);
+    let text = api.get_utf8_text().expect("Failed to get text");
+    assert!(text.starts_with("Replacement"));
+    let hocr = api.get_hocr_text(0).expect("Failed to get hOCR");
+    assert!(hocr.contains(">Replacement<"));
+}
+
+#[test]
+fn test_composite_renderer() {
+    use tesseract_rs::TessResultRenderer;
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    api.set_input_name("sample_text.png")
+        .expect("Failed to set input name");
+
+    let outputbase = std::env::temp_dir().join("tesseract_rs_composite");
+    let base = outputbase.to_str().unwrap();
+    let renderer = TessResultRenderer::composite([
+        TessResultRenderer::new_text_renderer(base).unwrap(),
+        TessResultRenderer::new_hocr_renderer(base).unwrap(),
+        TessResultRenderer::new_alto_renderer(base).unwrap(),
+        TessResultRenderer::new_tsv_renderer(base).unwrap(),
+        TessResultRenderer::new_unlv_renderer(base).unwrap(),
+        TessResultRenderer::new_lstm_box_renderer(base).unwrap(),
+    ])
+    .expect("Failed to combine renderers");
+    assert_eq!(renderer.get_extension().unwrap(), "txt");
+    assert!(renderer.begin_document("composite"));
+    assert!(renderer.add_image(&api));
+    assert!(renderer.end_document());
+
+    for extension in ["txt", "hocr", "xml", "tsv", "unlv", "box"] {
+        let path = PathBuf::from(format!("{}.{}", base, extension));
+        let rendered = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Missing {} output", extension));
+        assert!(!rendered.trim().is_empty(), "Empty {} output", extension);
+    }
+
+    assert!(TessResultRenderer::composite(Vec::new()).is_err());
+}
+
+#[test]
+fn test_render_to_memory() {
+    use tesseract_rs::{OutputBuffer, TessResultRenderer};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    api.set_input_name("sample_text.png")
+        .expect("Failed to set input name");
+
+    let text = OutputBuffer::new();
+    let hocr = OutputBuffer::new();
+    let pdf = OutputBuffer::new();
+    let renderer = TessResultRenderer::composite([
+        TessResultRenderer::new_text_renderer_to_writer(text.clone()).unwrap(),
+        TessResultRenderer::new_hocr_renderer_to_writer(hocr.clone()).unwrap(),
+        TessResultRenderer::new_pdf_renderer_to_writer(
+            tessdata_dir.to_str().unwrap(),
+            false,
+            pdf.clone(),
+        )
+        .unwrap(),
+    ])
+    .expect("Failed to combine renderers");
+    assert!(renderer.begin_document("memory"));
+    assert!(renderer.add_image(&api));
+    assert!(renderer.end_document());
+    drop(renderer);
+
+    let expected = api.get_utf8_text().expect("Failed to get text");
+    assert_eq!(String::from_utf8(text.take()).unwrap(), expected);
+    let hocr = String::from_utf8(hocr.take()).unwrap();
+    assert!(hocr.contains("ocrx_word"));
+    assert!(hocr.trim_end().ends_with("</html>"));
+    let pdf = pdf.take();
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(pdf.windows(5).any(|window| window == b"%%EOF"));
+}
+
+#[test]
+fn test_process_pages_with_renderer() {
+    use tesseract_rs::{OutputBuffer, TessResultRenderer};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    // Leptonica is built without image codecs, so hand it a PPM file.
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let path = std::env::temp_dir().join("tesseract-rs-process-pages.ppm");
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.extend_from_slice(&image_data);
+    std::fs::write(&path, ppm).expect("Failed to write image");
+
+    let text = OutputBuffer::new();
+    let renderer = TessResultRenderer::new_text_renderer_to_writer(text.clone())
+        .expect("Failed to create renderer");
+    api.process_pages_with(path.to_str().unwrap(), None, 0, &renderer)
+        .expect("Failed to process pages");
+    assert_eq!(renderer.get_image_num(), 0);
+    let text = String::from_utf8(text.take()).unwrap();
+    assert!(text.contains("sample text"), "Unexpected text: {}", text);
+
+    let missing = std::env::temp_dir().join("tesseract-rs-missing.ppm");
+    assert!(api
+        .process_pages_with(missing.to_str().unwrap(), None, 0, &renderer)
+        .is_err());
+}
+
+#[test]
+fn test_document_session() {
+    use tesseract_rs::{OutputBuffer, TessResultRenderer};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    let set_image = || {
+        api.set_image(
+            &image_data,
+            width as i32,
+            height as i32,
+            3,
+            3 * width as i32,
+        )
+        .expect("Failed to set image")
+    };
+
+    let hocr = OutputBuffer::new();
+    let renderer = TessResultRenderer::new_hocr_renderer_to_writer(hocr.clone())
+        .expect("Failed to create renderer");
+    let mut document = api
+        .begin_document(&renderer, "session")
+        .expect("Failed to begin document");
+    set_image();
+    // The page is recognized by the session.
+    document.add_page().expect("Failed to add page");
+    set_image();
+    api.recognize().expect("Failed to recognize");
+    document.add_page().expect("Failed to add page");
+    assert_eq!(document.page_count(), 2);
+    document.finish().expect("Failed to finish document");
+    assert_eq!(renderer.get_image_num(), 1);
+    let rendered = String::from_utf8(hocr.take()).unwrap();
+    assert_eq!(rendered.matches("class='ocr_page'").count(), 2);
+    assert!(rendered.trim_end().ends_with("</html>"));
+
+    // A session that is not finished ends the document when dropped.
+    {
+        let mut document = api
+            .begin_document(&renderer, "dropped")
+            .expect("Failed to begin document");
+        set_image();
+        document.add_page().expect("Failed to add page");
+    }
+    let rendered = String::from_utf8(hocr.take()).unwrap();
+    assert!(rendered.trim_end().ends_with("</html>"));
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn test_pdf_writer_text_layer() {
+    use tesseract_rs::{PdfImage, PdfImageCompression, PdfMetadata, PdfOptions, PdfWriter};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+    let (image_data, width, height) =
+        load_test_image("sample_text.png").expect("Failed to load test image");
+    api.set_image(
+        &image_data,
+        width as i32,
+        height as i32,
+        3,
+        3 * width as i32,
+    )
+    .expect("Failed to set image");
+    let page = api.recognize_page().expect("Failed to recognize page");
+    let image = PdfImage::new(&image_data, width, height, 3, 300).expect("Failed to wrap image");
+
+    let options = PdfOptions {
+        image_compression: PdfImageCompression::Flate,
+        compress_text: false,
+        metadata: PdfMetadata {
+            title: Some("Sample".to_owned()),
+            ..PdfMetadata::default()
+        },
+        ..PdfOptions::default()
+    };
+    let mut writer = PdfWriter::new(Vec::new(), options.clone()).expect("Failed to start PDF");
+    writer.add_page(&image, &page).expect("Failed to add page");
+    let document = writer.finish().expect("Failed to finish PDF");
+
+    let contains = |haystack: &[u8], needle: &[u8]| {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    };
+    assert!(document.starts_with(b"%PDF-"));
+    assert!(contains(&document, b"/Title (Sample)"));
+    assert!(contains(&document, b"3 Tr"));
+    // The text layer encodes each word as UTF-16 code units.
+    let word = page.words().next().expect("No words recognized");
+    let hex: String = word
+        .text
+        .encode_utf16()
+        .map(|unit| format!("{:04X}", unit))
+        .collect();
+    assert!(contains(&document, format!("<{}", hex).as_bytes()));
+
+    let mut writer = PdfWriter::append(&document, Vec::new(), options).expect("Failed to read PDF");
+    writer
+        .add_page(&image, &page)
+        .expect("Failed to append page");
+    let updated = writer.finish().expect("Failed to finish update");
+    assert!(updated.starts_with(&document));
+    assert!(contains(&updated, b"/Count 2"));
+}
+
+#[test]
+fn test_preprocess_pipeline() {
+    use tesseract_rs::{GrayImage, Pipeline};
+
+    let tessdata_dir = get_tessdata_dir();
+    let api = TesseractAPI::new();
+    api.init(tessdata_dir.to_str().unwrap(), "eng")
+        .expect("Failed to initialize Tesseract");
+
+    let (image_data, width, height) = load_test_image("sample_text.png").unwrap();
+    let image = GrayImage::from_pixels(&image_data, width, height, 3, 3 * width)
+        .expect("Failed to convert image")
+        .with_dpi(300);
+    let pipeline = Pipeline::new()
+        .normalize_contrast(1.0)
+        .deskew()
+        .sauvola(31, 0.34)
+        .despeckle(2);
+    let output = pipeline
+        .apply(&api, image)
+        .expect("Failed to preprocess image");
+    assert_eq!((output.width(), output.height()), (width, height));
+    assert!(output
+        .data()
+        .iter()
+        .all(|&value| value == 0 || value == 255));
+
+    let text = api.get_utf8_text().expect("Failed to perform OCR");
+    assert!(text.to_lowercase().contains("sample"));
+}